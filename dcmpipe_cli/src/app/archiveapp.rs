@@ -1,6 +1,18 @@
-use std::path::PathBuf;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use walkdir::WalkDir;
+
+use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmobject::{DicomNode, DicomRoot};
+use dcmpipe_lib::core::parser::util::parse_into_object;
+use dcmpipe_lib::core::parser::{Parser, ParserBuilder};
+use dcmpipe_lib::core::source::for_each_entry;
+use dcmpipe_lib::core::tagstop::TagStop;
 
 use crate::app::CommandApplication;
 
@@ -16,12 +28,92 @@ impl ArchiveApp {
             destination,
         }
     }
+
+    /// Copies the dataset into the folder of its series, named by its SOP Instance UID. Datasets
+    /// which aren't DICOM or are missing either UID are skipped.
+    fn archive_dataset(
+        &self,
+        parser_builder: &ParserBuilder<'_>,
+        name: &str,
+        dataset: &[u8],
+    ) -> Result<bool> {
+        let mut parser: Parser<'_, &[u8]> = parser_builder.build(dataset);
+        let dcm_root: DicomRoot<'_> = match parse_into_object(&mut parser) {
+            Ok(Some(dcm_root)) => dcm_root,
+            _ => {
+                println!("Skipping, not DICOM: {}", name);
+                return Ok(false);
+            }
+        };
+
+        let series_uid: Option<String> = get_uid(&dcm_root, tags::SeriesInstanceUID.tag);
+        let sop_uid: Option<String> = get_uid(&dcm_root, tags::SOPInstanceUID.tag);
+        let (series_uid, sop_uid): (String, String) = match (series_uid, sop_uid) {
+            (Some(series_uid), Some(sop_uid)) => (series_uid, sop_uid),
+            _ => {
+                println!(
+                    "Skipping, no valid SeriesInstanceUID and SOPInstanceUID: {}",
+                    name
+                );
+                return Ok(false);
+            }
+        };
+
+        let series_folder: PathBuf = self.destination.join(series_uid);
+        fs::create_dir_all(&series_folder)?;
+        fs::write(series_folder.join(format!("{}.dcm", sop_uid)), dataset)?;
+        Ok(true)
+    }
 }
 
 impl CommandApplication for ArchiveApp {
     fn run(&mut self) -> Result<()> {
-        // use fields to stop getting dead code warnings
-        format!("{:?}, {:?}", self.source, self.destination);
-        unimplemented!()
+        // only the UIDs are needed to determine where the dataset is archived to
+        let parser_builder: ParserBuilder<'_> = ParserBuilder::default()
+            .dictionary(&STANDARD_DICOM_DICTIONARY)
+            .tagstop(TagStop::AfterTag(tags::SeriesInstanceUID.tag.into()));
+
+        let walkdir = WalkDir::new(&self.source)
+            .into_iter()
+            .filter_map(|e| e.ok());
+
+        let mut archived: usize = 0;
+        for entry in walkdir {
+            if !entry.metadata()?.file_type().is_file() {
+                continue;
+            }
+
+            // entries of archives are each archived as their own dataset
+            let path: &Path = entry.path();
+            for_each_entry(path, |name: &str, entry: &mut dyn Read| -> Result<()> {
+                let mut dataset: Vec<u8> = Vec::new();
+                entry.read_to_end(&mut dataset)?;
+                if self.archive_dataset(&parser_builder, name, &dataset)? {
+                    archived += 1;
+                }
+                Ok(())
+            })?;
+        }
+
+        println!(
+            "Archived {} datasets into {}",
+            archived,
+            self.destination.display()
+        );
+        Ok(())
+    }
+}
+
+/// Gets the value of the UID element, if it's present and safe to use as a file name.
+fn get_uid(dcm_root: &DicomRoot<'_>, tag: u32) -> Option<String> {
+    let uid: String = dcm_root
+        .get_child_by_tag(tag)
+        .and_then(|obj| String::try_from(obj.get_element()).ok())?;
+    let is_valid: bool =
+        !uid.is_empty() && uid.chars().all(|c: char| c.is_ascii_digit() || c == '.');
+    if is_valid {
+        Some(uid)
+    } else {
+        None
     }
 }
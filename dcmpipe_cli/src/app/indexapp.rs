@@ -1,6 +1,5 @@
 use std::collections::HashMap;
-use std::convert::TryInto;
-use std::fs::File;
+use std::convert::{TryFrom, TryInto};
 use std::path::{Path, PathBuf};
 
 use bson::oid::ObjectId;
@@ -15,8 +14,8 @@ use dcmpipe_dict::dict::tags;
 use dcmpipe_lib::core::dcmelement::{DicomElement, RawValue};
 use dcmpipe_lib::core::dcmobject::{DicomNode, DicomObject, DicomRoot};
 use dcmpipe_lib::core::parser::util::parse_into_object;
-use dcmpipe_lib::core::parser::{Parser, ParserBuilder};
-use dcmpipe_lib::core::tagstop::TagStop;
+use dcmpipe_lib::core::parser::ParserBuilder;
+use dcmpipe_lib::core::source::{for_each_parser, split_entry_name};
 use dcmpipe_lib::defn::tag::Tag;
 
 use crate::app::CommandApplication;
@@ -30,6 +29,10 @@ static COLLECTION_NAME: &str = "series";
 
 static MONGO_ID_KEY: &str = "_id";
 
+/// Values larger than this many bytes, such as pixel data, are skipped over instead of being read
+/// into memory while scanning.
+const BULK_DATA_THRESHOLD: u32 = 64 * 1024;
+
 /// Tracks a dicom document scanned from disk or from the database. I was originally going to make
 /// this an enum with variants `FromDisk` and `FromDb` and then try to merge so that the same
 /// record is updated from disk contents rather than creating new records, however it was easier
@@ -91,59 +94,22 @@ impl IndexApp {
         let walkdir = WalkDir::new(&folder).into_iter().filter_map(|e| e.ok());
 
         let parser_builder: ParserBuilder<'_> = ParserBuilder::default()
-            .tagstop(TagStop::BeforeTag(tags::PixelData.tag.into()))
+            .bulk_data_threshold(BULK_DATA_THRESHOLD)
             .dictionary(&STANDARD_DICOM_DICTIONARY);
         for entry in walkdir {
             if !entry.metadata()?.file_type().is_file() {
                 continue;
             }
 
-            let file: File = File::open(entry.path())?;
-            let mut parser: Parser<'_, File> = parser_builder.build(file);
-
-            let dcm_root: Option<DicomRoot<'_>> = parse_into_object(&mut parser)?;
-            if dcm_root.is_none() {
-                continue;
-            }
-            let dcm_root: DicomRoot<'_> = dcm_root.unwrap();
-
-            let uid_obj: &DicomObject = dcm_root
-                .get_child_by_tag(tags::SeriesInstanceUID.tag)
-                .or_else(|| dcm_root.get_child_by_tag(tags::SOPInstanceUID.tag))
-                .ok_or_else(|| {
-                    anyhow!(
-                        "DICOM file has no SeriesInstanceUID or SOPInstanceUID: {:?}",
-                        entry.path().display()
-                    )
-                })?;
-            let uid_key: String = uid_obj.get_element().try_into()?;
-            let entry_key: String = uid_key.clone();
-            let dicom_doc: &mut DicomDoc = uid_to_doc
-                .entry(entry_key)
-                .or_insert_with(|| DicomDoc::new(uid_key.clone()));
-
-            let metadata_doc: &mut Document = dicom_doc
-                .doc
-                .entry("metadata".to_owned())
-                .or_insert_with(|| Document::new().into())
-                .as_document_mut()
-                .ok_or_else(|| anyhow!("Field failure: metadata"))?;
-            let files_field: &mut Array = metadata_doc
-                .entry("files".to_owned())
-                .or_insert_with(|| Vec::<String>::new().into())
-                .as_array_mut()
-                .ok_or_else(|| anyhow!("Field failure: metadata.files"))?;
-            files_field.push(format!("{}", entry.path().display()).into());
-            metadata_doc.insert("serieskey", uid_key);
-
-            for (_child_tag, child_obj) in dcm_root.iter_child_nodes() {
-                let child_elem: &DicomElement = child_obj.get_element();
-                if child_elem.is_seq_like() {
-                    // TODO: handle sequences
-                } else {
-                    insert_elem_entry(child_elem, &mut dicom_doc.doc)?;
+            // entries of archives are recorded by their name, e.g. `study.zip!/IMG0001`
+            for_each_parser(entry.path(), &parser_builder, |name: &str, mut parser| {
+                let dcm_root: Option<DicomRoot<'_>> = parse_into_object(&mut parser)?;
+                if dcm_root.is_none() {
+                    return Ok(());
                 }
-            }
+                let dcm_root: DicomRoot<'_> = dcm_root.unwrap();
+                index_dcm_root(name, &dcm_root, &mut uid_to_doc)
+            })?;
         }
 
         Ok(uid_to_doc)
@@ -237,7 +203,8 @@ impl IndexApp {
             let num_files: usize = files_array.len();
             files_array.retain(|bson| match bson.as_str() {
                 None => false,
-                Some(path) => Path::new(path).is_file(),
+                // entries of archives are verified by their archive existing
+                Some(path) => Path::new(split_entry_name(path).0).is_file(),
             });
 
             match files_array.len() {
@@ -317,11 +284,59 @@ impl IndexApp {
     }
 }
 
+/// Adds the dataset parsed from the file or archive entry with the given name into the map of
+/// scanned documents.
+fn index_dcm_root(
+    name: &str,
+    dcm_root: &DicomRoot<'_>,
+    uid_to_doc: &mut HashMap<String, DicomDoc>,
+) -> Result<()> {
+    let uid_obj: &DicomObject = dcm_root
+        .get_child_by_tag(tags::SeriesInstanceUID.tag)
+        .or_else(|| dcm_root.get_child_by_tag(tags::SOPInstanceUID.tag))
+        .ok_or_else(|| {
+            anyhow!(
+                "DICOM file has no SeriesInstanceUID or SOPInstanceUID: {:?}",
+                name
+            )
+        })?;
+    let uid_key: String = uid_obj.get_element().try_into()?;
+    let entry_key: String = uid_key.clone();
+    let dicom_doc: &mut DicomDoc = uid_to_doc
+        .entry(entry_key)
+        .or_insert_with(|| DicomDoc::new(uid_key.clone()));
+
+    let metadata_doc: &mut Document = dicom_doc
+        .doc
+        .entry("metadata".to_owned())
+        .or_insert_with(|| Document::new().into())
+        .as_document_mut()
+        .ok_or_else(|| anyhow!("Field failure: metadata"))?;
+    let files_field: &mut Array = metadata_doc
+        .entry("files".to_owned())
+        .or_insert_with(|| Vec::<String>::new().into())
+        .as_array_mut()
+        .ok_or_else(|| anyhow!("Field failure: metadata.files"))?;
+    files_field.push(name.into());
+    metadata_doc.insert("serieskey", uid_key);
+
+    for (_child_tag, child_obj) in dcm_root.iter_child_nodes() {
+        let child_elem: &DicomElement = child_obj.get_element();
+        if child_elem.is_seq_like() {
+            // TODO: handle sequences
+        } else if child_elem.get_bulk_data().is_some() {
+            // large values such as pixel data are not indexed
+        } else {
+            insert_elem_entry(child_elem, &mut dicom_doc.doc)?;
+        }
+    }
+    Ok(())
+}
+
 /// Builds a bson value from the given `DicomElement` and inserts it into the bson document
 fn insert_elem_entry(elem: &DicomElement, dicom_doc: &mut Document) -> Result<()> {
     let key: String = Tag::format_tag_to_path_display(elem.tag);
-    let raw_value: RawValue = elem.parse_value()?;
-    match raw_value {
+    match elem.parse_value()? {
         RawValue::Attribute(attr) => {
             dicom_doc.insert(key, attr.0);
         }
@@ -366,6 +381,32 @@ fn insert_elem_entry(elem: &DicomElement, dicom_doc: &mut Document) -> Result<()
                 }
             }
         }
+        RawValue::Longs(longs) => {
+            if !longs.is_empty() {
+                if longs.len() == 1 {
+                    dicom_doc.insert(key, longs[0]);
+                } else {
+                    dicom_doc.insert(key, longs);
+                }
+            }
+        }
+        RawValue::UnsignedLongs(ulongs) => {
+            if !ulongs.is_empty() {
+                // convert to i64 because Bson doesn't support u64, values beyond i64::MAX are
+                // stored as strings to avoid losing precision
+                let ulongs: Vec<Bson> = ulongs
+                    .into_iter()
+                    .map(|val: u64| {
+                        i64::try_from(val).map_or_else(|_| Bson::String(val.to_string()), Bson::I64)
+                    })
+                    .collect::<Vec<Bson>>();
+                if ulongs.len() == 1 {
+                    dicom_doc.insert(key, ulongs[0].clone());
+                } else {
+                    dicom_doc.insert(key, ulongs);
+                }
+            }
+        }
         RawValue::Strings(strings) => {
             if !strings.is_empty() {
                 if strings.len() == 1 {
@@ -1,4 +1,5 @@
 use std::fs::File;
+use std::io::Read;
 use std::iter::Peekable;
 use std::path::Path;
 
@@ -12,6 +13,7 @@ pub(crate) mod editapp;
 pub(crate) mod indexapp;
 pub(crate) mod printapp;
 pub(crate) mod scanapp;
+pub(crate) mod validateapp;
 
 pub(crate) trait CommandApplication {
     fn run(&mut self) -> Result<()>;
@@ -26,15 +28,21 @@ fn parse_file(path: &Path) -> Result<Parser<'_, File>> {
     let mut parser: Parser<'_, File> = ParserBuilder::default()
         .dictionary(&STANDARD_DICOM_DICTIONARY)
         .build(file);
+    check_dicom(&path.display().to_string(), &mut parser)?;
+    Ok(parser)
+}
 
-    let mut peeker: Peekable<&mut Parser<'_, File>> = parser.by_ref().peekable();
+/// Checks that the dataset with the given name, a file or an entry within an archive, begins with
+/// a DICOM element.
+fn check_dicom<DatasetType: Read>(name: &str, parser: &mut Parser<'_, DatasetType>) -> Result<()> {
+    let mut peeker: Peekable<&mut Parser<'_, DatasetType>> = parser.by_ref().peekable();
 
     let first: Option<&Result<DicomElement, ParseError>> = peeker.peek();
     if let Some(Err(_)) = first {
-        return Err(anyhow!("file is not dicom: {}", path.display()));
+        return Err(anyhow!("file is not dicom: {}", name));
     } else if first.is_none() {
-        return Err(anyhow!("file is empty: {}", path.display()));
+        return Err(anyhow!("file is empty: {}", name));
     }
 
-    Ok(parser)
+    Ok(())
 }
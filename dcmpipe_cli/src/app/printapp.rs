@@ -1,21 +1,21 @@
-use std::fs::File;
-use std::io::{self, StdoutLock, Write};
+use std::io::{self, Read, StdoutLock, Write};
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
 use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
 use dcmpipe_dict::dict::tags;
 use dcmpipe_lib::core::dcmelement::{DicomElement, RawValue};
 use dcmpipe_lib::core::dcmobject::{DicomNode, DicomObject, DicomRoot};
-use dcmpipe_lib::core::dcmsqelem::SequenceElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
 use dcmpipe_lib::core::parser::util::parse_into_object;
-use dcmpipe_lib::core::parser::Parser;
+use dcmpipe_lib::core::parser::{Parser, ParserBuilder};
+use dcmpipe_lib::core::source::for_each_parser;
 use dcmpipe_lib::defn::dcmdict::DicomDictionary;
 use dcmpipe_lib::defn::tag::Tag;
 use dcmpipe_lib::defn::ts::TSRef;
 
-use crate::app::{parse_file, CommandApplication};
+use crate::app::{check_dicom, CommandApplication};
 
 static HIDE_GROUP_TAGS: bool = false;
 static HIDE_DELIMITATION_TAGS: bool = false;
@@ -33,9 +33,9 @@ impl PrintApp {
 }
 
 impl PrintApp {
-    fn render_stream(
+    fn render_stream<DatasetType: Read>(
         &mut self,
-        mut parser: Parser<'_, File>,
+        mut parser: Parser<'_, DatasetType>,
         stdout: &mut StdoutLock<'_>,
     ) -> Result<()> {
         let mut prev_was_file_meta: bool = true;
@@ -63,9 +63,9 @@ impl PrintApp {
         Ok(())
     }
 
-    fn render_root(
+    fn render_root<DatasetType: Read>(
         &mut self,
-        mut parser: Parser<'_, File>,
+        mut parser: Parser<'_, DatasetType>,
         mut stdout: &mut StdoutLock<'_>,
     ) -> Result<()> {
         let dcmroot: DicomRoot<'_> =
@@ -123,23 +123,29 @@ impl CommandApplication for PrintApp {
     fn run(&mut self) -> Result<()> {
         let path_buf: PathBuf = self.file.clone();
         let path: &Path = path_buf.as_path();
-        let parser: Parser<'_, File> = parse_file(path)?;
+        if !path.is_file() {
+            return Err(anyhow!("invalid file: {}", path.display()));
+        }
+        let parser_builder: ParserBuilder<'_> =
+            ParserBuilder::default().dictionary(&STANDARD_DICOM_DICTIONARY);
 
         let stdout = io::stdout();
         let mut stdout = stdout.lock();
-        stdout.write_all(format!(
-            "\n# Dicom-File-Format File: {:#?}\n\n# Dicom-Meta-Information-Header\n# Used TransferSyntax: {}\n",
-            path,
-            parser.get_ts().uid.ident).as_ref()
-        )?;
-
-        if self.stream {
-            self.render_stream(parser, &mut stdout)?;
-        } else {
-            self.render_root(parser, &mut stdout)?;
-        }
-
-        Ok(())
+        // archives are printed one entry after another
+        for_each_parser(path, &parser_builder, |name: &str, mut parser| {
+            check_dicom(name, &mut parser)?;
+            stdout.write_all(format!(
+                "\n# Dicom-File-Format File: {:#?}\n\n# Dicom-Meta-Information-Header\n# Used TransferSyntax: {}\n",
+                name,
+                parser.get_ts().uid.ident).as_ref()
+            )?;
+
+            if self.stream {
+                self.render_stream(parser, &mut stdout)
+            } else {
+                self.render_root(parser, &mut stdout)
+            }
+        })
     }
 }
 
@@ -175,7 +181,7 @@ fn render_element(element: &DicomElement) -> Result<Option<String>> {
     };
     let vr: &str = element.vr.ident;
 
-    let seq_path: &Vec<SequenceElement> = element.get_sequence_path();
+    let seq_path: &SequencePath = element.get_sequence_path();
 
     let mut indent_width: usize = seq_path.len();
     if indent_width > 0
@@ -291,6 +297,15 @@ pub fn render_value(elem: &DicomElement) -> Result<String> {
             sep = " / ";
             ellipses = format_vec_to_strings(uints, &mut str_vals, |val: u32| format!("{}", val));
         }
+        RawValue::Longs(longs) => {
+            sep = " / ";
+            ellipses = format_vec_to_strings(longs, &mut str_vals, |val: i64| format!("{}", val));
+        }
+        RawValue::UnsignedLongs(ulongs) => {
+            sep = " / ";
+            ellipses =
+                format_vec_to_strings(ulongs, &mut str_vals, |val: u64| format!("{}", val));
+        }
         RawValue::Bytes(bytes) => {
             ellipses =
                 format_vec_to_strings(bytes, &mut str_vals, |val: u8| format!("{:02x}", val));
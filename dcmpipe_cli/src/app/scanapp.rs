@@ -1,18 +1,19 @@
-use std::fs::File;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use walkdir::WalkDir;
 
 use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
-use dcmpipe_lib::core::parser::{Parser, ParserBuilder};
+use dcmpipe_lib::core::parser::{Diagnostic, Parser, ParserBuilder};
+use dcmpipe_lib::core::source::for_each_parser;
 
 use crate::app::CommandApplication;
 
 enum ScanResult {
     Success,
     NotDicom,
-    InvalidData(Box<dyn std::error::Error>),
+    InvalidData(Vec<String>),
 }
 
 pub struct ScanApp {
@@ -31,51 +32,79 @@ impl ScanApp {
             .filter(|path: &PathBuf| path.is_file())
     }
 
-    fn parse_all_element_values(&self, parser: Parser<'_, File>) -> ScanResult {
+    /// Parses all elements and their values, collecting every problem found rather than stopping
+    /// at the first one.
+    fn parse_all_element_values<DatasetType: Read>(
+        &self,
+        mut parser: Parser<'_, DatasetType>,
+    ) -> ScanResult {
         let mut is_first_elem: bool = true;
-        for elem_result in parser {
+        let mut problems: Vec<String> = Vec::new();
+        for elem_result in parser.by_ref() {
             match elem_result {
                 Ok(elem) => {
-                    match elem.parse_value() {
-                        Ok(_) => {}
-                        Err(e) => return ScanResult::InvalidData(Box::new(e)),
-                    };
+                    if let Err(e) = elem.parse_value() {
+                        problems.push(format!("{}: {}", elem.get_tagpath().display(), e));
+                    }
                 }
                 Err(e) => {
                     if is_first_elem {
                         return ScanResult::NotDicom;
                     }
-                    return ScanResult::InvalidData(Box::new(e));
+                    problems.push(e.to_string());
                 }
             };
             is_first_elem = false;
         }
 
-        ScanResult::Success
+        // parsing ends at the first error, which is the last problem found
+        if let Some(context) = parser.get_error_context() {
+            if let Some(problem) = problems.last_mut() {
+                problem.push_str(&format!(", {}", context));
+            }
+            problems.push(format!("\t{}", context.hex_excerpt()));
+        }
+
+        let problems: Vec<String> = parser
+            .take_diagnostics()
+            .iter()
+            .map(Diagnostic::to_string)
+            .chain(problems)
+            .collect::<Vec<String>>();
+        if problems.is_empty() {
+            ScanResult::Success
+        } else {
+            ScanResult::InvalidData(problems)
+        }
     }
 }
 
 impl CommandApplication for ScanApp {
     fn run(&mut self) -> Result<()> {
-        let parser_builder: ParserBuilder<'_> =
-            ParserBuilder::default().dictionary(&STANDARD_DICOM_DICTIONARY);
+        let parser_builder: ParserBuilder<'_> = ParserBuilder::default()
+            .dictionary(&STANDARD_DICOM_DICTIONARY)
+            .recover(true);
 
         for path in self.get_files() {
-            let file: File = File::open(path.clone())?;
-            let parser: Parser<'_, File> = parser_builder.build(file);
+            // entries of archives are named relative to the archive's path
+            for_each_parser(&path, &parser_builder, |name: &str, parser| -> Result<()> {
+                let relative_path: &str = Path::new(name)
+                    .strip_prefix(&self.folder)?
+                    .to_str()
+                    .expect("relative path");
 
-            let relative_path: &str = path
-                .strip_prefix(&self.folder)?
-                .to_str()
-                .expect("relative path");
-
-            match self.parse_all_element_values(parser) {
-                ScanResult::Success => {}  /*println!("Valid DICOM: {}", path_str),*/
-                ScanResult::NotDicom => {} /*println!("Not DICOM: {}", relative_path),*/
-                ScanResult::InvalidData(e) => {
-                    println!("Failure Parsing: {}\n\t{}", relative_path, e)
-                }
-            };
+                match self.parse_all_element_values(parser) {
+                    ScanResult::Success => {}  /*println!("Valid DICOM: {}", path_str),*/
+                    ScanResult::NotDicom => {} /*println!("Not DICOM: {}", relative_path),*/
+                    ScanResult::InvalidData(problems) => {
+                        println!("Failure Parsing: {}", relative_path);
+                        for problem in problems {
+                            println!("\t{}", problem);
+                        }
+                    }
+                };
+                Ok(())
+            })?;
         }
 
         Ok(())
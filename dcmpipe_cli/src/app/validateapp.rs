@@ -0,0 +1,88 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use dcmpipe_lib::core::dcmobject::DicomRoot;
+use dcmpipe_lib::core::parser::util::parse_into_object;
+use dcmpipe_lib::core::parser::Parser;
+use dcmpipe_lib::core::validate::{validate, ValidationIssue, ValidationResult};
+
+use crate::app::{parse_file, CommandApplication};
+
+pub struct ValidateApp {
+    json: bool,
+    file: PathBuf,
+}
+
+impl ValidateApp {
+    pub fn new(json: bool, file: PathBuf) -> ValidateApp {
+        ValidateApp { json, file }
+    }
+
+    fn render_text(&self, path: &Path, result: &ValidationResult) -> String {
+        let mut output: String = format!(
+            "# File: {}\n# IOD: {}\n",
+            path.display(),
+            result.iod.unwrap_or("<Unknown SOP Class>")
+        );
+        for issue in result.issues.iter() {
+            output.push_str(&format!("{}\n", issue));
+        }
+        output.push_str(&format!("# {} issue(s) found\n", result.issues.len()));
+        output
+    }
+
+    fn render_json(&self, path: &Path, result: &ValidationResult) -> Result<String> {
+        let issues: Vec<Value> = result
+            .issues
+            .iter()
+            .map(|issue: &ValidationIssue| {
+                json!({
+                    "kind": issue.kind.get_ident(),
+                    "tagpath": issue.tagpath.display(),
+                    "message": issue.message,
+                })
+            })
+            .collect::<Vec<Value>>();
+        let output: Value = json!({
+            "file": path.display().to_string(),
+            "iod": result.iod,
+            "valid": result.is_valid(),
+            "issues": issues,
+        });
+        Ok(format!("{}\n", serde_json::to_string_pretty(&output)?))
+    }
+}
+
+impl CommandApplication for ValidateApp {
+    fn run(&mut self) -> Result<()> {
+        let path_buf: PathBuf = self.file.clone();
+        let path: &Path = path_buf.as_path();
+        let mut parser: Parser<'_, File> = parse_file(path)?;
+        let dcmroot: DicomRoot<'_> = parse_into_object(&mut parser)?
+            .ok_or_else(|| anyhow!("failed to parse any dicom elements"))?;
+
+        let result: ValidationResult = validate(&dcmroot);
+        let output: String = if self.json {
+            self.render_json(path, &result)?
+        } else {
+            self.render_text(path, &result)
+        };
+
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        stdout.write_all(output.as_ref())?;
+
+        if !result.is_valid() {
+            return Err(anyhow!(
+                "{} issue(s) found validating {}",
+                result.issues.len(),
+                path.display()
+            ));
+        }
+        Ok(())
+    }
+}
@@ -22,6 +22,18 @@ pub enum Command {
         /// until the error is encountered.
         stream: bool,
 
+        /// The file to process as a DICOM dataset. Each entry of a `.zip`, `.tar`, or `.tar.gz`
+        /// archive is printed, and `.gz` files are decompressed.
+        file: PathBuf,
+    },
+    /// Validates a DICOM dataset against the dictionary and the IOD of its SOP Class.
+    ///
+    /// Exits with a non-zero status if any issues are found.
+    Validate {
+        #[structopt(short, long)]
+        /// Output the results as JSON instead of human-readable text.
+        json: bool,
+
         /// The file to process as a DICOM dataset.
         file: PathBuf,
     },
@@ -32,7 +44,8 @@ pub enum Command {
     },
     /// Recursively parses a folder of DICOM datasets and prints results of parsing.
     ///
-    /// This is primarily useful for locating DICOM files which fail to parse.
+    /// This is primarily useful for locating DICOM files which fail to parse. Entries within
+    /// `.zip`, `.tar`, and `.tar.gz` archives are parsed, and reported as `archive.zip!/entry`.
     Parse {
         /// The folder to recursively scan for DICOM datasets.
         folder: PathBuf,
@@ -55,6 +68,9 @@ pub enum Command {
     /// into the destination folder in a consistent structure:
     ///   - One series per folder
     ///   - Each DICOM file will be named in the format `[SOP_UID].dcm`
+    ///
+    /// Entries within `.zip`, `.tar`, and `.tar.gz` archives and `.gz` files in the source folder
+    /// are archived as their own DICOM files.
     Archive {
         /// The source folder of DICOM datasets to process.
         source: PathBuf,
@@ -66,7 +82,8 @@ pub enum Command {
 
 #[derive(StructOpt, Debug)]
 pub enum IndexCommand {
-    /// Recursively scans a folder for DICOM datasets, indexing them into a database.
+    /// Recursively scans a folder for DICOM datasets, indexing them into a database. Entries
+    /// within `.zip`, `.tar`, and `.tar.gz` archives are indexed as `archive.zip!/entry`.
     Scan {
         /// The folder to scan for DICOM datasets.
         folder: PathBuf,
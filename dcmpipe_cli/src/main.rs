@@ -7,6 +7,7 @@ use crate::app::editapp::EditApp;
 use crate::app::indexapp::IndexApp;
 use crate::app::printapp::PrintApp;
 use crate::app::scanapp::ScanApp;
+use crate::app::validateapp::ValidateApp;
 use crate::app::CommandApplication;
 use crate::args::{Arguments, Command};
 
@@ -26,6 +27,7 @@ fn make_app() -> Box<dyn CommandApplication> {
 
     match args.command {
         Command::Print { stream, file } => Box::new(PrintApp::new(stream, file)),
+        Command::Validate { json, file } => Box::new(ValidateApp::new(json, file)),
         Command::Edit { file } => Box::new(EditApp::new(file)),
         Command::Parse { folder } => Box::new(ScanApp::new(folder)),
         Command::Index { db, cmd } => Box::new(IndexApp::new(db, cmd)),
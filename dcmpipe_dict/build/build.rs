@@ -1,4 +1,6 @@
+use dcmpipe_dict_builder::ciddefnwrite;
 use dcmpipe_dict_builder::defnwrite;
+use dcmpipe_dict_builder::ioddefnwrite;
 
 use std::fs::File;
 use std::path::Path;
@@ -7,12 +9,39 @@ use std::path::Path;
 // http://dicom.nema.org/medical/dicom/current/source/docbook/part06/part06.xml
 static DICOM_DEFINITIONS_XML_FILE: &str = "build/dicom_xml/part06.xml";
 
+// This file was downloaded from
+// http://dicom.nema.org/medical/dicom/current/source/docbook/part03/part03.xml
+static DICOM_IODS_XML_FILE: &str = "build/dicom_xml/part03.xml";
+
+// This file was downloaded from
+// http://dicom.nema.org/medical/dicom/current/source/docbook/part04/part04.xml
+static DICOM_SOP_CLASSES_XML_FILE: &str = "build/dicom_xml/part04.xml";
+
+// This file was downloaded from
+// http://dicom.nema.org/medical/dicom/current/source/docbook/part16/part16.xml
+static DICOM_CONTEXT_GROUPS_XML_FILE: &str = "build/dicom_xml/part16.xml";
+
 fn main() {
     // Causes this build script to re-run if the file is modified/changed
     println!("cargo:rerun-if-changed=./{}", DICOM_DEFINITIONS_XML_FILE);
+    println!("cargo:rerun-if-changed=./{}", DICOM_IODS_XML_FILE);
+    println!("cargo:rerun-if-changed=./{}", DICOM_SOP_CLASSES_XML_FILE);
+    println!("cargo:rerun-if-changed=./{}", DICOM_CONTEXT_GROUPS_XML_FILE);
 
     let file: File = File::open(DICOM_DEFINITIONS_XML_FILE).expect("Unable to load XML file");
     let folder: &Path = Path::new("src/dict/");
 
     defnwrite::process_xml_file(file, folder).expect("Failed to process XML file");
+
+    let part03: File = File::open(DICOM_IODS_XML_FILE).expect("Unable to load XML file");
+    let part04: File = File::open(DICOM_SOP_CLASSES_XML_FILE).expect("Unable to load XML file");
+
+    ioddefnwrite::process_iod_xml_files(part03, part04, folder)
+        .expect("Failed to process XML files");
+
+    let part16: File =
+        File::open(DICOM_CONTEXT_GROUPS_XML_FILE).expect("Unable to load XML file");
+
+    ciddefnwrite::process_context_group_xml_file(part16, folder)
+        .expect("Failed to process XML file");
 }
@@ -1,4 +1,9 @@
+pub(crate) mod context_group_lookup;
+pub mod context_groups;
+pub(crate) mod iod_lookup;
+pub mod iods;
 pub(crate) mod lookup;
+pub mod modules;
 pub mod stdlookup;
 pub mod tags;
 pub mod transfer_syntaxes;
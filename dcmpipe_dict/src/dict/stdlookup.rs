@@ -1,8 +1,13 @@
+use dcmpipe_lib::defn::contextgroup::ContextGroupRef;
 use dcmpipe_lib::defn::dcmdict::DicomDictionary;
+use dcmpipe_lib::defn::iod::IODRef;
+use dcmpipe_lib::defn::module::ModuleRef;
 use dcmpipe_lib::defn::tag::TagRef;
 use dcmpipe_lib::defn::ts::TSRef;
 use dcmpipe_lib::defn::uid::UIDRef;
 
+use crate::dict::context_group_lookup::{CONTEXT_GROUP_BY_CID, CONTEXT_GROUP_BY_IDENT};
+use crate::dict::iod_lookup::{IOD_BY_IDENT, IOD_BY_SOP_CLASS_UID, MODULE_BY_IDENT};
 use crate::dict::lookup::{
     TAG_BY_IDENT, TAG_BY_VALUE, TS_BY_IDENT, TS_BY_UID, UID_BY_IDENT, UID_BY_UID,
 };
@@ -36,4 +41,24 @@ impl DicomDictionary for StandardDicomDictionary {
     fn get_uid_by_name(&self, name: &str) -> Option<UIDRef> {
         UID_BY_IDENT.get(name).copied()
     }
+
+    fn get_iod_by_sop_class_uid(&self, uid: &str) -> Option<IODRef> {
+        IOD_BY_SOP_CLASS_UID.get(uid).copied()
+    }
+
+    fn get_iod_by_name(&self, name: &str) -> Option<IODRef> {
+        IOD_BY_IDENT.get(name).copied()
+    }
+
+    fn get_module_by_name(&self, name: &str) -> Option<ModuleRef> {
+        MODULE_BY_IDENT.get(name).copied()
+    }
+
+    fn get_context_group_by_cid(&self, cid: u32) -> Option<ContextGroupRef> {
+        CONTEXT_GROUP_BY_CID.get(&cid).copied()
+    }
+
+    fn get_context_group_by_name(&self, name: &str) -> Option<ContextGroupRef> {
+        CONTEXT_GROUP_BY_IDENT.get(name).copied()
+    }
 }
@@ -1,10 +1,18 @@
+use dcmpipe_lib::defn::contextgroup::ContextGroupRef;
+use dcmpipe_lib::defn::iod::{ModuleUsage, IOD};
+use dcmpipe_lib::defn::module::{AttributeType, ModuleAttribute, ModuleRef};
 use dcmpipe_lib::defn::tag::Tag;
 use dcmpipe_lib::defn::ts::TransferSyntax;
 use dcmpipe_lib::defn::uid::UIDRef;
 
+use crate::dict::context_group_lookup::{CONTEXT_GROUP_BY_CID, CONTEXT_GROUP_BY_IDENT};
+use crate::dict::context_groups as cids;
+use crate::dict::iod_lookup::{IOD_BY_IDENT, IOD_BY_SOP_CLASS_UID, MODULE_BY_IDENT};
+use crate::dict::iods;
 use crate::dict::lookup::{
     TAG_BY_IDENT, TAG_BY_VALUE, TS_BY_IDENT, TS_BY_UID, UID_BY_IDENT, UID_BY_UID,
 };
+use crate::dict::modules;
 use crate::dict::tags;
 use crate::dict::transfer_syntaxes as ts;
 use crate::dict::uids;
@@ -79,6 +87,51 @@ pub fn test_uids_lookup() {
     assert_eq!(ctis_by_id, &uids::CTImageStorage);
 }
 
+#[test]
+pub fn test_iods_lookup() {
+    let ct_by_ident: &IOD = IOD_BY_IDENT.get("CTImageIOD").expect("IOD not found");
+    assert_eq!(ct_by_ident, &iods::CTImageIOD);
+
+    let ct_by_uid: &IOD = IOD_BY_SOP_CLASS_UID
+        .get(&uids::CTImageStorage.uid)
+        .expect("IOD not found");
+    assert_eq!(ct_by_uid, &iods::CTImageIOD);
+
+    let patient_module: ModuleRef = MODULE_BY_IDENT
+        .get("PatientModule")
+        .expect("Module not found");
+    assert_eq!(patient_module, &modules::PatientModule);
+
+    let patient_usage: ModuleUsage = ct_by_uid
+        .get_modules()
+        .iter()
+        .find(|iod_module| iod_module.module == patient_module)
+        .map(|iod_module| iod_module.usage)
+        .expect("IOD missing module");
+    assert_eq!(patient_usage, ModuleUsage::Mandatory);
+
+    let patient_name: &ModuleAttribute = patient_module
+        .get_attribute(tags::PatientsName.tag)
+        .expect("Module missing attribute");
+    assert_eq!(patient_name.attr_type, AttributeType::Type2);
+}
+
+#[test]
+pub fn test_context_groups_lookup() {
+    let ar_by_cid: ContextGroupRef = CONTEXT_GROUP_BY_CID
+        .get(&4)
+        .expect("Context group not found");
+    assert_eq!(ar_by_cid, &cids::AnatomicRegion);
+
+    let ar_by_ident: ContextGroupRef = CONTEXT_GROUP_BY_IDENT
+        .get("AnatomicRegion")
+        .expect("Context group not found");
+    assert_eq!(ar_by_ident, &cids::AnatomicRegion);
+
+    assert!(ar_by_cid.contains("SCT", "818981001"));
+    assert!(!ar_by_cid.contains("DCM", "818981001"));
+}
+
 /// Sanity-check of the pre-defined TransferSyntax's to ensure
 /// that their defined properties reflect the UID's name.
 /// May catch issues with improperly copying over values from definitions.
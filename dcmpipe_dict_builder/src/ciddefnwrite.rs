@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, Write};
+use std::path::{Path, PathBuf};
+
+use crate::defnwrite::{sanitize_var_name, save_codefile};
+use crate::tableparser::{XmlDocbookTable, XmlDocbookTableIterator, XmlDocbookTableResult};
+
+static CONTEXT_GROUP_LOOKUP_PREAMBLE: &str =
+    "//! This is an auto-generated file. Do not make modifications here.
+
+use dcmpipe_lib::defn::contextgroup::ContextGroupRef;
+
+use crate::dict::context_groups as cids;
+
+";
+
+static CONTEXT_GROUP_PREAMBLE: &str =
+    "//! This is an auto-generated file. Do not make modifications here.
+
+#![allow(non_upper_case_globals)]
+
+use dcmpipe_lib::defn::contextgroup::{Code, ContextGroup};
+
+";
+
+macro_rules! context_group_definition {
+    ($($args:tt)*) => {
+        format!(
+"/// {}
+///
+/// - **CID:** {}
+pub static {}: ContextGroup = ContextGroup {{
+    ident: \"{}\",
+    cid: {},
+    name: \"{}\",
+    codes: {},
+    includes: {},
+}};
+
+", $($args)*)
+    };
+}
+
+struct XmlCode {
+    scheme: String,
+    value: String,
+    meaning: String,
+}
+
+struct XmlContextGroup {
+    var_name: String,
+    cid: u32,
+    name: String,
+    codes: Vec<XmlCode>,
+    /// The Context IDs of included context groups
+    includes: Vec<u32>,
+}
+
+/// Processes the Context Group tables of Part 16 into `context_groups.rs` and
+/// `context_group_lookup.rs`.
+pub fn process_context_group_xml_file(part16: File, folder: &Path) -> Result<(), Error> {
+    let bufread: BufReader<File> = BufReader::new(part16);
+    let tables: Vec<XmlDocbookTable> = XmlDocbookTableIterator::new(bufread)
+        .map(|item: XmlDocbookTableResult| {
+            item.unwrap_or_else(|_| panic!("Error parsing XML docbook table"))
+        })
+        .collect::<Vec<XmlDocbookTable>>();
+
+    let mut used_var_names: HashSet<String> = HashSet::new();
+    let mut used_cids: HashSet<u32> = HashSet::new();
+    let mut groups: Vec<XmlContextGroup> = tables
+        .iter()
+        .filter_map(|table| process_context_group_table(table, &mut used_var_names))
+        // some context groups are split across several tables, only the first is used
+        .filter(|group| used_cids.insert(group.cid))
+        .collect::<Vec<XmlContextGroup>>();
+
+    remove_invalid_includes(&mut groups);
+
+    let var_names: HashMap<u32, String> = groups
+        .iter()
+        .map(|group| (group.cid, group.var_name.clone()))
+        .collect::<HashMap<u32, String>>();
+
+    let mut cid_lookup_phf: phf_codegen::Map<u32> = phf_codegen::Map::new();
+    let mut ident_lookup_phf: phf_codegen::Map<String> = phf_codegen::Map::new();
+
+    let mut context_groups: String = groups
+        .iter()
+        .map(|group| {
+            let includes: Vec<String> = group
+                .includes
+                .iter()
+                .filter_map(|cid| var_names.get(cid))
+                .map(|var_name| format!("&{}", var_name))
+                .collect::<Vec<String>>();
+
+            cid_lookup_phf.entry(group.cid, &format!("&cids::{}", group.var_name));
+            ident_lookup_phf.entry(
+                group.var_name.clone(),
+                &format!("&cids::{}", group.var_name),
+            );
+
+            context_group_definition!(
+                group.name,
+                group.cid, // comment placeholders
+                group.var_name,
+                group.var_name,
+                group.cid,
+                escape_str(&group.name),
+                format_codes(&group.codes),
+                format!("&[{}]", includes.join(", "))
+            ) // field placeholders
+        })
+        .collect::<String>();
+
+    // Remove trailing newlines
+    if context_groups.len() > 1 {
+        context_groups.remove(context_groups.len() - 2);
+    }
+
+    std::fs::create_dir_all(folder)?;
+
+    let mut context_groups_file: PathBuf = folder.to_path_buf();
+    context_groups_file.push("context_groups.rs");
+    save_codefile(
+        context_groups_file.as_path(),
+        CONTEXT_GROUP_PREAMBLE.to_owned(),
+        &context_groups,
+    )?;
+
+    let mut lookup_file_path: PathBuf = folder.to_path_buf();
+    lookup_file_path.push("context_group_lookup.rs");
+
+    let mut lookup_file: BufWriter<File> =
+        BufWriter::new(File::create(lookup_file_path.as_path()).unwrap());
+    write!(&mut lookup_file, "{}", CONTEXT_GROUP_LOOKUP_PREAMBLE)?;
+
+    write!(
+        &mut lookup_file,
+        "pub static CONTEXT_GROUP_BY_CID: phf::Map<u32, ContextGroupRef> = "
+    )?;
+    cid_lookup_phf.build(&mut lookup_file)?;
+
+    write!(&mut lookup_file, ";\n\n")?;
+    write!(
+        &mut lookup_file,
+        "pub static CONTEXT_GROUP_BY_IDENT: phf::Map<&'static str, ContextGroupRef> = "
+    )?;
+    ident_lookup_phf.build(&mut lookup_file)?;
+    writeln!(&mut lookup_file, ";")?;
+
+    Ok(())
+}
+
+/// Parses the Context ID from the id of a context group's table or section, `table_CID_4`
+fn parse_cid(id: &str) -> Option<u32> {
+    let index: usize = id.find("CID_")?;
+    id[index + 4..].parse::<u32>().ok()
+}
+
+fn process_context_group_table(
+    table: &XmlDocbookTable,
+    used_var_names: &mut HashSet<String>,
+) -> Option<XmlContextGroup> {
+    let cid: u32 = parse_cid(&table.id)?;
+    let scheme_col: usize = table.column("Coding Scheme Designator")?;
+    let value_col: usize = table.column("Code Value")?;
+    let meaning_col: usize = table.column("Code Meaning")?;
+
+    let name: String = table.caption.trim().to_owned();
+    let mut var_name: String = sanitize_var_name(&name);
+    if var_name.is_empty() {
+        return None;
+    }
+    if used_var_names.contains(&var_name) {
+        var_name = format!("{}_CID_{}", var_name, cid);
+    }
+    used_var_names.insert(var_name.clone());
+
+    let mut codes: Vec<XmlCode> = Vec::new();
+    let mut includes: Vec<u32> = Vec::new();
+    for row in table.rows.iter() {
+        // included context groups are listed in a single cell spanning the row
+        if let Some(first) = row.first() {
+            if first.text.starts_with("Include") {
+                if let Some(include) = first.xrefs.iter().find_map(|id| parse_cid(id)) {
+                    if include != cid && !includes.contains(&include) {
+                        includes.push(include);
+                    }
+                }
+                continue;
+            }
+        }
+
+        let cell_text = |col: usize| -> String {
+            row.get(col)
+                .map(|cell| cell.text.trim().to_owned())
+                .unwrap_or_default()
+        };
+        let scheme: String = cell_text(scheme_col);
+        let value: String = cell_text(value_col);
+        let meaning: String = cell_text(meaning_col);
+        if scheme.is_empty() || value.is_empty() {
+            continue;
+        }
+        codes.push(XmlCode {
+            scheme,
+            value,
+            meaning,
+        });
+    }
+
+    Some(XmlContextGroup {
+        var_name,
+        cid,
+        name,
+        codes,
+        includes,
+    })
+}
+
+/// Removes includes of context groups which aren't defined, or which would result in an include
+/// cycle, so that looking up a code through included groups always terminates.
+fn remove_invalid_includes(groups: &mut [XmlContextGroup]) {
+    let defined: HashSet<u32> = groups.iter().map(|group| group.cid).collect();
+    for group in groups.iter_mut() {
+        group.includes.retain(|cid| defined.contains(cid));
+    }
+
+    let mut includes_by_cid: HashMap<u32, Vec<u32>> = groups
+        .iter()
+        .map(|group| (group.cid, group.includes.clone()))
+        .collect();
+    for group in groups.iter_mut() {
+        let cid: u32 = group.cid;
+        group
+            .includes
+            .retain(|include| !reaches(*include, cid, &includes_by_cid, &mut HashSet::new()));
+        includes_by_cid.insert(cid, group.includes.clone());
+    }
+}
+
+/// Whether the context group `from` includes the context group `to`, directly or indirectly.
+fn reaches(
+    from: u32,
+    to: u32,
+    includes_by_cid: &HashMap<u32, Vec<u32>>,
+    visited: &mut HashSet<u32>,
+) -> bool {
+    if from == to {
+        return true;
+    }
+    if !visited.insert(from) {
+        return false;
+    }
+    includes_by_cid.get(&from).map_or(false, |includes| {
+        includes
+            .iter()
+            .any(|include| reaches(*include, to, includes_by_cid, visited))
+    })
+}
+
+fn format_codes(codes: &[XmlCode]) -> String {
+    if codes.is_empty() {
+        return "&[]".to_owned();
+    }
+    let mut code: String = "&[\n".to_owned();
+    for xml_code in codes {
+        code.push_str(&format!(
+            "        Code {{\n            scheme: \"{}\",\n            value: \"{}\",\n            meaning: \"{}\",\n        }},\n",
+            escape_str(&xml_code.scheme),
+            escape_str(&xml_code.value),
+            escape_str(&xml_code.meaning),
+        ));
+    }
+    code.push_str("    ]");
+    code
+}
+
+fn escape_str(text: &str) -> String {
+    text.replace("\\", "\\\\").replace("\"", "\\\"")
+}
@@ -436,7 +436,7 @@ fn process_element(
     Some(code)
 }
 
-fn save_codefile(filename: &Path, preamble: String, code: &str) -> Result<(), Error> {
+pub(crate) fn save_codefile(filename: &Path, preamble: String, code: &str) -> Result<(), Error> {
     let mut out_rs_file: File = File::create(filename)?;
     out_rs_file.write_all(&preamble.into_bytes())?;
     out_rs_file.write_all(code.as_bytes())?;
@@ -449,7 +449,7 @@ fn save_codefile(filename: &Path, preamble: String, code: &str) -> Result<(), Er
 /// Some things straight up don't have a name aside from "(Retired)" o.O
 /// Such as: 1.2.840.10008.5.1.4.1.​1.​40 and (0018,0061)
 /// So check whether the returned string is empty
-fn sanitize_var_name(var_name: &str) -> String {
+pub(crate) fn sanitize_var_name(var_name: &str) -> String {
     let is_retired: bool = var_name.contains("Retired");
     // let is_trial: bool = var_name.contains("Trial");
 
@@ -0,0 +1,633 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, Write};
+use std::path::{Path, PathBuf};
+
+use crate::defnwrite::{sanitize_var_name, save_codefile};
+use crate::tableparser::{
+    XmlDocbookCell, XmlDocbookTable, XmlDocbookTableIterator, XmlDocbookTableResult,
+};
+
+static IOD_LOOKUP_PREAMBLE: &str =
+    "//! This is an auto-generated file. Do not make modifications here.
+
+use dcmpipe_lib::defn::iod::IODRef;
+use dcmpipe_lib::defn::module::ModuleRef;
+
+use crate::dict::iods;
+use crate::dict::modules;
+
+";
+
+static MODULE_PREAMBLE: &str = "//! This is an auto-generated file. Do not make modifications here.
+
+#![allow(non_upper_case_globals)]
+#![allow(clippy::unreadable_literal)]
+
+use dcmpipe_lib::defn::module::{AttributeType, Module, ModuleAttribute};
+
+";
+
+static IOD_PREAMBLE: &str = "//! This is an auto-generated file. Do not make modifications here.
+
+#![allow(non_upper_case_globals)]
+
+use dcmpipe_lib::defn::iod::{IODModule, ModuleUsage, IOD};
+
+use crate::dict::modules;
+
+";
+
+macro_rules! module_definition {
+    ($($args:tt)*) => {
+        format!(
+"/// {}
+///
+/// - **Reference:** {}
+pub static {}: Module = Module {{
+    ident: \"{}\",
+    name: {:?},
+    reference: {:?},
+    attributes: {},
+}};
+
+", $($args)*)
+    };
+}
+
+macro_rules! iod_definition {
+    ($($args:tt)*) => {
+        format!(
+"/// {}
+///
+/// - **Reference:** {}
+pub static {}: IOD = IOD {{
+    ident: \"{}\",
+    name: {:?},
+    reference: {:?},
+    modules: {},
+}};
+
+", $($args)*)
+    };
+}
+
+/// An attribute row of a module or macro table, with any included macros already expanded.
+struct XmlModuleAttribute {
+    depth: usize,
+    tag: u32,
+    attr_type: &'static str,
+}
+
+/// An attribute of a module with the attributes of its sequence items nested under it.
+struct ModuleAttributeNode {
+    tag: u32,
+    attr_type: &'static str,
+    items: Vec<ModuleAttributeNode>,
+}
+
+struct XmlModule {
+    var_name: String,
+    name: String,
+    reference: String,
+    attributes: Vec<ModuleAttributeNode>,
+}
+
+struct XmlIODModule {
+    ie: String,
+    module_var_name: String,
+    usage: &'static str,
+}
+
+struct XmlIOD {
+    var_name: String,
+    name: String,
+    reference: String,
+    modules: Vec<XmlIODModule>,
+}
+
+/// Index of the tables read from Part 3, used for resolving the cross-references between IODs,
+/// modules, and the macros included by modules.
+struct Part03Tables {
+    tables: Vec<XmlDocbookTable>,
+    by_id: HashMap<String, usize>,
+    /// The first attribute table of each section, preferring tables directly within the section
+    attr_table_by_section: HashMap<String, usize>,
+}
+
+impl Part03Tables {
+    fn new(tables: Vec<XmlDocbookTable>) -> Part03Tables {
+        let mut by_id: HashMap<String, usize> = HashMap::new();
+        let mut attr_table_by_section: HashMap<String, usize> = HashMap::new();
+        for (index, table) in tables.iter().enumerate() {
+            by_id.insert(table.id.clone(), index);
+            if !is_attribute_table(table) {
+                continue;
+            }
+            if let Some(section) = table.section() {
+                attr_table_by_section.insert(section.to_owned(), index);
+            }
+        }
+        // sections without their own attribute table resolve to the first table nested within
+        for (index, table) in tables.iter().enumerate().rev() {
+            if !is_attribute_table(table) {
+                continue;
+            }
+            for section in table.sections.iter().filter(|id| !id.is_empty()) {
+                if table.section() != Some(section.as_str()) {
+                    attr_table_by_section.entry(section.clone()).or_insert(index);
+                }
+            }
+        }
+        Part03Tables {
+            tables,
+            by_id,
+            attr_table_by_section,
+        }
+    }
+
+    /// Resolves a cross-reference to a section or table into the index of an attribute table
+    fn resolve_attr_table(&self, linkend: &str) -> Option<usize> {
+        if let Some(index) = self.by_id.get(linkend) {
+            if is_attribute_table(&self.tables[*index]) {
+                return Some(*index);
+            }
+        }
+        self.attr_table_by_section.get(linkend).copied()
+    }
+}
+
+/// Processes the module and IOD tables of Part 3, along with the SOP Class table of Part 4
+/// which maps SOP Class UIDs to IODs, into `modules.rs`, `iods.rs`, and `iod_lookup.rs`.
+pub fn process_iod_xml_files(part03: File, part04: File, folder: &Path) -> Result<(), Error> {
+    let part03_tables: Vec<XmlDocbookTable> = read_tables(part03);
+    let part04_tables: Vec<XmlDocbookTable> = read_tables(part04);
+
+    let part03_tables: Part03Tables = Part03Tables::new(part03_tables);
+
+    let mut module_var_names: HashMap<usize, String> = HashMap::new();
+    let mut used_var_names: HashSet<String> = HashSet::new();
+    let mut xml_modules: Vec<XmlModule> = Vec::new();
+    let mut xml_iods: Vec<XmlIOD> = Vec::new();
+    // The IOD defined within each section, used for resolving references from Part 4. Sections
+    // containing more than one IOD map to `None`.
+    let mut iod_by_section: HashMap<String, Option<String>> = HashMap::new();
+
+    for table in part03_tables.tables.iter().filter(|t| is_iod_table(t)) {
+        let iod: XmlIOD = match process_iod_table(
+            table,
+            &part03_tables,
+            &mut module_var_names,
+            &mut used_var_names,
+            &mut xml_modules,
+        ) {
+            Some(iod) => iod,
+            None => continue,
+        };
+        for section in table.sections.iter().filter(|id| !id.is_empty()) {
+            iod_by_section
+                .entry(section.clone())
+                .and_modify(|existing| *existing = None)
+                .or_insert_with(|| Some(iod.var_name.clone()));
+        }
+        xml_iods.push(iod);
+    }
+
+    let mut iod_ident_lookup_phf: phf_codegen::Map<String> = phf_codegen::Map::new();
+    let mut iod_uid_lookup_phf: phf_codegen::Map<String> = phf_codegen::Map::new();
+    let mut module_ident_lookup_phf: phf_codegen::Map<String> = phf_codegen::Map::new();
+
+    let mut sop_class_uids: HashSet<String> = HashSet::new();
+    for table in part04_tables.iter() {
+        let uid_col: usize = match table.column("SOP Class UID") {
+            Some(col) => col,
+            None => continue,
+        };
+        let iod_col: usize = match table.column("IOD Specification") {
+            Some(col) => col,
+            None => continue,
+        };
+        for row in table.rows.iter() {
+            let (uid_cell, iod_cell) = match (row.get(uid_col), row.get(iod_col)) {
+                (Some(uid_cell), Some(iod_cell)) => (uid_cell, iod_cell),
+                _ => continue,
+            };
+            let uid: String = uid_cell.text.split_whitespace().collect::<String>();
+            if uid.is_empty() || sop_class_uids.contains(&uid) {
+                continue;
+            }
+            let iod_var_name: Option<&String> = iod_cell
+                .xrefs
+                .iter()
+                .filter_map(|linkend| iod_by_section.get(linkend))
+                .find_map(Option::as_ref);
+            if let Some(iod_var_name) = iod_var_name {
+                iod_uid_lookup_phf.entry(uid.clone(), &format!("&iods::{}", iod_var_name));
+                sop_class_uids.insert(uid);
+            }
+        }
+    }
+
+    let mut modules: String = xml_modules
+        .iter()
+        .map(|module| {
+            module_ident_lookup_phf.entry(
+                module.var_name.clone(),
+                &format!("&modules::{}", module.var_name),
+            );
+            module_definition!(
+                module.name,
+                module.reference, // comment placeholders
+                module.var_name,
+                module.var_name,
+                module.name,
+                module.reference,
+                format_attributes(&module.attributes, 1)
+            ) // field placeholders
+        })
+        .collect::<String>();
+
+    let mut iods: String = xml_iods
+        .iter()
+        .map(|iod| {
+            iod_ident_lookup_phf.entry(iod.var_name.clone(), &format!("&iods::{}", iod.var_name));
+            iod_definition!(
+                iod.name,
+                iod.reference, // comment placeholders
+                iod.var_name,
+                iod.var_name,
+                iod.name,
+                iod.reference,
+                format_iod_modules(&iod.modules)
+            ) // field placeholders
+        })
+        .collect::<String>();
+
+    // Remove trailing newlines
+    if modules.len() > 1 {
+        modules.remove(modules.len() - 2);
+    }
+    if iods.len() > 1 {
+        iods.remove(iods.len() - 2);
+    }
+
+    std::fs::create_dir_all(folder)?;
+
+    let path_buf: PathBuf = folder.to_path_buf();
+    let mut modules_file = path_buf.clone();
+    modules_file.push("modules.rs");
+    let mut iods_file = path_buf;
+    iods_file.push("iods.rs");
+
+    save_codefile(modules_file.as_path(), MODULE_PREAMBLE.to_owned(), &modules)?;
+    save_codefile(iods_file.as_path(), IOD_PREAMBLE.to_owned(), &iods)?;
+
+    let mut lookup_file_path: PathBuf = folder.to_path_buf();
+    lookup_file_path.push("iod_lookup.rs");
+
+    let mut lookup_file: BufWriter<File> =
+        BufWriter::new(File::create(lookup_file_path.as_path()).unwrap());
+    write!(&mut lookup_file, "{}", IOD_LOOKUP_PREAMBLE)?;
+
+    write!(
+        &mut lookup_file,
+        "pub static IOD_BY_IDENT: phf::Map<&'static str, IODRef> = "
+    )?;
+    iod_ident_lookup_phf.build(&mut lookup_file)?;
+
+    write!(&mut lookup_file, ";\n\n")?;
+    write!(
+        &mut lookup_file,
+        "pub static IOD_BY_SOP_CLASS_UID: phf::Map<&'static str, IODRef> = "
+    )?;
+    iod_uid_lookup_phf.build(&mut lookup_file)?;
+
+    write!(&mut lookup_file, ";\n\n")?;
+    write!(
+        &mut lookup_file,
+        "pub static MODULE_BY_IDENT: phf::Map<&'static str, ModuleRef> = "
+    )?;
+    module_ident_lookup_phf.build(&mut lookup_file)?;
+    writeln!(&mut lookup_file, ";")?;
+
+    Ok(())
+}
+
+fn read_tables(file: File) -> Vec<XmlDocbookTable> {
+    let bufread: BufReader<File> = BufReader::new(file);
+    XmlDocbookTableIterator::new(bufread)
+        .map(|item: XmlDocbookTableResult| {
+            item.unwrap_or_else(|_| panic!("Error parsing XML docbook table"))
+        })
+        .collect::<Vec<XmlDocbookTable>>()
+}
+
+/// Module and macro tables list attributes by name, tag, and (usually) type
+fn is_attribute_table(table: &XmlDocbookTable) -> bool {
+    table.column("Attribute Name").is_some() && table.column("Tag").is_some()
+}
+
+/// IOD tables list the modules of each information entity, `CT Image IOD Modules`
+fn is_iod_table(table: &XmlDocbookTable) -> bool {
+    table.caption.contains("IOD Modules")
+        && table.column("IE").is_some()
+        && table.column("Module").is_some()
+        && table.column("Reference").is_some()
+        && table.column("Usage").is_some()
+}
+
+/// Processes an IOD table, also processing any modules it refers to which have not already been
+/// processed.
+fn process_iod_table(
+    table: &XmlDocbookTable,
+    part03_tables: &Part03Tables,
+    module_var_names: &mut HashMap<usize, String>,
+    used_var_names: &mut HashSet<String>,
+    xml_modules: &mut Vec<XmlModule>,
+) -> Option<XmlIOD> {
+    let name: String = table
+        .caption
+        .trim_end_matches("Modules")
+        .trim()
+        .to_owned();
+    let var_name: String = unique_var_name(sanitize_var_name(&name), &table.id, used_var_names)?;
+
+    // The IOD table is usually in a subsection of the IOD's section
+    let reference: String = if table.sections.len() > 1 {
+        &table.sections[table.sections.len() - 2]
+    } else {
+        table.section().unwrap_or(&table.id)
+    }
+    .trim_start_matches("sect_")
+    .to_owned();
+
+    let ie_col: usize = table.column("IE")?;
+    let ref_col: usize = table.column("Reference")?;
+    let usage_col: usize = table.column("Usage")?;
+
+    let mut modules: Vec<XmlIODModule> = Vec::new();
+    for row in table.rows.iter() {
+        let (ie, reference, usage) = match (row.get(ie_col), row.get(ref_col), row.get(usage_col))
+        {
+            (Some(ie), Some(reference), Some(usage)) => (ie, reference, usage),
+            _ => continue,
+        };
+        let module_table: usize = match reference
+            .xrefs
+            .iter()
+            .find_map(|linkend| part03_tables.resolve_attr_table(linkend))
+        {
+            Some(module_table) => module_table,
+            None => continue,
+        };
+
+        let module_var_name: String = match module_var_names.get(&module_table) {
+            Some(module_var_name) => module_var_name.clone(),
+            None => {
+                let module: XmlModule = match process_module_table(
+                    module_table,
+                    reference,
+                    part03_tables,
+                    used_var_names,
+                ) {
+                    Some(module) => module,
+                    None => continue,
+                };
+                let module_var_name: String = module.var_name.clone();
+                module_var_names.insert(module_table, module_var_name.clone());
+                xml_modules.push(module);
+                module_var_name
+            }
+        };
+
+        let usage: &'static str = match usage.text.chars().next() {
+            Some('M') => "ModuleUsage::Mandatory",
+            Some('C') => "ModuleUsage::Conditional",
+            _ => "ModuleUsage::UserOption",
+        };
+
+        modules.push(XmlIODModule {
+            ie: ie.text.clone(),
+            module_var_name,
+            usage,
+        });
+    }
+
+    Some(XmlIOD {
+        var_name,
+        name,
+        reference,
+        modules,
+    })
+}
+
+fn process_module_table(
+    module_table: usize,
+    reference: &XmlDocbookCell,
+    part03_tables: &Part03Tables,
+    used_var_names: &mut HashSet<String>,
+) -> Option<XmlModule> {
+    let table: &XmlDocbookTable = &part03_tables.tables[module_table];
+    let name: String = table
+        .caption
+        .trim_end_matches("Attributes")
+        .trim()
+        .to_owned();
+    let var_name: String = unique_var_name(sanitize_var_name(&name), &table.id, used_var_names)?;
+    let reference: String = reference
+        .xrefs
+        .first()
+        .map(String::as_str)
+        .or_else(|| table.section())
+        .unwrap_or(&table.id)
+        .trim_start_matches("sect_")
+        .to_owned();
+
+    let mut attributes: Vec<XmlModuleAttribute> = Vec::new();
+    let mut included: Vec<usize> = Vec::new();
+    read_attributes(module_table, 0, part03_tables, &mut included, &mut attributes);
+
+    Some(XmlModule {
+        var_name,
+        name,
+        reference,
+        attributes: nest_attributes(&attributes),
+    })
+}
+
+/// Reads the attributes of a module or macro table, expanding the macros it includes. The
+/// `included` list tracks the tables currently being expanded, to guard against include cycles.
+fn read_attributes(
+    table_index: usize,
+    depth: usize,
+    part03_tables: &Part03Tables,
+    included: &mut Vec<usize>,
+    attributes: &mut Vec<XmlModuleAttribute>,
+) {
+    if included.contains(&table_index) {
+        return;
+    }
+    included.push(table_index);
+
+    let table: &XmlDocbookTable = &part03_tables.tables[table_index];
+    let name_col: usize = table.column("Attribute Name").unwrap_or(0);
+    let tag_col: Option<usize> = table.column("Tag");
+    let type_col: Option<usize> = table.column("Type");
+
+    for row in table.rows.iter() {
+        let first: &XmlDocbookCell = match row.get(name_col) {
+            Some(first) => first,
+            None => continue,
+        };
+        let row_depth: usize = depth + first.text.chars().take_while(|c| *c == '>').count();
+
+        if first.text.trim_start_matches('>').trim().starts_with("Include") {
+            let include: Option<usize> = first
+                .xrefs
+                .iter()
+                .find_map(|linkend| part03_tables.resolve_attr_table(linkend));
+            if let Some(include) = include {
+                read_attributes(include, row_depth, part03_tables, included, attributes);
+            }
+            continue;
+        }
+
+        let tag: Option<u32> = tag_col
+            .and_then(|col| row.get(col))
+            .and_then(|cell| parse_tag(&cell.text));
+        let tag: u32 = match tag {
+            Some(tag) => tag,
+            None => continue,
+        };
+
+        let attr_type: &'static str = type_col
+            .and_then(|col| row.get(col))
+            .map_or("AttributeType::Type3", |cell| parse_attr_type(&cell.text));
+
+        attributes.push(XmlModuleAttribute {
+            depth: row_depth,
+            tag,
+            attr_type,
+        });
+    }
+
+    included.pop();
+}
+
+/// Parses a tag in the form `(0010,0010)`. Tags defined for repeating groups, such as
+/// `(60xx,0010)`, are parsed as the first group of the range.
+fn parse_tag(text: &str) -> Option<u32> {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '(' && *c != ')')
+        .map(|c| if c == 'x' || c == 'X' { '0' } else { c })
+        .collect::<String>();
+    let mut parts = text.split(',');
+    let group: u32 = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let element: u32 = u32::from_str_radix(parts.next()?, 16).ok()?;
+    if parts.next().is_some() || group > 0xFFFF || element > 0xFFFF {
+        return None;
+    }
+    Some((group << 16) | element)
+}
+
+/// Parses the type of an attribute, attributes with an unrecognized type are treated as Type 3
+fn parse_attr_type(text: &str) -> &'static str {
+    match text.split_whitespace().next().unwrap_or("") {
+        "1" => "AttributeType::Type1",
+        "1C" => "AttributeType::Type1C",
+        "2" => "AttributeType::Type2",
+        "2C" => "AttributeType::Type2C",
+        _ => "AttributeType::Type3",
+    }
+}
+
+/// Builds the flat list of attributes into a tree, where attributes of sequence items are nested
+/// under the sequence attribute preceding them.
+fn nest_attributes(attributes: &[XmlModuleAttribute]) -> Vec<ModuleAttributeNode> {
+    let mut root: Vec<ModuleAttributeNode> = Vec::new();
+    for attr in attributes {
+        let mut level: &mut Vec<ModuleAttributeNode> = &mut root;
+        for _ in 0..attr.depth {
+            if level.is_empty() {
+                break;
+            }
+            let last: usize = level.len() - 1;
+            level = &mut level[last].items;
+        }
+        level.push(ModuleAttributeNode {
+            tag: attr.tag,
+            attr_type: attr.attr_type,
+            items: Vec::new(),
+        });
+    }
+    root
+}
+
+fn format_attributes(attributes: &[ModuleAttributeNode], indent: usize) -> String {
+    if attributes.is_empty() {
+        return "&[]".to_owned();
+    }
+    let pad: String = "    ".repeat(indent);
+    let mut code: String = "&[\n".to_owned();
+    for attr in attributes {
+        code.push_str(&format!(
+            "{}    ModuleAttribute {{\n{}        tag: 0x{:04X}_{:04X},\n{}        attr_type: {},\n{}        items: {},\n{}    }},\n",
+            pad,
+            pad,
+            (attr.tag >> 16) & 0x0000_FFFF,
+            attr.tag & 0x0000_FFFF,
+            pad,
+            attr.attr_type,
+            pad,
+            format_attributes(&attr.items, indent + 2),
+            pad,
+        ));
+    }
+    code.push_str(&pad);
+    code.push(']');
+    code
+}
+
+fn format_iod_modules(modules: &[XmlIODModule]) -> String {
+    if modules.is_empty() {
+        return "&[]".to_owned();
+    }
+    let mut code: String = "&[\n".to_owned();
+    for module in modules {
+        code.push_str(&format!(
+            "        IODModule {{\n            ie: {:?},\n            module: &modules::{},\n            usage: {},\n        }},\n",
+            module.ie,
+            module.module_var_name,
+            module.usage,
+        ));
+    }
+    code.push_str("    ]");
+    code
+}
+
+/// Some modules and IODs share names across sections of Part 3, such definitions after the first
+/// are suffixed with the id of the table they're defined in.
+fn unique_var_name(
+    var_name: String,
+    table_id: &str,
+    used_var_names: &mut HashSet<String>,
+) -> Option<String> {
+    if var_name.is_empty() {
+        return None;
+    }
+    let var_name: String = if used_var_names.contains(&var_name) {
+        format!(
+            "{}_{}",
+            var_name,
+            table_id
+                .trim_start_matches("table_")
+                .replace(".", "_")
+                .replace("-", "_")
+        )
+    } else {
+        var_name
+    };
+    used_var_names.insert(var_name.clone());
+    Some(var_name)
+}
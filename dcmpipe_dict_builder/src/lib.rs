@@ -1,2 +1,8 @@
+pub mod ciddefnwrite;
 pub mod defnwrite;
+pub mod ioddefnwrite;
+pub mod tableparser;
 pub mod xmlparser;
+
+#[cfg(test)]
+mod tests;
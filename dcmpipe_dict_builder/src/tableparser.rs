@@ -0,0 +1,315 @@
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::Error as XmlError;
+use quick_xml::Reader;
+
+pub type XmlDocbookTableResult = Result<XmlDocbookTable, XmlError>;
+
+/// A cell of a DocBook table. Cells spanning multiple rows are repeated in each row they span.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct XmlDocbookCell {
+    /// The text content of the cell, with the text of each paragraph separated by a space
+    pub text: String,
+    /// The target of all cross-references within the cell, in document order
+    pub xrefs: Vec<String>,
+    /// The number of columns this cell spans
+    pub colspan: usize,
+    /// The number of rows this cell spans
+    pub rowspan: usize,
+}
+
+/// A table from one of the DocBook parts of the DICOM standard.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct XmlDocbookTable {
+    /// The `xml:id` of the table, `table_C.7-1`
+    pub id: String,
+    /// The caption of the table, `Patient Module Attributes`
+    pub caption: String,
+    /// The `xml:id` of the sections containing this table, outermost first
+    pub sections: Vec<String>,
+    /// The column names, taken from the last row of the table header
+    pub header: Vec<String>,
+    /// The rows of the table body
+    pub rows: Vec<Vec<XmlDocbookCell>>,
+}
+
+impl XmlDocbookTable {
+    /// Gets the index of the first column whose name starts with the given name
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header.iter().position(|col| col.starts_with(name))
+    }
+
+    /// Gets the `xml:id` of the section directly containing this table
+    pub fn section(&self) -> Option<&str> {
+        self.sections
+            .iter()
+            .rev()
+            .find(|id| !id.is_empty())
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum XmlDocbookReadingState {
+    Off,
+    InCaption,
+    InTableHead,
+    InTableBody,
+}
+
+/// Iterates over all tables with an `xml:id` in a DocBook document. Unlike
+/// `XmlDicomDefinitionIterator` this does not interpret the contents of the tables, which is left
+/// to the caller based on the table's caption and column names.
+pub struct XmlDocbookTableIterator<R: BufRead> {
+    parser: Reader<R>,
+    state: XmlDocbookReadingState,
+
+    /// The `xml:id` of all sections currently open, sections without an id are empty strings
+    sections: Vec<String>,
+    /// Tables nested within a table's cell are not returned, their content is added to the cell
+    nested_tables: usize,
+
+    table: Option<XmlDocbookTable>,
+    row: Option<Vec<XmlDocbookCell>>,
+    cell: Option<XmlDocbookCell>,
+    /// Cells of prior rows which span into following rows, indexed by column, along with the
+    /// number of rows still remaining to span.
+    rowspans: Vec<Option<(XmlDocbookCell, usize)>>,
+}
+
+impl<R: BufRead> XmlDocbookTableIterator<R> {
+    pub fn new(xml: R) -> XmlDocbookTableIterator<R> {
+        let mut reader = Reader::from_reader(xml);
+        reader.expand_empty_elements(true).trim_text(true);
+        XmlDocbookTableIterator {
+            parser: reader,
+            state: XmlDocbookReadingState::Off,
+
+            sections: Vec::new(),
+            nested_tables: 0,
+
+            table: None,
+            row: None,
+            cell: None,
+            rowspans: Vec::new(),
+        }
+    }
+
+    fn parse_text_bytes(&self, data: &BytesText<'_>) -> String {
+        data.unescape_and_decode(&self.parser)
+            .unwrap_or_else(|_| panic!("Error parsing DICOM table text: {:?}", data))
+            .trim()
+            .replace("\u{200b}", "")
+    }
+
+    fn parse_attr(&self, e: &BytesStart<'_>, key: &[u8]) -> Option<String> {
+        e.attributes()
+            .filter_map(|attr| attr.ok())
+            .find(|attr| attr.key == key)
+            .map(|attr| {
+                attr.unescape_and_decode_value(&self.parser)
+                    .unwrap_or_else(|_| panic!("Error parsing DICOM table attribute: {:?}", e))
+            })
+    }
+
+    fn parse_attr_as_usize(&self, e: &BytesStart<'_>, key: &[u8]) -> usize {
+        self.parse_attr(e, key)
+            .and_then(|val| val.parse::<usize>().ok())
+            .filter(|val| *val > 0)
+            .unwrap_or(1)
+    }
+
+    /// Places the cells read for the current row into their columns, filling in any columns
+    /// which are spanned by cells from prior rows.
+    fn finish_row(&mut self, cells: Vec<XmlDocbookCell>) -> Vec<XmlDocbookCell> {
+        let mut row: Vec<XmlDocbookCell> = Vec::new();
+        let mut col: usize = 0;
+        let mut cells = cells.into_iter();
+        loop {
+            if let Some(Some((spanned, remaining))) = self.rowspans.get_mut(col) {
+                // the spanned cell also covers the columns it spans in this row
+                let colspan: usize = spanned.colspan;
+                row.push(spanned.clone());
+                *remaining -= 1;
+                if *remaining == 0 {
+                    self.rowspans[col] = None;
+                }
+                col += colspan;
+                continue;
+            }
+
+            let cell: XmlDocbookCell = match cells.next() {
+                Some(cell) => cell,
+                None => break,
+            };
+            if cell.rowspan > 1 {
+                if self.rowspans.len() <= col {
+                    self.rowspans.resize(col + 1, None);
+                }
+                self.rowspans[col] = Some((cell.clone(), cell.rowspan - 1));
+            }
+            col += cell.colspan;
+            row.push(cell);
+        }
+        row
+    }
+}
+
+impl<R: BufRead> Iterator for XmlDocbookTableIterator<R> {
+    type Item = XmlDocbookTableResult;
+
+    fn next(&mut self) -> Option<XmlDocbookTableResult> {
+        loop {
+            let mut buf: Vec<u8> = Vec::new();
+            let res: Result<Event<'_>, XmlError> = self.parser.read_event(&mut buf);
+            match res {
+                Ok(Event::Start(ref e)) => {
+                    let local_name: &[u8] = e.local_name();
+                    if local_name == b"section" {
+                        let id: String = self.parse_attr(e, b"xml:id").unwrap_or_default();
+                        self.sections.push(id);
+                        continue;
+                    }
+
+                    if local_name == b"table" {
+                        if self.state == XmlDocbookReadingState::Off {
+                            if let Some(id) = self.parse_attr(e, b"xml:id") {
+                                self.table = Some(XmlDocbookTable {
+                                    id,
+                                    sections: self.sections.clone(),
+                                    ..XmlDocbookTable::default()
+                                });
+                                self.rowspans.clear();
+                                self.state = XmlDocbookReadingState::InTableHead;
+                            }
+                        } else {
+                            self.nested_tables += 1;
+                        }
+                        continue;
+                    }
+
+                    if self.state == XmlDocbookReadingState::Off || self.nested_tables > 0 {
+                        continue;
+                    }
+
+                    match local_name {
+                        b"caption" => self.state = XmlDocbookReadingState::InCaption,
+                        b"thead" => self.state = XmlDocbookReadingState::InTableHead,
+                        b"tbody" => self.state = XmlDocbookReadingState::InTableBody,
+                        b"tr" => self.row = Some(Vec::new()),
+                        b"td" | b"th" => {
+                            self.cell = Some(XmlDocbookCell {
+                                colspan: self.parse_attr_as_usize(e, b"colspan"),
+                                rowspan: self.parse_attr_as_usize(e, b"rowspan"),
+                                ..XmlDocbookCell::default()
+                            });
+                        }
+                        b"xref" | b"olink" => {
+                            // references to other parts of the standard are olinks
+                            let key: &[u8] = if local_name == b"xref" {
+                                b"linkend"
+                            } else {
+                                b"targetptr"
+                            };
+                            if let Some(linkend) = self.parse_attr(e, key) {
+                                if let Some(cell) = self.cell.as_mut() {
+                                    cell.xrefs.push(linkend);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(ref e)) => {
+                    let local_name: &[u8] = e.local_name();
+                    if local_name == b"section" {
+                        self.sections.pop();
+                        continue;
+                    }
+
+                    if self.state == XmlDocbookReadingState::Off {
+                        continue;
+                    }
+
+                    if local_name == b"table" {
+                        if self.nested_tables > 0 {
+                            self.nested_tables -= 1;
+                            continue;
+                        }
+                        self.state = XmlDocbookReadingState::Off;
+                        self.row = None;
+                        self.cell = None;
+                        if let Some(table) = self.table.take() {
+                            return Some(Ok(table));
+                        }
+                        continue;
+                    }
+
+                    if self.nested_tables > 0 {
+                        continue;
+                    }
+
+                    match local_name {
+                        b"caption" => self.state = XmlDocbookReadingState::InTableHead,
+                        b"td" | b"th" => {
+                            if let (Some(row), Some(cell)) = (self.row.as_mut(), self.cell.take()) {
+                                row.push(cell);
+                            }
+                        }
+                        b"tr" => {
+                            if let Some(cells) = self.row.take() {
+                                match self.state {
+                                    XmlDocbookReadingState::InTableHead => {
+                                        if let Some(table) = self.table.as_mut() {
+                                            table.header =
+                                                cells.into_iter().map(|cell| cell.text).collect();
+                                        }
+                                    }
+                                    XmlDocbookReadingState::InTableBody => {
+                                        let row: Vec<XmlDocbookCell> = self.finish_row(cells);
+                                        if let Some(table) = self.table.as_mut() {
+                                            table.rows.push(row);
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Text(data)) => {
+                    if self.state == XmlDocbookReadingState::Off {
+                        continue;
+                    }
+                    let text: String = self.parse_text_bytes(&data);
+                    if text.is_empty() {
+                        continue;
+                    }
+                    let target: Option<&mut String> =
+                        if self.state == XmlDocbookReadingState::InCaption {
+                            self.table.as_mut().map(|table| &mut table.caption)
+                        } else {
+                            self.cell.as_mut().map(|cell| &mut cell.text)
+                        };
+                    if let Some(target) = target {
+                        if !target.is_empty() {
+                            target.push(' ');
+                        }
+                        target.push_str(&text);
+                    }
+                }
+                Ok(Event::Eof { .. }) => {
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        None
+    }
+}
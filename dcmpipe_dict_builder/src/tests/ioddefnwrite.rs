@@ -0,0 +1,250 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::ioddefnwrite::process_iod_xml_files;
+
+static PART03: &str = r#"<book>
+<section xml:id="sect_A.3">
+  <section xml:id="sect_A.3.3">
+    <table xml:id="table_A.3-1">
+      <caption>CT Image IOD Modules</caption>
+      <thead><tr><th>IE</th><th>Module</th><th>Reference</th><th>Usage</th></tr></thead>
+      <tbody>
+        <tr>
+          <td rowspan="2">Patient</td>
+          <td>Patient</td>
+          <td><xref linkend="sect_C.7.1.1"/></td>
+          <td>M</td>
+        </tr>
+        <tr>
+          <td>Clinical Trial Subject</td>
+          <td><xref linkend="sect_C.7.1.3"/></td>
+          <td>U</td>
+        </tr>
+        <tr>
+          <td>Image</td>
+          <td>Contrast/Bolus</td>
+          <td><xref linkend="sect_C.7.6.4"/></td>
+          <td>C - Required if contrast media was used in this image</td>
+        </tr>
+      </tbody>
+    </table>
+  </section>
+</section>
+<section xml:id="sect_C.7.1.1">
+  <table xml:id="table_C.7-1">
+    <caption>Patient Module Attributes</caption>
+    <thead>
+      <tr><th>Attribute Name</th><th>Tag</th><th>Type</th><th>Attribute Description</th></tr>
+    </thead>
+    <tbody>
+      <tr><td>Patient's Name</td><td>(0010,0010)</td><td>2</td><td>Name.</td></tr>
+      <tr><td>Issuer of Patient ID Qualifiers Sequence</td><td>(0010,0024)</td><td>3</td><td/></tr>
+      <tr>
+        <td>&gt;Include <xref linkend="table_10-1"/></td>
+        <td colspan="3">Defined Context ID is 1.</td>
+      </tr>
+      <tr><td>Overlay Data</td><td>(60xx,3000)</td><td>1C</td><td/></tr>
+    </tbody>
+  </table>
+</section>
+<section xml:id="sect_C.7.1.3">
+  <section xml:id="sect_C.7.1.3.1">
+    <table xml:id="table_C.7-2b">
+      <caption>Clinical Trial Subject Module Attributes</caption>
+      <thead><tr><th>Attribute Name</th><th>Tag</th><th>Type</th></tr></thead>
+      <tbody>
+        <tr><td>Clinical Trial Sponsor Name</td><td>(0012,0010)</td><td>1</td></tr>
+      </tbody>
+    </table>
+  </section>
+</section>
+<section xml:id="sect_C.7.6.4">
+  <table xml:id="table_C.7-13">
+    <caption>Contrast/Bolus Module Attributes</caption>
+    <thead><tr><th>Attribute Name</th><th>Tag</th><th>Type</th></tr></thead>
+    <tbody>
+      <tr><td>Contrast/Bolus Agent</td><td>(0018,0010)</td><td>2</td></tr>
+    </tbody>
+  </table>
+</section>
+<section xml:id="sect_10.1">
+  <table xml:id="table_10-1">
+    <caption>Code Sequence Macro Attributes</caption>
+    <thead><tr><th>Attribute Name</th><th>Tag</th><th>Type</th></tr></thead>
+    <tbody>
+      <tr><td>Code Value</td><td>(0008,0100)</td><td>1C</td></tr>
+      <tr><td>Include <xref linkend="table_10-1"/></td><td/><td/></tr>
+    </tbody>
+  </table>
+</section>
+</book>"#;
+
+static PART04: &str = r#"<book>
+<table xml:id="table_B.5-1">
+  <caption>Standard SOP Classes</caption>
+  <thead>
+    <tr><th>SOP Class Name</th><th>SOP Class UID</th><th>IOD Specification (defined in PS3.3)</th></tr>
+  </thead>
+  <tbody>
+    <tr>
+      <td>CT Image Storage</td>
+      <td>1.2.840.10008.5.1.4.1.1.2</td>
+      <td><olink targetptr="sect_A.3"/></td>
+    </tr>
+    <tr>
+      <td>Unknown Storage</td>
+      <td>1.2.3</td>
+      <td><olink targetptr="sect_A.99"/></td>
+    </tr>
+  </tbody>
+</table>
+</book>"#;
+
+/// Writes the contents to a file within the folder, returning the file opened for reading.
+fn create_file(folder: &Path, name: &str, contents: &str) -> File {
+    let path: PathBuf = folder.join(name);
+    fs::write(&path, contents).expect("Unable to write XML file");
+    File::open(path).expect("Unable to open XML file")
+}
+
+fn read_file(folder: &Path, name: &str) -> String {
+    fs::read_to_string(folder.join(name)).expect("Unable to read generated file")
+}
+
+#[test]
+fn test_process_iod_xml_files() {
+    let folder: PathBuf =
+        std::env::temp_dir().join(format!("dcmpipe_ioddefnwrite_{}", std::process::id()));
+    fs::create_dir_all(&folder).expect("Unable to create folder");
+    let part03: File = create_file(&folder, "part03.xml", PART03);
+    let part04: File = create_file(&folder, "part04.xml", PART04);
+    process_iod_xml_files(part03, part04, &folder).expect("Failed to process XML files");
+
+    let modules: String = read_file(&folder, "modules.rs");
+    let iods: String = read_file(&folder, "iods.rs");
+    let iod_lookup: String = read_file(&folder, "iod_lookup.rs");
+    fs::remove_dir_all(&folder).expect("Unable to remove folder");
+
+    // the included macro is nested within the sequence, and isn't expanded within itself
+    assert!(modules.contains(
+        "/// Patient Module
+///
+/// - **Reference:** C.7.1.1
+pub static PatientModule: Module = Module {
+    ident: \"PatientModule\",
+    name: \"Patient Module\",
+    reference: \"C.7.1.1\",
+    attributes: &[
+        ModuleAttribute {
+            tag: 0x0010_0010,
+            attr_type: AttributeType::Type2,
+            items: &[],
+        },
+        ModuleAttribute {
+            tag: 0x0010_0024,
+            attr_type: AttributeType::Type3,
+            items: &[
+                ModuleAttribute {
+                    tag: 0x0008_0100,
+                    attr_type: AttributeType::Type1C,
+                    items: &[],
+                },
+            ],
+        },
+        ModuleAttribute {
+            tag: 0x6000_3000,
+            attr_type: AttributeType::Type1C,
+            items: &[],
+        },
+    ],
+};"
+    ));
+    // the module's section resolves to the table nested within its subsection
+    assert!(modules.contains("pub static ClinicalTrialSubjectModule: Module"));
+    assert!(modules.contains("pub static ContrastBolusModule: Module"));
+    assert!(!modules.contains("CodeSequenceMacro"));
+
+    assert!(iods.contains(
+        "/// CT Image IOD
+///
+/// - **Reference:** A.3
+pub static CTImageIOD: IOD = IOD {
+    ident: \"CTImageIOD\",
+    name: \"CT Image IOD\",
+    reference: \"A.3\",
+    modules: &[
+        IODModule {
+            ie: \"Patient\",
+            module: &modules::PatientModule,
+            usage: ModuleUsage::Mandatory,
+        },
+        IODModule {
+            ie: \"Patient\",
+            module: &modules::ClinicalTrialSubjectModule,
+            usage: ModuleUsage::UserOption,
+        },
+        IODModule {
+            ie: \"Image\",
+            module: &modules::ContrastBolusModule,
+            usage: ModuleUsage::Conditional,
+        },
+    ],
+};"
+    ));
+
+    assert!(iod_lookup.contains("(\"CTImageIOD\", &iods::CTImageIOD)"));
+    assert!(iod_lookup.contains("(\"1.2.840.10008.5.1.4.1.1.2\", &iods::CTImageIOD)"));
+    assert!(!iod_lookup.contains("\"1.2.3\""));
+    assert!(iod_lookup.contains("(\"PatientModule\", &modules::PatientModule)"));
+}
+
+#[test]
+fn test_strings_are_escaped() {
+    let folder: PathBuf = std::env::temp_dir().join(format!(
+        "dcmpipe_ioddefnwrite_escape_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&folder).expect("Unable to create folder");
+    let part03: File = create_file(
+        &folder,
+        "part03.xml",
+        r#"<book>
+<section xml:id="sect_A.1">
+  <section xml:id="sect_A.1.1">
+    <table xml:id="table_A.1-1">
+      <caption>"Quoted" IOD Modules</caption>
+      <thead><tr><th>IE</th><th>Module</th><th>Reference</th><th>Usage</th></tr></thead>
+      <tbody>
+        <tr>
+          <td>Back\slash "IE"</td>
+          <td>Patient</td>
+          <td><xref linkend="sect_C.1"/></td>
+          <td>M</td>
+        </tr>
+      </tbody>
+    </table>
+  </section>
+</section>
+<section xml:id="sect_C.1">
+  <table xml:id="table_C.1-1">
+    <caption>"Quoted" Module Attributes</caption>
+    <thead><tr><th>Attribute Name</th><th>Tag</th><th>Type</th></tr></thead>
+    <tbody>
+      <tr><td>Patient's Name</td><td>(0010,0010)</td><td>2</td></tr>
+    </tbody>
+  </table>
+</section>
+</book>"#,
+    );
+    let part04: File = create_file(&folder, "part04.xml", "<book/>");
+    process_iod_xml_files(part03, part04, &folder).expect("Failed to process XML files");
+
+    let modules: String = read_file(&folder, "modules.rs");
+    let iods: String = read_file(&folder, "iods.rs");
+    fs::remove_dir_all(&folder).expect("Unable to remove folder");
+
+    assert!(modules.contains("    name: \"\\\"Quoted\\\" Module\",\n"));
+    assert!(iods.contains("    name: \"\\\"Quoted\\\" IOD\",\n"));
+    assert!(iods.contains("            ie: \"Back\\\\slash \\\"IE\\\"\",\n"));
+}
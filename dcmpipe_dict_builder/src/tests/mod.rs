@@ -0,0 +1,2 @@
+mod ioddefnwrite;
+mod tableparser;
@@ -0,0 +1,119 @@
+use crate::tableparser::{XmlDocbookCell, XmlDocbookTable, XmlDocbookTableIterator};
+
+fn read_tables(xml: &str) -> Vec<XmlDocbookTable> {
+    XmlDocbookTableIterator::new(xml.as_bytes())
+        .collect::<Result<Vec<XmlDocbookTable>, _>>()
+        .expect("tables should parse")
+}
+
+fn cell_text(row: &[XmlDocbookCell]) -> Vec<&str> {
+    row.iter().map(|cell| cell.text.as_str()).collect()
+}
+
+#[test]
+fn test_table_contents() {
+    let tables: Vec<XmlDocbookTable> = read_tables(
+        r#"<book>
+        <section xml:id="sect_C.7.1">
+          <section>
+            <section xml:id="sect_C.7.1.1">
+              <table xml:id="table_C.7-1">
+                <caption>Patient Module Attributes</caption>
+                <thead>
+                  <tr><th><para>Attribute Name</para></th><th><para>Tag</para></th></tr>
+                </thead>
+                <tbody>
+                  <tr>
+                    <td><para>Patient's Name</para></td>
+                    <td><para>(0010,0010)</para></td>
+                  </tr>
+                  <tr>
+                    <td>
+                      <para>Include <xref linkend="table_10-18"/> and</para>
+                      <para><olink targetptr="sect_10.2"/></para>
+                    </td>
+                    <td>
+                      <table><tbody><tr><td><para>nested</para></td></tr></tbody></table>
+                    </td>
+                  </tr>
+                </tbody>
+              </table>
+            </section>
+          </section>
+        </section>
+        <table><caption>Without an id</caption></table>
+        </book>"#,
+    );
+    assert_eq!(tables.len(), 1);
+
+    let table: &XmlDocbookTable = &tables[0];
+    assert_eq!(table.id, "table_C.7-1");
+    assert_eq!(table.caption, "Patient Module Attributes");
+    assert_eq!(table.sections, vec!["sect_C.7.1", "", "sect_C.7.1.1"]);
+    assert_eq!(table.section(), Some("sect_C.7.1.1"));
+    assert_eq!(table.header, vec!["Attribute Name", "Tag"]);
+    assert_eq!(table.column("Tag"), Some(1));
+    assert_eq!(table.column("Type"), None);
+
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(
+        cell_text(&table.rows[0]),
+        vec!["Patient's Name", "(0010,0010)"]
+    );
+    assert_eq!(table.rows[1][0].text, "Include and");
+    assert_eq!(table.rows[1][0].xrefs, vec!["table_10-18", "sect_10.2"]);
+    // the contents of a nested table are added to the cell containing it
+    assert_eq!(table.rows[1][1].text, "nested");
+}
+
+#[test]
+fn test_spanned_cells_are_repeated() {
+    let tables: Vec<XmlDocbookTable> = read_tables(
+        r#"<table xml:id="table_A.3-1">
+          <thead><tr><th>IE</th><th>Module</th><th>Reference</th><th>Usage</th></tr></thead>
+          <tbody>
+            <tr><td rowspan="3">Patient</td><td>Patient</td><td>C.7.1.1</td><td>M</td></tr>
+            <tr><td>Clinical Trial Subject</td><td>C.7.1.3</td><td>U</td></tr>
+            <tr><td colspan="2" rowspan="2">Spans</td><td>M</td></tr>
+            <tr><td>Study</td><td>U</td></tr>
+          </tbody>
+        </table>"#,
+    );
+    let rows: &Vec<Vec<XmlDocbookCell>> = &tables[0].rows;
+    assert_eq!(
+        rows.iter()
+            .map(|row| cell_text(row))
+            .collect::<Vec<Vec<&str>>>(),
+        vec![
+            vec!["Patient", "Patient", "C.7.1.1", "M"],
+            vec!["Patient", "Clinical Trial Subject", "C.7.1.3", "U"],
+            vec!["Patient", "Spans", "M"],
+            vec!["Study", "Spans", "U"],
+        ]
+    );
+    assert_eq!(rows[3][1].colspan, 2);
+}
+
+#[test]
+fn test_cell_spanning_rows_and_columns() {
+    // the spanned cell covers both of its columns in the second row, so the cell from the first
+    // row spanning the last column is also repeated
+    let tables: Vec<XmlDocbookTable> = read_tables(
+        r#"<table xml:id="table_1">
+          <thead><tr><th>A</th><th>B</th><th>C</th></tr></thead>
+          <tbody>
+            <tr><td colspan="2" rowspan="2">AB</td><td rowspan="2">C</td></tr>
+            <tr></tr>
+            <tr><td>A</td><td>B</td><td>C</td></tr>
+          </tbody>
+        </table>"#,
+    );
+    assert_eq!(
+        tables[0]
+            .rows
+            .iter()
+            .map(|row| cell_text(row))
+            .collect::<Vec<Vec<&str>>>(),
+        vec![vec!["AB", "C"], vec!["AB", "C"], vec!["A", "B", "C"]]
+    );
+}
@@ -0,0 +1,27 @@
+//! Element values which are skipped over while parsing instead of being read into memory, see
+//! `ParserBuilder::bulk_data_threshold`.
+
+use std::io::{Read, Result, Seek, SeekFrom};
+
+/// The location of an element's value within the dataset it was parsed from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BulkDataRef {
+    /// The byte position of the value field, relative to where the parser started reading the
+    /// dataset.
+    pub offset: u64,
+    /// The number of bytes in the value field.
+    pub length: u32,
+}
+
+impl BulkDataRef {
+    /// Reads the value from the given source, which must be the dataset the element was parsed
+    /// from and which the parser started reading from its beginning. As with values read during
+    /// parsing, a value with odd length is padded with a zero to be even length.
+    pub fn load<SourceType: Read + Seek>(&self, source: &mut SourceType) -> Result<Vec<u8>> {
+        source.seek(SeekFrom::Start(self.offset))?;
+        let length: usize = self.length as usize;
+        let mut buffer: Vec<u8> = vec![0; length + length % 2];
+        source.read_exact(&mut buffer[0..length])?;
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,769 @@
+//! Character sets using ISO 2022 code extensions, where a single value may switch between several
+//! character repertoires with escape sequences.
+//! See DICOM Part 5 Section 6.1.2.5 Use of Code Extension Techniques
+//! http://dicom.nema.org/medical/dicom/current/output/chtml/part05/sect_6.1.2.5.html
+
+use std::sync::Mutex;
+
+use encoding::all;
+use encoding::{
+    ByteWriter, CodecError, DecoderTrap, EncoderTrap, Encoding, EncodingRef, RawDecoder,
+    RawEncoder, StringWriter,
+};
+
+use crate::core::charset::CSRef;
+
+/// The escape character, which begins all escape sequences.
+const ESC: u8 = 0x1B;
+/// The character code for `\`, the value delimiter.
+const BACKSLASH: u8 = 0x5C;
+
+/// How the characters of a graphic set are encoded once it has been designated.
+enum SetKind {
+    /// ISO 646, ASCII
+    Ascii,
+    /// JIS X 0201 Romaji, which is ASCII except for 05/12 (Yen) and 07/14 (Overline)
+    Romaji,
+    /// JIS X 0201 Katakana, half-width katakana in the upper half of the code table
+    Katakana,
+    /// The upper half of an ISO 8859 style single-byte character set
+    Upper(EncodingRef),
+    /// JIS X 0208, two-byte Kanji in the lower half of the code table
+    JisX0208,
+    /// JIS X 0212, two-byte supplementary Kanji in the lower half of the code table
+    JisX0212,
+    /// KS X 1001, two-byte Hangul and Hanja in the upper half of the code table
+    KsX1001,
+    /// GB 2312, two-byte Simplified Chinese in the upper half of the code table
+    Gb2312,
+}
+
+/// A graphic character set which can be designated to G0 or G1 by an escape sequence.
+struct GraphicSet {
+    /// The bytes of the escape sequence which designates this set, excluding the ESC.
+    escape: &'static [u8],
+    /// Whether this set is designated to G1 (upper half) or G0 (lower half)
+    is_g1: bool,
+    kind: SetKind,
+}
+
+impl GraphicSet {
+    fn is_multi_byte(&self) -> bool {
+        matches!(
+            self.kind,
+            SetKind::JisX0208 | SetKind::JisX0212 | SetKind::KsX1001 | SetKind::Gb2312
+        )
+    }
+
+    /// Encodes the given character with this set, `None` if the set does not contain it.
+    fn encode_char(&self, c: char) -> Option<Vec<u8>> {
+        match self.kind {
+            SetKind::Ascii => {
+                if c.is_ascii() {
+                    Some(vec![c as u8])
+                } else {
+                    None
+                }
+            }
+            SetKind::Romaji => match c {
+                '\u{A5}' => Some(vec![0x5C]),
+                '\u{203E}' => Some(vec![0x7E]),
+                '\\' | '~' => None,
+                _ if c.is_ascii() => Some(vec![c as u8]),
+                _ => None,
+            },
+            SetKind::Katakana => match c {
+                '\u{FF61}'..='\u{FF9F}' => Some(vec![(c as u32 - 0xFF61 + 0xA1) as u8]),
+                _ => None,
+            },
+            SetKind::Upper(cs) => match encode_with(cs, c) {
+                Some(bytes) if bytes.len() == 1 && bytes[0] >= 0xA0 => Some(bytes),
+                _ => None,
+            },
+            SetKind::JisX0208 => match encode_with(all::EUC_JP, c) {
+                Some(bytes) if is_two_byte_upper(&bytes) => {
+                    Some(vec![bytes[0] & 0x7F, bytes[1] & 0x7F])
+                }
+                _ => None,
+            },
+            // The EUC-JP encoder does not support encoding to JIS X 0212
+            SetKind::JisX0212 => None,
+            SetKind::KsX1001 => match encode_with(all::WINDOWS_949, c) {
+                Some(bytes) if is_two_byte_upper(&bytes) => Some(bytes),
+                _ => None,
+            },
+            SetKind::Gb2312 => match encode_with(all::GBK, c) {
+                Some(bytes) if is_two_byte_upper(&bytes) => Some(bytes),
+                _ => None,
+            },
+        }
+    }
+
+    /// Decodes a single character of this set. The given bytes must be the number of bytes used
+    /// by a character of this set, with the high bit as they appear in the value.
+    fn decode_char(&self, bytes: &[u8]) -> Option<String> {
+        match self.kind {
+            SetKind::Ascii => Some((bytes[0] as char).to_string()),
+            SetKind::Romaji => match bytes[0] {
+                0x5C => Some('\u{A5}'.to_string()),
+                0x7E => Some('\u{203E}'.to_string()),
+                b => Some((b as char).to_string()),
+            },
+            SetKind::Katakana => match bytes[0] {
+                b @ 0xA1..=0xDF => {
+                    std::char::from_u32(u32::from(b) - 0xA1 + 0xFF61).map(|c| c.to_string())
+                }
+                _ => None,
+            },
+            SetKind::Upper(cs) => decode_with(cs, bytes),
+            SetKind::JisX0208 => decode_with(all::EUC_JP, &[bytes[0] | 0x80, bytes[1] | 0x80]),
+            SetKind::JisX0212 => {
+                decode_with(all::EUC_JP, &[0x8F, bytes[0] | 0x80, bytes[1] | 0x80])
+            }
+            SetKind::KsX1001 => decode_with(all::WINDOWS_949, bytes),
+            SetKind::Gb2312 => decode_with(all::GBK, bytes),
+        }
+    }
+}
+
+static ASCII: GraphicSet = GraphicSet {
+    escape: b"(B",
+    is_g1: false,
+    kind: SetKind::Ascii,
+};
+static ROMAJI: GraphicSet = GraphicSet {
+    escape: b"(J",
+    is_g1: false,
+    kind: SetKind::Romaji,
+};
+static KATAKANA: GraphicSet = GraphicSet {
+    escape: b")I",
+    is_g1: true,
+    kind: SetKind::Katakana,
+};
+static LATIN1: GraphicSet = GraphicSet {
+    escape: b"-A",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_1 as EncodingRef),
+};
+static LATIN2: GraphicSet = GraphicSet {
+    escape: b"-B",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_2 as EncodingRef),
+};
+static LATIN3: GraphicSet = GraphicSet {
+    escape: b"-C",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_3 as EncodingRef),
+};
+static LATIN4: GraphicSet = GraphicSet {
+    escape: b"-D",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_4 as EncodingRef),
+};
+static CYRILLIC: GraphicSet = GraphicSet {
+    escape: b"-L",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_5 as EncodingRef),
+};
+static ARABIC: GraphicSet = GraphicSet {
+    escape: b"-G",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_6 as EncodingRef),
+};
+static GREEK: GraphicSet = GraphicSet {
+    escape: b"-F",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_7 as EncodingRef),
+};
+static HEBREW: GraphicSet = GraphicSet {
+    escape: b"-H",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_8 as EncodingRef),
+};
+// The upper half of Windows-1254 matches ISO 8859-9
+static LATIN5: GraphicSet = GraphicSet {
+    escape: b"-M",
+    is_g1: true,
+    kind: SetKind::Upper(all::WINDOWS_1254 as EncodingRef),
+};
+static LATIN9: GraphicSet = GraphicSet {
+    escape: b"-b",
+    is_g1: true,
+    kind: SetKind::Upper(all::ISO_8859_15 as EncodingRef),
+};
+// The upper half of Windows-874 matches TIS 620-2533
+static THAI: GraphicSet = GraphicSet {
+    escape: b"-T",
+    is_g1: true,
+    kind: SetKind::Upper(all::WINDOWS_874 as EncodingRef),
+};
+static JIS_X_0208: GraphicSet = GraphicSet {
+    escape: b"$B",
+    is_g1: false,
+    kind: SetKind::JisX0208,
+};
+static JIS_X_0212: GraphicSet = GraphicSet {
+    escape: b"$(D",
+    is_g1: false,
+    kind: SetKind::JisX0212,
+};
+static KS_X_1001: GraphicSet = GraphicSet {
+    escape: b"$)C",
+    is_g1: true,
+    kind: SetKind::KsX1001,
+};
+static GB_2312: GraphicSet = GraphicSet {
+    escape: b"$)A",
+    is_g1: true,
+    kind: SetKind::Gb2312,
+};
+
+static GRAPHIC_SETS: [&GraphicSet; 18] = [
+    &ASCII,
+    &ROMAJI,
+    &KATAKANA,
+    &LATIN1,
+    &LATIN2,
+    &LATIN3,
+    &LATIN4,
+    &CYRILLIC,
+    &ARABIC,
+    &GREEK,
+    &HEBREW,
+    &LATIN5,
+    &LATIN9,
+    &THAI,
+    &JIS_X_0208,
+    &JIS_X_0212,
+    &KS_X_1001,
+    &GB_2312,
+];
+
+/// A Defined Term of Specific Character Set which uses code extensions, and the graphic sets it
+/// designates to G0 and G1.
+/// See Part 3 Table C.12-3 and C.12-4
+struct CodeElement {
+    term: &'static str,
+    g0: Option<&'static GraphicSet>,
+    g1: Option<&'static GraphicSet>,
+}
+
+static CODE_ELEMENTS: [CodeElement; 17] = [
+    CodeElement {
+        term: "ISO 2022 IR 6",
+        g0: Some(&ASCII),
+        g1: None,
+    },
+    CodeElement {
+        term: "ISO 2022 IR 100",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN1),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 101",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN2),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 109",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN3),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 110",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN4),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 144",
+        g0: Some(&ASCII),
+        g1: Some(&CYRILLIC),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 127",
+        g0: Some(&ASCII),
+        g1: Some(&ARABIC),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 126",
+        g0: Some(&ASCII),
+        g1: Some(&GREEK),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 138",
+        g0: Some(&ASCII),
+        g1: Some(&HEBREW),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 148",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN5),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 203",
+        g0: Some(&ASCII),
+        g1: Some(&LATIN9),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 13",
+        g0: Some(&ROMAJI),
+        g1: Some(&KATAKANA),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 166",
+        g0: Some(&ASCII),
+        g1: Some(&THAI),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 87",
+        g0: Some(&JIS_X_0208),
+        g1: None,
+    },
+    CodeElement {
+        term: "ISO 2022 IR 159",
+        g0: Some(&JIS_X_0212),
+        g1: None,
+    },
+    CodeElement {
+        term: "ISO 2022 IR 149",
+        g0: None,
+        g1: Some(&KS_X_1001),
+    },
+    CodeElement {
+        term: "ISO 2022 IR 58",
+        g0: None,
+        g1: Some(&GB_2312),
+    },
+];
+
+/// Character sets which have been created for a Specific Character Set value. These are kept for
+/// the lifetime of the program as `CSRef` requires a static reference.
+static CHARSETS: Mutex<Vec<&'static DicomIso2022Encoding>> = Mutex::new(Vec::new());
+
+/// The maximum number of character sets which are created. Datasets only use a handful of the
+/// combinations of Defined Terms, so this bounds the memory kept by `CHARSETS` without limiting
+/// any reasonable dataset.
+const MAX_CHARSETS: usize = 64;
+
+/// Normalizes a Defined Term for comparison, the same as `lookup_charset` does. Terms for single
+/// byte character sets without code extensions, `ISO_IR 100`, are treated as their equivalent term
+/// with code extensions, `ISO 2022 IR 100`.
+fn normalize_term(term: &str) -> String {
+    let term: String = term
+        .chars()
+        .filter(|c: &char| !c.is_whitespace() && *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match term.strip_prefix("isoir") {
+        Some(rest) => format!("iso2022ir{}", rest),
+        None => term,
+    }
+}
+
+/// Looks up the index of the code element within `CODE_ELEMENTS` for the Defined Term.
+fn lookup_code_element(term: &str) -> Option<usize> {
+    let term: String = normalize_term(term);
+    CODE_ELEMENTS
+        .iter()
+        .position(|element: &CodeElement| normalize_term(element.term) == term)
+}
+
+/// Whether the given value of Specific Character Set is a Defined Term which uses code extensions.
+pub(crate) fn is_iso2022_term(term: &str) -> bool {
+    normalize_term(term).starts_with("iso2022")
+}
+
+/// Looks up the character set for the values of Specific Character Set (0008,0005) which use
+/// code extensions. An empty first value indicates the default character repertoire. Returns
+/// `None` if any of the values is not a recognized Defined Term, or if `MAX_CHARSETS` different
+/// character sets have already been created.
+pub(crate) fn lookup_iso2022_charset(values: &[&str]) -> Option<CSRef> {
+    // The first value determines the initial state. The order of the others doesn't affect
+    // decoding, so they're kept as a set, in the order of `CODE_ELEMENTS`.
+    let mut first: Option<usize> = None;
+    let mut others: u32 = 0;
+    for (index, value) in values.iter().enumerate() {
+        let element: usize = if value.is_empty() && index == 0 {
+            0
+        } else if value.is_empty() {
+            continue;
+        } else {
+            lookup_code_element(value)?
+        };
+        match first {
+            None => first = Some(element),
+            Some(first) if first != element => others |= 1 << element,
+            Some(_) => {}
+        }
+    }
+    let key: (usize, u32) = (first?, others);
+
+    let mut charsets = CHARSETS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cs) = charsets.iter().find(|cs| cs.key == key) {
+        return Some(*cs as CSRef);
+    }
+    if charsets.len() >= MAX_CHARSETS {
+        return None;
+    }
+
+    let elements: Vec<&'static CodeElement> = std::iter::once(key.0)
+        .chain((0..CODE_ELEMENTS.len()).filter(|index: &usize| others & (1 << index) != 0))
+        .map(|index: usize| &CODE_ELEMENTS[index])
+        .collect::<Vec<&'static CodeElement>>();
+    let name: String = elements
+        .iter()
+        .map(|element: &&CodeElement| element.term)
+        .collect::<Vec<&str>>()
+        .join("\\");
+
+    let first: &'static CodeElement = elements[0];
+    let initial: CodeState = CodeState {
+        // The multi-byte sets which are designated to G0 can't be the initial G0 set, as then the
+        // delimiters of the default character repertoire would not be available.
+        g0: first.g0.filter(|g0| !g0.is_multi_byte()).unwrap_or(&ASCII),
+        g1: first.g1,
+    };
+    let cs: &'static DicomIso2022Encoding = Box::leak(Box::new(DicomIso2022Encoding {
+        name: Box::leak(name.into_boxed_str()),
+        key,
+        initial,
+        elements,
+    }));
+    charsets.push(cs);
+    Some(cs as CSRef)
+}
+
+/// The graphic sets currently designated to G0 and G1.
+#[derive(Clone, Copy)]
+struct CodeState {
+    g0: &'static GraphicSet,
+    g1: Option<&'static GraphicSet>,
+}
+
+impl CodeState {
+    fn designate(&mut self, set: &'static GraphicSet) {
+        if set.is_g1 {
+            self.g1 = Some(set);
+        } else {
+            self.g0 = set;
+        }
+    }
+
+    fn is_designated(&self, set: &'static GraphicSet) -> bool {
+        if set.is_g1 {
+            self.g1.map_or(false, |g1| std::ptr::eq(g1, set))
+        } else {
+            std::ptr::eq(self.g0, set)
+        }
+    }
+
+    /// Writes the escape sequences needed to change from this state to the given state.
+    fn write_escapes_to(&self, other: &CodeState, output: &mut dyn ByteWriter) {
+        if !std::ptr::eq(self.g0, other.g0) {
+            output.write_byte(ESC);
+            output.write_bytes(other.g0.escape);
+        }
+        if let Some(g1) = other.g1 {
+            if !self.is_designated(g1) {
+                output.write_byte(ESC);
+                output.write_bytes(g1.escape);
+            }
+        }
+    }
+}
+
+/// A character set for the values of Specific Character Set which use code extensions. Values
+/// begin in the state of the first value of Specific Character Set, and escape sequences within
+/// the value switch to the repertoires of the other values. The state is reset to the initial
+/// state at the end of lines and the `^`, `=`, and `\` delimiters.
+pub struct DicomIso2022Encoding {
+    /// The Defined Terms of this character set, separated by `\`
+    name: &'static str,
+    /// The index of the first code element and the set of the other code elements' indices
+    key: (usize, u32),
+    /// The state at the start of each value and after delimiters
+    initial: CodeState,
+    /// The code elements which may be used when encoding, the first value of Specific Character Set
+    /// followed by the others in the order of `CODE_ELEMENTS`
+    elements: Vec<&'static CodeElement>,
+}
+
+impl Encoding for DicomIso2022Encoding {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn raw_encoder(&self) -> Box<dyn RawEncoder> {
+        Box::new(DicomIso2022Encoder {
+            initial: self.initial,
+            state: self.initial,
+            elements: self.elements.clone(),
+        })
+    }
+
+    fn raw_decoder(&self) -> Box<dyn RawDecoder> {
+        Box::new(DicomIso2022Decoder {
+            initial: self.initial,
+            state: self.initial,
+            pending: Vec::new(),
+        })
+    }
+}
+
+/// Whether the given character requires the initial state be active, PS3.5 6.1.2.5.3
+fn is_reset_char(c: char) -> bool {
+    matches!(c, '^' | '=' | '\\' | '\r' | '\n' | '\x0C')
+}
+
+struct DicomIso2022Encoder {
+    initial: CodeState,
+    state: CodeState,
+    elements: Vec<&'static CodeElement>,
+}
+
+impl DicomIso2022Encoder {
+    /// Finds the graphic set to encode the given character with, preferring the sets which are
+    /// currently designated.
+    fn find_set(&self, c: char) -> Option<(&'static GraphicSet, Vec<u8>)> {
+        let designated = self.state.g1.into_iter().chain(Some(self.state.g0));
+        let available = self
+            .elements
+            .iter()
+            .flat_map(|element: &&CodeElement| element.g1.into_iter().chain(element.g0));
+        designated
+            .chain(available)
+            .chain(Some(&ASCII))
+            .find_map(|set: &'static GraphicSet| set.encode_char(c).map(|bytes| (set, bytes)))
+    }
+}
+
+impl RawEncoder for DicomIso2022Encoder {
+    fn from_self(&self) -> Box<dyn RawEncoder> {
+        Box::new(DicomIso2022Encoder {
+            initial: self.initial,
+            state: self.initial,
+            elements: self.elements.clone(),
+        })
+    }
+
+    fn raw_feed(
+        &mut self,
+        input: &str,
+        output: &mut dyn ByteWriter,
+    ) -> (usize, Option<CodecError>) {
+        for (index, c) in input.char_indices() {
+            if is_reset_char(c) {
+                self.state.write_escapes_to(&self.initial, output);
+                self.state = self.initial;
+            }
+
+            // Control characters and space are the same in all graphic sets
+            if c.is_ascii_control() || c == ' ' {
+                output.write_byte(c as u8);
+                continue;
+            }
+
+            match self.find_set(c) {
+                Some((set, bytes)) => {
+                    if !self.state.is_designated(set) {
+                        output.write_byte(ESC);
+                        output.write_bytes(set.escape);
+                        self.state.designate(set);
+                    }
+                    output.write_bytes(&bytes);
+                }
+                None => {
+                    return (
+                        index,
+                        Some(CodecError {
+                            upto: (index + c.len_utf8()) as isize,
+                            cause: "unrepresentable character".into(),
+                        }),
+                    );
+                }
+            }
+        }
+        (input.len(), None)
+    }
+
+    fn raw_finish(&mut self, output: &mut dyn ByteWriter) -> Option<CodecError> {
+        self.state.write_escapes_to(&self.initial, output);
+        self.state = self.initial;
+        None
+    }
+}
+
+struct DicomIso2022Decoder {
+    initial: CodeState,
+    state: CodeState,
+    /// Bytes of an incomplete escape sequence or multi-byte character from the previous input
+    pending: Vec<u8>,
+}
+
+/// The result of decoding the sequence of bytes at some position.
+enum Decoded {
+    /// A sequence of the given length was decoded
+    Consumed(usize),
+    /// The sequence of the given length is not valid
+    Invalid(usize, &'static str),
+    /// The sequence continues beyond the available bytes
+    Incomplete,
+}
+
+impl DicomIso2022Decoder {
+    fn decode_at(&mut self, bytes: &[u8], output: &mut dyn StringWriter) -> Decoded {
+        let b: u8 = bytes[0];
+        match b {
+            ESC => self.decode_escape(bytes),
+            0x00..=0x20 | 0x7F => {
+                output.write_char(b as char);
+                if matches!(b, b'\r' | b'\n' | 0x0C) {
+                    self.state = self.initial;
+                }
+                Decoded::Consumed(1)
+            }
+            0x21..=0x7E => {
+                let g0: &'static GraphicSet = self.state.g0;
+                if g0.is_multi_byte() {
+                    self.decode_multi_byte(g0, bytes, 0x21..=0x7E, output)
+                } else {
+                    match g0.decode_char(&bytes[..1]) {
+                        Some(text) => output.write_str(&text),
+                        None => return Decoded::Invalid(1, "invalid sequence"),
+                    }
+                    if matches!(b, b'^' | b'=' | BACKSLASH) {
+                        self.state = self.initial;
+                    }
+                    Decoded::Consumed(1)
+                }
+            }
+            0x80..=0x9F => Decoded::Invalid(1, "invalid sequence"),
+            0xA0..=0xFF => match self.state.g1 {
+                None => Decoded::Invalid(1, "no character set designated to G1"),
+                Some(g1) if g1.is_multi_byte() => {
+                    self.decode_multi_byte(g1, bytes, 0xA1..=0xFE, output)
+                }
+                Some(g1) => match g1.decode_char(&bytes[..1]) {
+                    Some(text) => {
+                        output.write_str(&text);
+                        Decoded::Consumed(1)
+                    }
+                    None => Decoded::Invalid(1, "invalid sequence"),
+                },
+            },
+        }
+    }
+
+    fn decode_escape(&mut self, bytes: &[u8]) -> Decoded {
+        // ESC, any number of intermediate bytes 02/00 to 02/15, then a final byte
+        let end: Option<usize> = bytes
+            .iter()
+            .skip(1)
+            .position(|b: &u8| !(0x20..=0x2F).contains(b))
+            .map(|index: usize| index + 1);
+        let end: usize = match end {
+            Some(end) => end,
+            None => return Decoded::Incomplete,
+        };
+        let escape: &[u8] = &bytes[1..=end];
+        match GRAPHIC_SETS
+            .iter()
+            .find(|set: &&&GraphicSet| set.escape == escape)
+        {
+            Some(set) => {
+                self.state.designate(set);
+                Decoded::Consumed(end + 1)
+            }
+            None => Decoded::Invalid(end + 1, "unsupported escape sequence"),
+        }
+    }
+
+    fn decode_multi_byte(
+        &mut self,
+        set: &'static GraphicSet,
+        bytes: &[u8],
+        range: std::ops::RangeInclusive<u8>,
+        output: &mut dyn StringWriter,
+    ) -> Decoded {
+        if bytes.len() < 2 {
+            return Decoded::Incomplete;
+        }
+        if !range.contains(&bytes[1]) {
+            return Decoded::Invalid(1, "invalid sequence");
+        }
+        match set.decode_char(&bytes[..2]) {
+            Some(text) => {
+                output.write_str(&text);
+                Decoded::Consumed(2)
+            }
+            None => Decoded::Invalid(2, "invalid sequence"),
+        }
+    }
+}
+
+impl RawDecoder for DicomIso2022Decoder {
+    fn from_self(&self) -> Box<dyn RawDecoder> {
+        Box::new(DicomIso2022Decoder {
+            initial: self.initial,
+            state: self.initial,
+            pending: Vec::new(),
+        })
+    }
+
+    fn raw_feed(
+        &mut self,
+        input: &[u8],
+        output: &mut dyn StringWriter,
+    ) -> (usize, Option<CodecError>) {
+        let num_pending: usize = self.pending.len();
+        let mut bytes: Vec<u8> = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(input);
+
+        let mut pos: usize = 0;
+        while pos < bytes.len() {
+            match self.decode_at(&bytes[pos..], output) {
+                Decoded::Consumed(len) => pos += len,
+                Decoded::Incomplete => {
+                    self.pending = bytes[pos..].to_vec();
+                    return (input.len(), None);
+                }
+                Decoded::Invalid(len, cause) => {
+                    let start: isize = pos as isize - num_pending as isize;
+                    return (
+                        start.max(0) as usize,
+                        Some(CodecError {
+                            upto: start + len as isize,
+                            cause: cause.into(),
+                        }),
+                    );
+                }
+            }
+        }
+        (input.len(), None)
+    }
+
+    fn raw_finish(&mut self, _output: &mut dyn StringWriter) -> Option<CodecError> {
+        self.state = self.initial;
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.pending.clear();
+        Some(CodecError {
+            upto: 0,
+            cause: "incomplete sequence".into(),
+        })
+    }
+}
+
+fn is_two_byte_upper(bytes: &[u8]) -> bool {
+    bytes.len() == 2 && bytes.iter().all(|b: &u8| (0xA1..=0xFE).contains(b))
+}
+
+fn encode_with(cs: EncodingRef, c: char) -> Option<Vec<u8>> {
+    cs.encode(c.encode_utf8(&mut [0u8; 4]), EncoderTrap::Strict).ok()
+}
+
+fn decode_with(cs: EncodingRef, bytes: &[u8]) -> Option<String> {
+    cs.decode(bytes, DecoderTrap::Strict).ok()
+}
@@ -1,10 +1,35 @@
 use encoding::all::{self, WINDOWS_1252};
 use encoding::EncodingRef;
 
+mod iso2022;
+mod substitute;
+
+pub use iso2022::DicomIso2022Encoding;
+pub use substitute::{is_substitute, SubstituteCharset, UnsupportedCharsetPolicy};
+
 pub type CSRef = EncodingRef;
 
 pub static DEFAULT_CHARACTER_SET: CSRef = WINDOWS_1252 as CSRef;
 
+/// Looks up the character set for the values of Specific Character Set (0008,0005). If any of the
+/// values are Defined Terms which use code extensions, `ISO 2022 IR 87`, then the returned character
+/// set handles switching between the repertoires of all values with escape sequences. Otherwise the
+/// first non-empty value is used. If there are no non-empty values then this is the default
+/// character set. Returns `None` if the character set is not supported.
+/// See DICOM Part 3 Section C.12.1.1.2 Specific Character Set
+/// http://dicom.nema.org/medical/dicom/current/output/chtml/part03/sect_C.12.html#sect_C.12.1.1.2
+pub fn lookup_specific_character_set(values: &[String]) -> Option<CSRef> {
+    let values: Vec<&str> = values.iter().map(|value: &String| value.trim()).collect();
+    if values.iter().any(|value: &&str| iso2022::is_iso2022_term(value)) {
+        return iso2022::lookup_iso2022_charset(&values);
+    }
+
+    match values.into_iter().find(|value: &&str| !value.is_empty()) {
+        Some(value) => lookup_charset(value),
+        None => Some(DEFAULT_CHARACTER_SET),
+    }
+}
+
 /// This is based off `encoding::label::encoding_from_whatwg_label` with a few minor changes
 /// - All whitespace, hyphens, and underscores are stripped when doing a lookup
 /// - Added `ISO-IR-192` mapping for `UTF-8`
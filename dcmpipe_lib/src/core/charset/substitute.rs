@@ -0,0 +1,129 @@
+//! Character sets used in place of a Specific Character Set which is not supported.
+//! See DICOM Part 5 Section 6.1.2.3, Note under "Considerations on the Handling of Unsupported
+//! Character Sets"
+//! http://dicom.nema.org/medical/dicom/current/output/chtml/part05/sect_6.1.2.3.html
+
+use encoding::all::{ASCII, WINDOWS_1252};
+use encoding::{CodecError, Encoding, EncodingRef, RawDecoder, RawEncoder, StringWriter};
+
+use crate::core::charset::CSRef;
+
+/// What the parser should do when the Specific Character Set of a dataset is not supported.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum UnsupportedCharsetPolicy {
+    /// Parsing fails with `ParseError::UnsupportedCharacterSet`.
+    Strict,
+    /// Values are decoded with the default character repertoire, with any characters outside of
+    /// it replaced by U+FFFD.
+    Replace,
+    /// Values are decoded with the default character set, `WINDOWS_1252`.
+    #[default]
+    FallbackToDefault,
+    /// Values are not decoded, attempting to decode characters outside of the default character
+    /// repertoire results in an error. The bytes are still available from the element.
+    RawBytes,
+}
+
+impl UnsupportedCharsetPolicy {
+    /// The character set to use in place of the unsupported one, `None` for `Strict`.
+    pub fn get_substitute(self) -> Option<CSRef> {
+        match self {
+            UnsupportedCharsetPolicy::Strict => None,
+            UnsupportedCharsetPolicy::Replace => Some(&SUBSTITUTE_REPLACE),
+            UnsupportedCharsetPolicy::FallbackToDefault => Some(&SUBSTITUTE_DEFAULT),
+            UnsupportedCharsetPolicy::RawBytes => Some(&SUBSTITUTE_RAW_BYTES),
+        }
+    }
+}
+
+/// A character set used in place of an unsupported Specific Character Set. This wraps another
+/// character set so that elements decoded with a substitute can be identified.
+pub struct SubstituteCharset {
+    name: &'static str,
+    policy: UnsupportedCharsetPolicy,
+}
+
+pub static SUBSTITUTE_REPLACE: SubstituteCharset = SubstituteCharset {
+    name: "substitute-replace",
+    policy: UnsupportedCharsetPolicy::Replace,
+};
+
+pub static SUBSTITUTE_DEFAULT: SubstituteCharset = SubstituteCharset {
+    name: "substitute-default",
+    policy: UnsupportedCharsetPolicy::FallbackToDefault,
+};
+
+pub static SUBSTITUTE_RAW_BYTES: SubstituteCharset = SubstituteCharset {
+    name: "substitute-raw-bytes",
+    policy: UnsupportedCharsetPolicy::RawBytes,
+};
+
+/// Whether the given character set was substituted for an unsupported Specific Character Set.
+pub fn is_substitute(cs: CSRef) -> bool {
+    let name: &str = cs.name();
+    [&SUBSTITUTE_REPLACE, &SUBSTITUTE_DEFAULT, &SUBSTITUTE_RAW_BYTES]
+        .iter()
+        .any(|substitute: &&SubstituteCharset| substitute.name == name)
+}
+
+impl SubstituteCharset {
+    pub fn get_policy(&self) -> UnsupportedCharsetPolicy {
+        self.policy
+    }
+
+    fn get_wrapped(&self) -> EncodingRef {
+        match self.policy {
+            UnsupportedCharsetPolicy::FallbackToDefault => WINDOWS_1252,
+            _ => ASCII,
+        }
+    }
+}
+
+impl Encoding for SubstituteCharset {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn raw_encoder(&self) -> Box<dyn RawEncoder> {
+        self.get_wrapped().raw_encoder()
+    }
+
+    fn raw_decoder(&self) -> Box<dyn RawDecoder> {
+        match self.policy {
+            UnsupportedCharsetPolicy::Replace => Box::new(ReplacingDecoder),
+            _ => self.get_wrapped().raw_decoder(),
+        }
+    }
+}
+
+/// Decodes the default character repertoire, replacing all other bytes with U+FFFD.
+struct ReplacingDecoder;
+
+impl RawDecoder for ReplacingDecoder {
+    fn from_self(&self) -> Box<dyn RawDecoder> {
+        Box::new(ReplacingDecoder)
+    }
+
+    fn is_ascii_compatible(&self) -> bool {
+        true
+    }
+
+    fn raw_feed(
+        &mut self,
+        input: &[u8],
+        output: &mut dyn StringWriter,
+    ) -> (usize, Option<CodecError>) {
+        for b in input {
+            if b.is_ascii() {
+                output.write_char(*b as char);
+            } else {
+                output.write_char('\u{FFFD}');
+            }
+        }
+        (input.len(), None)
+    }
+
+    fn raw_finish(&mut self, _output: &mut dyn StringWriter) -> Option<CodecError> {
+        None
+    }
+}
@@ -0,0 +1,125 @@
+//! Coded concepts parsed from the items of Code Sequences.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::core::dcmobject::{DicomNode, DicomObject};
+use crate::core::parser::error::ParseError;
+use crate::defn::constants::tags;
+use crate::defn::contextgroup::{Code, ContextGroupRef};
+use crate::defn::tag::Tag;
+
+#[derive(Error, Debug)]
+/// Errors that can occur when interpreting a Code Sequence item.
+pub enum CodedConceptError {
+    #[error("code sequence item is missing {}", Tag::format_tag_to_display(* .0))]
+    /// A required attribute of the Basic Code Sequence Macro is not present or is empty.
+    MissingAttribute(u32),
+
+    #[error("error parsing code sequence item value")]
+    /// The value of an attribute of the item could not be parsed.
+    ValueError {
+        #[from]
+        source: ParseError,
+    },
+
+    #[error("code {concept} is not in CID {cid}")]
+    /// The coded concept is not defined by the Context Group it is required to be from.
+    NotInContextGroup { concept: String, cid: u32 },
+}
+
+/// A coded concept from an item of a Code Sequence, Part 3, Ch 8.8 Basic Code Sequence Macro.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodedConcept {
+    /// The Code Value, taken from the first present of Code Value, Long Code Value, or
+    /// URN Code Value.
+    pub code_value: String,
+    pub coding_scheme_designator: String,
+    pub coding_scheme_version: Option<String>,
+    pub code_meaning: String,
+}
+
+impl CodedConcept {
+    /// Whether this concept matches the given Context Group code, comparing the code value and
+    /// coding scheme designator.
+    pub fn matches(&self, code: &Code) -> bool {
+        self.code_value == code.value
+            && self
+                .coding_scheme_designator
+                .eq_ignore_ascii_case(code.scheme)
+    }
+
+    /// Looks up this concept in the given Context Group, or any Context Groups it includes.
+    pub fn find_in(&self, cid: ContextGroupRef) -> Option<&'static Code> {
+        cid.get_code(&self.coding_scheme_designator, &self.code_value)
+    }
+
+    /// Parses a Code Sequence item and checks that its concept is defined by the given Context
+    /// Group.
+    pub fn from_item_in_cid(
+        item: &DicomObject,
+        cid: ContextGroupRef,
+    ) -> Result<CodedConcept, CodedConceptError> {
+        let concept: CodedConcept = CodedConcept::try_from(item)?;
+        if concept.find_in(cid).is_none() {
+            return Err(CodedConceptError::NotInContextGroup {
+                concept: concept.to_string(),
+                cid: cid.get_cid(),
+            });
+        }
+        Ok(concept)
+    }
+}
+
+impl fmt::Display for CodedConcept {
+    /// Formats as `(CodeValue, CodingSchemeDesignator, "CodeMeaning")`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}, \"{}\")",
+            self.code_value, self.coding_scheme_designator, self.code_meaning
+        )
+    }
+}
+
+impl TryFrom<&DicomObject> for CodedConcept {
+    type Error = CodedConceptError;
+
+    /// Parses the attributes of the Basic Code Sequence Macro from an item of a Code Sequence.
+    fn try_from(item: &DicomObject) -> Result<Self, Self::Error> {
+        let code_value: String = get_string(item, tags::CODE_VALUE)?
+            .or(get_string(item, tags::LONG_CODE_VALUE)?)
+            .or(get_string(item, tags::URN_CODE_VALUE)?)
+            .ok_or(CodedConceptError::MissingAttribute(tags::CODE_VALUE))?;
+        let coding_scheme_designator: String = get_string(item, tags::CODING_SCHEME_DESIGNATOR)?
+            .ok_or(CodedConceptError::MissingAttribute(tags::CODING_SCHEME_DESIGNATOR))?;
+        let code_meaning: String = get_string(item, tags::CODE_MEANING)?
+            .ok_or(CodedConceptError::MissingAttribute(tags::CODE_MEANING))?;
+        let coding_scheme_version: Option<String> = get_string(item, tags::CODING_SCHEME_VERSION)?;
+
+        Ok(CodedConcept {
+            code_value,
+            coding_scheme_designator,
+            coding_scheme_version,
+            code_meaning,
+        })
+    }
+}
+
+/// Gets the trimmed string value of an attribute of the item, `None` if not present or empty.
+fn get_string(item: &DicomObject, tag: u32) -> Result<Option<String>, CodedConceptError> {
+    match item.get_child_by_tag(tag) {
+        None => Ok(None),
+        Some(obj) => {
+            let value: String = String::try_from(obj.get_element())?;
+            let value: &str = value.trim();
+            if value.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(value.to_owned()))
+            }
+        }
+    }
+}
@@ -0,0 +1,728 @@
+//! Date (DA), Time (TM), and Date Time (DT) values, see Part 5 Section 6.2 Value Representation.
+//! Values keep the precision they were encoded with, such that `2020` remains a year and is not
+//! assumed to be `20200101`. Ranges of values, as used for Range Matching in queries, are parsed
+//! with `DicomRange`. See Part 4 Section C.2.2.2.5 Range Matching.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::core::dcmelement::{self, DicomElement};
+use crate::core::dcmobject::DicomNode;
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::Result;
+use crate::defn::constants::tags;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("invalid date/time value: {0:?}")]
+/// A value could not be parsed as a date, time, date time, or UTC offset.
+pub struct InvalidDateTime(pub String);
+
+/// A value which can be compared by the span of time it covers. Partial values cover a span, the
+/// value `2020` covers all of that year.
+pub trait TemporalValue: FromStr<Err = InvalidDateTime> + fmt::Display + Clone + PartialEq {
+    /// The first microsecond covered by this value.
+    fn earliest_micros(&self) -> i64;
+    /// The last microsecond covered by this value.
+    fn latest_micros(&self) -> i64;
+}
+
+/// A Date (DA) value. The month and day may be omitted, as permitted for the date component of a
+/// Date Time (DT), in which case the value represents the whole year or month.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DicomDate {
+    pub year: u16,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+/// A Time (TM) value. Components after the hour may be omitted, in which case the value represents
+/// the whole hour, minute, or second.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DicomTime {
+    pub hour: u8,
+    pub minute: Option<u8>,
+    pub second: Option<u8>,
+    /// The fractional second, in microseconds.
+    pub microsecond: Option<u32>,
+    /// The number of digits the fractional second was encoded with, 1 to 6.
+    pub fraction_digits: u8,
+}
+
+/// An offset from UTC, `&ZZXX`, as used in Date Time (DT) values and the Timezone Offset From UTC
+/// (0008,0201) attribute.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct UtcOffset {
+    /// The offset in minutes, negative for west of UTC.
+    pub minutes: i16,
+}
+
+/// A Date Time (DT) value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DicomDateTime {
+    pub date: DicomDate,
+    pub time: Option<DicomTime>,
+    pub offset: Option<UtcOffset>,
+}
+
+/// A range of values, `<start>-<end>`. Either end may be omitted for an open range. A value without
+/// the range separator has the same start and end.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DicomRange<T: TemporalValue> {
+    pub start: Option<T>,
+    pub end: Option<T>,
+}
+
+fn invalid(value: &str) -> InvalidDateTime {
+    InvalidDateTime(value.to_owned())
+}
+
+/// Trims the value, which must be ASCII as its components are sliced by byte position.
+fn trim_ascii(value: &str) -> std::result::Result<&str, InvalidDateTime> {
+    let trimmed: &str = value.trim();
+    if trimmed.is_ascii() {
+        Ok(trimmed)
+    } else {
+        Err(invalid(value))
+    }
+}
+
+/// Parses a fixed-width number of digits.
+fn parse_digits<N: FromStr>(value: &str) -> Option<N> {
+    if value.is_empty() || !value.bytes().all(|b: u8| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse::<N>().ok()
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of days since 1970-01-01 of the given date.
+/// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let (year, month, day): (i64, i64, i64) = (i64::from(year), i64::from(month), i64::from(day));
+    let year: i64 = if month <= 2 { year - 1 } else { year };
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year - era * 400;
+    let day_of_year: i64 = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+impl DicomDate {
+    /// The first day covered by this date.
+    pub fn earliest(&self) -> DicomDate {
+        DicomDate {
+            year: self.year,
+            month: Some(self.month.unwrap_or(1)),
+            day: Some(self.day.unwrap_or(1)),
+        }
+    }
+
+    /// The last day covered by this date.
+    pub fn latest(&self) -> DicomDate {
+        let month: u8 = self.month.unwrap_or(12);
+        DicomDate {
+            year: self.year,
+            month: Some(month),
+            day: Some(self.day.unwrap_or_else(|| days_in_month(self.year, month))),
+        }
+    }
+
+    /// Parses the date component of a Date Time, `YYYY[MM[DD]]`.
+    fn parse_partial(value: &str) -> Option<DicomDate> {
+        if !matches!(value.len(), 4 | 6 | 8) {
+            return None;
+        }
+        let year: u16 = parse_digits(value.get(0..4)?)?;
+        let month: Option<u8> = match value.get(4..6) {
+            Some(month) => Some(parse_digits(month).filter(|m: &u8| (1..=12).contains(m))?),
+            None => None,
+        };
+        let day: Option<u8> = match (month, value.get(6..8)) {
+            (Some(month), Some(day)) => Some(
+                parse_digits(day).filter(|d: &u8| *d >= 1 && *d <= days_in_month(year, month))?,
+            ),
+            _ => None,
+        };
+        Some(DicomDate { year, month, day })
+    }
+}
+
+impl FromStr for DicomDate {
+    type Err = InvalidDateTime;
+
+    /// Parses `YYYYMMDD`, partial dates `YYYY` and `YYYYMM`, and the ACR-NEMA `YYYY.MM.DD` format.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed: &str = trim_ascii(value)?;
+        let date: Option<DicomDate> = if trimmed.len() == 10 && trimmed.contains('.') {
+            trimmed
+                .get(4..5)
+                .filter(|sep| *sep == ".")
+                .and(trimmed.get(7..8).filter(|sep| *sep == "."))
+                .and_then(|_| DicomDate::parse_partial(&trimmed.replace('.', "")))
+        } else {
+            DicomDate::parse_partial(trimmed)
+        };
+        date.ok_or_else(|| invalid(value))
+    }
+}
+
+impl fmt::Display for DicomDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "{:02}", day)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TemporalValue for DicomDate {
+    fn earliest_micros(&self) -> i64 {
+        let date: DicomDate = self.earliest();
+        days_from_civil(date.year, date.month.unwrap_or(1), date.day.unwrap_or(1)) * MICROS_PER_DAY
+    }
+
+    fn latest_micros(&self) -> i64 {
+        let date: DicomDate = self.latest();
+        days_from_civil(date.year, date.month.unwrap_or(12), date.day.unwrap_or(31))
+            * MICROS_PER_DAY
+            + MICROS_PER_DAY
+            - 1
+    }
+}
+
+impl DicomTime {
+    /// The first microsecond covered by this time.
+    pub fn earliest(&self) -> DicomTime {
+        DicomTime {
+            hour: self.hour,
+            minute: Some(self.minute.unwrap_or(0)),
+            second: Some(self.second.unwrap_or(0)),
+            microsecond: Some(self.microsecond.unwrap_or(0)),
+            fraction_digits: 6,
+        }
+    }
+
+    /// The last microsecond covered by this time.
+    pub fn latest(&self) -> DicomTime {
+        let microsecond: u32 = match self.microsecond {
+            Some(micros) => micros + 10u32.pow(6 - u32::from(self.fraction_digits)) - 1,
+            None => 999_999,
+        };
+        DicomTime {
+            hour: self.hour,
+            minute: Some(self.minute.unwrap_or(59)),
+            second: Some(self.second.unwrap_or(59)),
+            microsecond: Some(microsecond),
+            fraction_digits: 6,
+        }
+    }
+
+    fn micros_since_midnight(&self) -> i64 {
+        (i64::from(self.hour) * 3600
+            + i64::from(self.minute.unwrap_or(0)) * 60
+            + i64::from(self.second.unwrap_or(0)))
+            * MICROS_PER_SECOND
+            + i64::from(self.microsecond.unwrap_or(0))
+    }
+
+    /// Parses `HH[MM[SS[.F{1-6}]]]`.
+    fn parse_partial(value: &str) -> Option<DicomTime> {
+        let (hms, fraction): (&str, Option<&str>) = match value.find('.') {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+        if hms.len() % 2 != 0 || hms.len() > 6 {
+            return None;
+        }
+        let hour: u8 = parse_digits(hms.get(0..2)?).filter(|h: &u8| *h < 24)?;
+        let minute: Option<u8> = match hms.get(2..4) {
+            Some(minute) => Some(parse_digits(minute).filter(|m: &u8| *m < 60)?),
+            None => None,
+        };
+        // 60 is allowed for leap seconds
+        let second: Option<u8> = match hms.get(4..6) {
+            Some(second) => Some(parse_digits(second).filter(|s: &u8| *s <= 60)?),
+            None => None,
+        };
+        let (microsecond, fraction_digits): (Option<u32>, u8) = match fraction {
+            Some(fraction) if second.is_some() && (1..=6).contains(&fraction.len()) => {
+                let digits: u32 = parse_digits(fraction)?;
+                (
+                    Some(digits * 10u32.pow(6 - fraction.len() as u32)),
+                    fraction.len() as u8,
+                )
+            }
+            Some(_) => return None,
+            None => (None, 0),
+        };
+        Some(DicomTime {
+            hour,
+            minute,
+            second,
+            microsecond,
+            fraction_digits,
+        })
+    }
+}
+
+impl FromStr for DicomTime {
+    type Err = InvalidDateTime;
+
+    /// Parses `HH[MM[SS[.F{1-6}]]]` and the ACR-NEMA `HH:MM:SS.frac` format.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed: &str = trim_ascii(value)?;
+        let time: Option<DicomTime> = if trimmed.contains(':') {
+            DicomTime::parse_partial(&trimmed.replace(':', ""))
+        } else {
+            DicomTime::parse_partial(trimmed)
+        };
+        time.ok_or_else(|| invalid(value))
+    }
+}
+
+impl fmt::Display for DicomTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.hour)?;
+        if let Some(minute) = self.minute {
+            write!(f, "{:02}", minute)?;
+            if let Some(second) = self.second {
+                write!(f, "{:02}", second)?;
+                if let Some(microsecond) = self.microsecond {
+                    let fraction: String = format!("{:06}", microsecond);
+                    write!(
+                        f,
+                        ".{}",
+                        &fraction[..usize::from(self.fraction_digits.min(6))]
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TemporalValue for DicomTime {
+    fn earliest_micros(&self) -> i64 {
+        self.earliest().micros_since_midnight()
+    }
+
+    fn latest_micros(&self) -> i64 {
+        self.latest().micros_since_midnight()
+    }
+}
+
+impl UtcOffset {
+    /// Gets the Timezone Offset From UTC (0008,0201) of the given dataset, if present.
+    pub fn from_dataset(node: &dyn DicomNode) -> Result<Option<UtcOffset>> {
+        node.get_child_by_tag(tags::TIMEZONE_OFFSET_FROM_UTC)
+            .map(|obj| UtcOffset::try_from(obj.get_element()))
+            .transpose()
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = InvalidDateTime;
+
+    /// Parses `&ZZXX` where `&` is `+` or `-`.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed: &str = trim_ascii(value)?;
+        let sign: i16 = match trimmed.get(0..1) {
+            Some("+") => 1,
+            Some("-") => -1,
+            _ => return Err(invalid(value)),
+        };
+        if trimmed.len() != 5 {
+            return Err(invalid(value));
+        }
+        let hours: i16 = trimmed
+            .get(1..3)
+            .and_then(parse_digits)
+            .ok_or_else(|| invalid(value))?;
+        let minutes: i16 = trimmed
+            .get(3..5)
+            .and_then(parse_digits)
+            .filter(|m: &i16| *m < 60)
+            .ok_or_else(|| invalid(value))?;
+        let minutes: i16 = sign * (hours * 60 + minutes);
+        // offsets range from -12:00 to +14:00
+        if !(-12 * 60..=14 * 60).contains(&minutes) {
+            return Err(invalid(value));
+        }
+        Ok(UtcOffset { minutes })
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign: char = if self.minutes < 0 { '-' } else { '+' };
+        let minutes: i16 = self.minutes.abs();
+        write!(f, "{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+impl DicomDateTime {
+    /// Uses the given offset if this value does not specify one. The Timezone Offset From UTC
+    /// (0008,0201) of a dataset applies to Date Time values without an offset.
+    pub fn or_offset(mut self, offset: Option<UtcOffset>) -> DicomDateTime {
+        if self.offset.is_none() {
+            self.offset = offset;
+        }
+        self
+    }
+
+    /// The microseconds since 1970-01-01 of the given date and time, in UTC if this value has an
+    /// offset.
+    fn micros_of(&self, date: DicomDate, time: DicomTime) -> i64 {
+        let offset: i64 = self.offset.map_or(0, |offset: UtcOffset| {
+            i64::from(offset.minutes) * 60 * MICROS_PER_SECOND
+        });
+        date.earliest_micros() + time.micros_since_midnight() - offset
+    }
+}
+
+impl FromStr for DicomDateTime {
+    type Err = InvalidDateTime;
+
+    /// Parses `YYYY[MM[DD[HH[MM[SS[.F{1-6}]]]]]][&ZZXX]`.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed: &str = trim_ascii(value)?;
+        let (datetime, offset): (&str, Option<UtcOffset>) =
+            match trimmed.rfind(['+', '-']) {
+                Some(index) => (
+                    &trimmed[..index],
+                    Some(trimmed[index..].parse::<UtcOffset>()?),
+                ),
+                None => (trimmed, None),
+            };
+
+        let date_len: usize = datetime.find('.').unwrap_or(datetime.len()).min(8);
+        let date: DicomDate = datetime
+            .get(..date_len)
+            .and_then(DicomDate::parse_partial)
+            .ok_or_else(|| invalid(value))?;
+        let time: Option<DicomTime> = if datetime.len() > date_len {
+            if date.day.is_none() {
+                return Err(invalid(value));
+            }
+            Some(
+                datetime
+                    .get(date_len..)
+                    .and_then(DicomTime::parse_partial)
+                    .ok_or_else(|| invalid(value))?,
+            )
+        } else {
+            None
+        };
+        Ok(DicomDateTime { date, time, offset })
+    }
+}
+
+impl fmt::Display for DicomDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date)?;
+        if let Some(time) = self.time {
+            write!(f, "{}", time)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "{}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl TemporalValue for DicomDateTime {
+    fn earliest_micros(&self) -> i64 {
+        let time: DicomTime = self.time.map_or(
+            DicomTime {
+                hour: 0,
+                minute: None,
+                second: None,
+                microsecond: None,
+                fraction_digits: 0,
+            },
+            |time: DicomTime| time.earliest(),
+        );
+        self.micros_of(self.date.earliest(), time)
+    }
+
+    fn latest_micros(&self) -> i64 {
+        match self.time {
+            Some(time) => self.micros_of(self.date, time.latest()),
+            None => {
+                let time: DicomTime = DicomTime {
+                    hour: 23,
+                    minute: None,
+                    second: None,
+                    microsecond: None,
+                    fraction_digits: 0,
+                };
+                self.micros_of(self.date.latest(), time.latest())
+            }
+        }
+    }
+}
+
+impl<T: TemporalValue> DicomRange<T> {
+    /// Whether the given value falls entirely within this range.
+    pub fn contains(&self, value: &T) -> bool {
+        let after_start: bool = self.start.as_ref().map_or(true, |start: &T| {
+            start.earliest_micros() <= value.earliest_micros()
+        });
+        let before_end: bool = self
+            .end
+            .as_ref()
+            .map_or(true, |end: &T| value.latest_micros() <= end.latest_micros());
+        after_start && before_end
+    }
+
+    /// Whether this range is for a single value rather than a range of values.
+    pub fn is_single_value(&self) -> bool {
+        self.start.is_some() && self.start == self.end
+    }
+}
+
+impl<T: TemporalValue> FromStr for DicomRange<T> {
+    type Err = InvalidDateTime;
+
+    /// Parses `<start>-<end>`, `<start>-`, `-<end>`, or a single value. For Date Time values which
+    /// may end with a negative UTC offset, the whole value is first attempted as a single value.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed: &str = value.trim();
+        if let Ok(single) = trimmed.parse::<T>() {
+            return Ok(DicomRange {
+                start: Some(single.clone()),
+                end: Some(single),
+            });
+        }
+
+        let parse_bound = |bound: &str| -> std::result::Result<Option<T>, InvalidDateTime> {
+            if bound.is_empty() {
+                Ok(None)
+            } else {
+                bound.parse::<T>().map(Some)
+            }
+        };
+        trimmed
+            .match_indices('-')
+            .find_map(|(index, _)| {
+                let start: Option<T> = parse_bound(&trimmed[..index]).ok()?;
+                let end: Option<T> = parse_bound(&trimmed[index + 1..]).ok()?;
+                if start.is_none() && end.is_none() {
+                    None
+                } else {
+                    Some(DicomRange { start, end })
+                }
+            })
+            .ok_or_else(|| invalid(value))
+    }
+}
+
+impl<T: TemporalValue> fmt::Display for DicomRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_single_value() {
+            if let Some(start) = &self.start {
+                return write!(f, "{}", start);
+            }
+        }
+        if let Some(start) = &self.start {
+            write!(f, "{}", start)?;
+        }
+        write!(f, "-")?;
+        if let Some(end) = &self.end {
+            write!(f, "{}", end)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the first value of the element with `FromStr`.
+fn parse_element<T: FromStr<Err = InvalidDateTime>>(element: &DicomElement) -> Result<T> {
+    let value: String = Vec::<String>::try_from(element)?
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    value
+        .parse::<T>()
+        .map_err(|e: InvalidDateTime| dcmelement::error(&e.to_string(), element))
+}
+
+impl TryFrom<&DicomElement> for DicomDate {
+    type Error = ParseError;
+
+    /// Parses the value of a DA element.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        parse_element(value)
+    }
+}
+
+impl TryFrom<&DicomElement> for DicomTime {
+    type Error = ParseError;
+
+    /// Parses the value of a TM element.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        parse_element(value)
+    }
+}
+
+impl TryFrom<&DicomElement> for DicomDateTime {
+    type Error = ParseError;
+
+    /// Parses the value of a DT element.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        parse_element(value)
+    }
+}
+
+impl TryFrom<&DicomElement> for UtcOffset {
+    type Error = ParseError;
+
+    /// Parses the value of a UTC offset element such as Timezone Offset From UTC.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        parse_element(value)
+    }
+}
+
+impl<T: TemporalValue> TryFrom<&DicomElement> for DicomRange<T> {
+    type Error = ParseError;
+
+    /// Parses the value of a DA, TM, or DT element used for Range Matching.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        parse_element(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use chrono::{
+        DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+    };
+
+    use super::{DicomDate, DicomDateTime, DicomTime, UtcOffset};
+
+    impl DicomDate {
+        /// Converts to a `NaiveDate`, using the first day covered by this date.
+        pub fn to_naive_date(&self) -> Option<NaiveDate> {
+            let date: DicomDate = self.earliest();
+            NaiveDate::from_ymd_opt(
+                i32::from(date.year),
+                u32::from(date.month?),
+                u32::from(date.day?),
+            )
+        }
+    }
+
+    impl From<NaiveDate> for DicomDate {
+        fn from(date: NaiveDate) -> Self {
+            DicomDate {
+                year: date.year() as u16,
+                month: Some(date.month() as u8),
+                day: Some(date.day() as u8),
+            }
+        }
+    }
+
+    impl DicomTime {
+        /// Converts to a `NaiveTime`, using the first microsecond covered by this time.
+        pub fn to_naive_time(&self) -> Option<NaiveTime> {
+            let time: DicomTime = self.earliest();
+            NaiveTime::from_hms_micro_opt(
+                u32::from(time.hour),
+                u32::from(time.minute?),
+                u32::from(time.second?),
+                time.microsecond?,
+            )
+        }
+    }
+
+    impl From<NaiveTime> for DicomTime {
+        fn from(time: NaiveTime) -> Self {
+            let microsecond: u32 = time.nanosecond() / 1000;
+            DicomTime {
+                hour: time.hour() as u8,
+                minute: Some(time.minute() as u8),
+                second: Some(time.second() as u8),
+                microsecond: if microsecond > 0 {
+                    Some(microsecond)
+                } else {
+                    None
+                },
+                fraction_digits: if microsecond > 0 { 6 } else { 0 },
+            }
+        }
+    }
+
+    impl UtcOffset {
+        pub fn to_fixed_offset(&self) -> Option<FixedOffset> {
+            FixedOffset::east_opt(i32::from(self.minutes) * 60)
+        }
+    }
+
+    impl From<FixedOffset> for UtcOffset {
+        fn from(offset: FixedOffset) -> Self {
+            UtcOffset {
+                minutes: (offset.local_minus_utc() / 60) as i16,
+            }
+        }
+    }
+
+    impl DicomDateTime {
+        /// Converts to a `NaiveDateTime`, using the first microsecond covered by this value. The
+        /// offset is not applied.
+        pub fn to_naive_datetime(&self) -> Option<NaiveDateTime> {
+            let time: NaiveTime = match self.time {
+                Some(time) => time.to_naive_time()?,
+                None => NaiveTime::from_hms_opt(0, 0, 0)?,
+            };
+            Some(self.date.to_naive_date()?.and_time(time))
+        }
+
+        /// Converts to a `DateTime` if this value has an offset, see `or_offset`.
+        pub fn to_datetime(&self) -> Option<DateTime<FixedOffset>> {
+            let offset: FixedOffset = self.offset?.to_fixed_offset()?;
+            offset
+                .from_local_datetime(&self.to_naive_datetime()?)
+                .single()
+        }
+    }
+
+    impl From<NaiveDateTime> for DicomDateTime {
+        fn from(datetime: NaiveDateTime) -> Self {
+            DicomDateTime {
+                date: datetime.date().into(),
+                time: Some(datetime.time().into()),
+                offset: None,
+            }
+        }
+    }
+
+    impl From<DateTime<FixedOffset>> for DicomDateTime {
+        fn from(datetime: DateTime<FixedOffset>) -> Self {
+            DicomDateTime {
+                offset: Some((*datetime.offset()).into()),
+                ..DicomDateTime::from(datetime.naive_local())
+            }
+        }
+    }
+}
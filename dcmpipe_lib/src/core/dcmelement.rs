@@ -1,16 +1,18 @@
 use std::borrow::Cow;
 use std::convert::TryFrom;
-use std::iter::once;
+use std::io::{Read, Seek};
 
 use encoding::types::DecoderTrap;
 
-use crate::core::charset::CSRef;
+use crate::core::bulkdata::BulkDataRef;
+use crate::core::charset::{self, CSRef};
+use crate::core::numeric::{self, NumericStringMode};
+use crate::core::seekable::ElementOffsets;
 use crate::core::parser::error::{ParseError};
 use crate::core::parser::parser::{Result};
 use crate::core::parser;
-use crate::core::dcmsqelem::SequenceElement;
-use crate::defn::constants::tags;
-use crate::defn::tag::{TagNode, TagPath};
+use crate::core::dcmsqelem::SequencePath;
+use crate::defn::tag::{Tag, TagPath};
 use crate::defn::ts::TSRef;
 use crate::defn::vl::ValueLength;
 use crate::defn::vr::{self, VRRef, CHARACTER_STRING_SEPARATOR};
@@ -20,6 +22,8 @@ const U16_SIZE: usize = std::mem::size_of::<u16>();
 const I16_SIZE: usize = std::mem::size_of::<i16>();
 const U32_SIZE: usize = std::mem::size_of::<u32>();
 const I32_SIZE: usize = std::mem::size_of::<i32>();
+const U64_SIZE: usize = std::mem::size_of::<u64>();
+const I64_SIZE: usize = std::mem::size_of::<i64>();
 const F32_SIZE: usize = std::mem::size_of::<f32>();
 const F64_SIZE: usize = std::mem::size_of::<f64>();
 
@@ -43,10 +47,12 @@ pub enum RawValue {
     Shorts(Vec<i16>),
     Integers(Vec<i32>),
     UnsignedIntegers(Vec<u32>),
+    Longs(Vec<i64>),
+    UnsignedLongs(Vec<u64>),
     Bytes(Vec<u8>),
 }
 
-fn error(message: &str, value: &DicomElement) -> ParseError {
+pub(crate) fn error(message: &str, value: &DicomElement) -> ParseError {
     ParseError::ValueParseError {
         message: message.to_owned(),
         tag: value.tag,
@@ -68,7 +74,11 @@ pub struct DicomElement {
     pub vl: ValueLength,
 
     data: Vec<u8>,
-    sq_path: Vec<SequenceElement>,
+    sq_path: SequencePath,
+    /// The location of the value within the dataset if it was not read while parsing.
+    bulk_data: Option<BulkDataRef>,
+    /// The position of the element within the dataset, if the parser was recording offsets.
+    offsets: Option<ElementOffsets>,
 
     ts: TSRef,
     cs: CSRef,
@@ -82,7 +92,7 @@ impl DicomElement {
         ts: TSRef,
         cs: CSRef,
         data: Vec<u8>,
-        sq_path: Vec<SequenceElement>,
+        sq_path: SequencePath,
     ) -> DicomElement {
         let cs: CSRef = vr.get_proper_cs(cs);
         DicomElement {
@@ -93,9 +103,27 @@ impl DicomElement {
             cs,
             data,
             sq_path,
+            bulk_data: None,
+            offsets: None,
         }
     }
 
+    /// Creates an element whose value was skipped over while parsing instead of being read into
+    /// memory. See `ParserBuilder::bulk_data_threshold`.
+    pub fn new_bulk_data(
+        tag: u32,
+        vr: VRRef,
+        vl: ValueLength,
+        ts: TSRef,
+        cs: CSRef,
+        bulk_data: BulkDataRef,
+        sq_path: SequencePath,
+    ) -> DicomElement {
+        let mut element: DicomElement = DicomElement::new(tag, vr, vl, ts, cs, Vec::new(), sq_path);
+        element.bulk_data = Some(bulk_data);
+        element
+    }
+
     pub fn get_ts(&self) -> TSRef {
         self.ts
     }
@@ -104,59 +132,107 @@ impl DicomElement {
         self.cs
     }
 
+    /// Whether this element's value is decoded with a character set that was substituted for an
+    /// unsupported Specific Character Set, see `ParserBuilder::unsupported_charset`.
+    pub fn is_cs_substituted(&self) -> bool {
+        charset::is_substitute(self.cs)
+    }
+
     pub fn get_data(&self) -> &Vec<u8> {
         &self.data
     }
 
-    pub fn get_sequence_path(&self) -> &Vec<SequenceElement> {
+    pub fn get_sequence_path(&self) -> &SequencePath {
         &self.sq_path
     }
 
+    /// The location of this element's value within the dataset if it has not been read into
+    /// memory. This is `None` for elements whose value was read while parsing or has since been
+    /// loaded with `load_bulk_data`.
+    pub fn get_bulk_data(&self) -> Option<BulkDataRef> {
+        self.bulk_data
+    }
+
+    /// The position of this element within the dataset it was parsed from. This is only recorded
+    /// if the parser was built with `ParserBuilder::record_offsets`.
+    pub fn get_offsets(&self) -> Option<ElementOffsets> {
+        self.offsets
+    }
+
+    pub(crate) fn set_offsets(&mut self, offsets: ElementOffsets) {
+        self.offsets = Some(offsets);
+    }
+
+    /// Reads the value of this element from the given source if it was skipped over as bulk data
+    /// while parsing, after which the value can be parsed as with any other element. The source
+    /// must be the dataset this element was parsed from, see `BulkDataRef::load`.
+    pub fn load_bulk_data<SourceType: Read + Seek>(&mut self, source: &mut SourceType) -> Result<()> {
+        if let Some(bulk_data) = self.bulk_data {
+            self.data = bulk_data
+                .load(source)
+                .map_err(|e: std::io::Error| ParseError::DetailedIOError {
+                    source: e,
+                    detail: format!(
+                        "loading bulk data for tag: {}, vl: {}",
+                        Tag::format_tag_to_display(self.tag),
+                        bulk_data.length
+                    ),
+                })?;
+            self.bulk_data = None;
+        }
+        Ok(())
+    }
+
     /// Returns if this element is a `SQ` or if it should be parsed as though it were a sequence
     pub fn is_seq_like(&self) -> bool {
         self.vr == &vr::SQ || parser::util::is_non_standard_seq(self.tag, self.vr, self.vl)
     }
 
-    /// Returns whether the the size of the value for this element is zero
+    /// Returns whether the the size of the value for this element is zero. Bulk data elements
+    /// whose value has not been loaded are not considered empty.
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.data.is_empty() && self.bulk_data.is_none()
     }
 
     /// Creates a `TagPath` for the current element
     pub fn get_tagpath(&self) -> TagPath {
-        self.sq_path
-            .iter()
-            .filter(|sq| sq.get_seq_tag() != tags::ITEM)
-            .map(|sq| sq.get_node().clone())
-            .chain(once(self.tag.into()))
-            .collect::<Vec<TagNode>>()
-            .into()
+        self.sq_path.get_tagpath(self.tag)
     }
 
-    /// Parses this element's data into native/raw value type
+    /// Parses this element's data into native/raw value type, with DS and IS values parsed
+    /// leniently. Malformed DS and IS values are returned as `RawValue::Strings` as they're
+    /// encoded. See `parse_value_with_mode`.
     pub fn parse_value(&self) -> Result<RawValue> {
-        if self.vr == &vr::AT {
+        self.parse_value_with_mode(NumericStringMode::Lenient)
+    }
+
+    /// Parses this element's data into native/raw value type, parsing DS and IS values with the
+    /// given mode. With `NumericStringMode::Lenient` values which can't be parsed as numbers are
+    /// returned as `RawValue::Strings`, with `NumericStringMode::Strict` they result in an error.
+    pub fn parse_value_with_mode(&self, mode: NumericStringMode) -> Result<RawValue> {
+        if self.bulk_data.is_some() {
+            Err(error("bulk data value has not been loaded", self))
+        } else if self.vr == &vr::AT {
             let attr: Attribute = Attribute::try_from(self)?;
             Ok(RawValue::Attribute(attr))
         } else if self.vr == &vr::UI {
             let uid: String = String::try_from(self)?;
             Ok(RawValue::Uid(uid))
         } else if self.vr == &vr::DS {
-            let parsed: Result<Vec<f64>> = Vec::<f64>::try_from(self);
-            if let Ok(doubles) = parsed {
-                Ok(RawValue::Doubles(doubles))
-            } else {
-                let parsed: Vec<String> = Vec::<String>::try_from(self)?;
-                Ok(RawValue::Strings(parsed))
+            match numeric::parse_decimal_strings(self, mode) {
+                Ok(doubles) => Ok(RawValue::Doubles(doubles)),
+                Err(_) if mode == NumericStringMode::Lenient => {
+                    Ok(RawValue::Strings(Vec::<String>::try_from(self)?))
+                }
+                Err(e) => Err(e),
             }
         } else if self.vr == &vr::IS {
-            let parsed: Result<Vec<f64>> = Vec::<f64>::try_from(self);
-            if let Ok(doubles) = parsed {
-                let ints: Vec<i32> = doubles.into_iter().map(|v| v as i32).collect::<Vec<i32>>();
-                Ok(RawValue::Integers(ints))
-            } else {
-                let parsed: Vec<String> = Vec::<String>::try_from(self)?;
-                Ok(RawValue::Strings(parsed))
+            match numeric::parse_integer_strings(self, mode) {
+                Ok(ints) => Ok(RawValue::Integers(ints)),
+                Err(_) if mode == NumericStringMode::Lenient => {
+                    Ok(RawValue::Strings(Vec::<String>::try_from(self)?))
+                }
+                Err(e) => Err(e),
             }
         } else if self.vr.is_character_string {
             let strings: Vec<String> = Vec::<String>::try_from(self)?;
@@ -227,6 +303,22 @@ impl DicomElement {
                 _ => vec![],
             };
             Ok(RawValue::UnsignedIntegers(uints))
+        } else if self.vr == &vr::SV {
+            let longs: Vec<i64> = match self.vl {
+                ValueLength::Explicit(len) if len > 0 && len % 8 == 0 => {
+                    Vec::<i64>::try_from(self)?
+                }
+                _ => vec![],
+            };
+            Ok(RawValue::Longs(longs))
+        } else if self.vr == &vr::UV || self.vr == &vr::OV {
+            let ulongs: Vec<u64> = match self.vl {
+                ValueLength::Explicit(len) if len > 0 && len % 8 == 0 => {
+                    Vec::<u64>::try_from(self)?
+                }
+                _ => vec![],
+            };
+            Ok(RawValue::UnsignedLongs(ulongs))
         } else {
             let bytes: Vec<u8> = self.get_data().clone();
             Ok(RawValue::Bytes(bytes))
@@ -667,6 +759,116 @@ impl TryFrom<&DicomElement> for Vec<u32> {
     }
 }
 
+impl TryFrom<&DicomElement> for i64 {
+    type Error = ParseError;
+
+    /// Parses the value for this element as a signed 64bit integer
+    /// Associated VRs: SV
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        Vec::<i64>::try_from(value)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| error("no i64's parsed", value))
+    }
+}
+
+impl TryFrom<&DicomElement> for Vec<i64> {
+    type Error = ParseError;
+
+    /// Parses the value for this element as a list of signed 64bit integer values
+    /// Associated VRs: SV
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        if value.vr.is_character_string {
+            let (values, errors): (Vec<Result<i64>>, Vec<Result<i64>>) =
+                Vec::<String>::try_from(value)?
+                    .into_iter()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse::<i64>().map_err(|e| error(&e.to_string(), value)))
+                    .partition(Result::is_ok);
+            if let Some(Err(e)) = errors.into_iter().last() {
+                return Err(e);
+            }
+            let values: Vec<i64> = values.into_iter().map(Result::unwrap).collect::<Vec<i64>>();
+            return Ok(values);
+        }
+
+        let num_bytes: usize = value.data.len();
+        if num_bytes < I64_SIZE || num_bytes % I64_SIZE != 0 {
+            return Err(error("num bytes not multiple of size of i64", value));
+        }
+
+        let mut buf: [u8; I64_SIZE] = [0; I64_SIZE];
+        let num_i64s: usize = num_bytes / I64_SIZE;
+        let mut result: Vec<i64> = Vec::with_capacity(num_i64s);
+        for i in 0..num_i64s {
+            let start: usize = i * I64_SIZE;
+            buf.copy_from_slice(&value.data[start..(start + I64_SIZE)]);
+            let val: i64 = if value.ts.is_big_endian() {
+                i64::from_be_bytes(buf)
+            } else {
+                i64::from_le_bytes(buf)
+            };
+            result.push(val);
+        }
+        Ok(result)
+    }
+}
+
+impl TryFrom<&DicomElement> for u64 {
+    type Error = ParseError;
+
+    /// Parses the value for this element as an unsigned 64bit integer
+    /// Associated VRs: UV
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        Vec::<u64>::try_from(value)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| error("no u64's parsed", value))
+    }
+}
+
+impl TryFrom<&DicomElement> for Vec<u64> {
+    type Error = ParseError;
+
+    /// Parses the value for this element as a list of unsigned 64bit integer values
+    /// Associated VRs: UV, OV
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        if value.vr.is_character_string {
+            let (values, errors): (Vec<Result<u64>>, Vec<Result<u64>>) =
+                Vec::<String>::try_from(value)?
+                    .into_iter()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse::<u64>().map_err(|e| error(&e.to_string(), value)))
+                    .partition(Result::is_ok);
+            if let Some(Err(e)) = errors.into_iter().last() {
+                return Err(e);
+            }
+            let values: Vec<u64> = values.into_iter().map(Result::unwrap).collect::<Vec<u64>>();
+            return Ok(values);
+        }
+
+        let num_bytes: usize = value.data.len();
+        if num_bytes < U64_SIZE || num_bytes % U64_SIZE != 0 {
+            return Err(error("num bytes not multiple of size of u64", value));
+        }
+
+        let mut buf: [u8; U64_SIZE] = [0; U64_SIZE];
+        let num_u64s: usize = num_bytes / U64_SIZE;
+        let mut result: Vec<u64> = Vec::with_capacity(num_u64s);
+        for i in 0..num_u64s {
+            let start: usize = i * U64_SIZE;
+            buf.copy_from_slice(&value.data[start..(start + U64_SIZE)]);
+            let val: u64 = if value.ts.is_big_endian() {
+                u64::from_be_bytes(buf)
+            } else {
+                u64::from_le_bytes(buf)
+            };
+            result.push(val);
+        }
+        Ok(result)
+    }
+}
+
 impl TryFrom<&DicomElement> for u16 {
     type Error = ParseError;
 
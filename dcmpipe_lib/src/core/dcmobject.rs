@@ -7,6 +7,19 @@ use crate::defn::dcmdict::DicomDictionary;
 use crate::defn::tag::{TagNode, TagPath};
 use crate::defn::ts::TSRef;
 
+/// What `parse_into_object` does when the same tag appears more than once within a dataset or
+/// item, since each node keeps only one child per tag.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum DuplicateTagPolicy {
+    /// The first element with the tag is kept.
+    KeepFirst,
+    /// The last element with the tag is kept.
+    #[default]
+    KeepLast,
+    /// Parsing fails with `ParseError::DuplicateTag`.
+    Error,
+}
+
 /// Trait for a dicom node which contains child elements.
 pub trait DicomNode {
     /// Get the number of child node if this is a root or sequence-like node.
@@ -15,6 +28,9 @@ pub trait DicomNode {
     fn get_child_by_tag(&self, tag: u32) -> Option<&DicomObject>;
     /// Iterator over the child nodes, in tag-ascending order.
     fn iter_child_nodes(&self) -> btree_map::Iter<'_, u32, DicomObject>;
+    /// Get the tags of the child nodes in the order they were parsed from the dataset, which
+    /// differs from the order of `iter_child_nodes` if the dataset's tags are out of order.
+    fn get_child_order(&self) -> &[u32];
     /// Get the number of item nodes if this is a sequence-like node.
     fn get_item_count(&self) -> usize;
     /// Get an item of the given index. Index is 1-based.
@@ -61,6 +77,7 @@ pub struct DicomRoot<'dict> {
     cs: CSRef,
     dictionary: &'dict dyn DicomDictionary,
     child_nodes: BTreeMap<u32, DicomObject>,
+    child_order: Vec<u32>,
 }
 
 impl<'dict> DicomRoot<'dict> {
@@ -70,14 +87,23 @@ impl<'dict> DicomRoot<'dict> {
         dictionary: &dyn DicomDictionary,
         child_nodes: BTreeMap<u32, DicomObject>,
     ) -> DicomRoot<'_> {
+        let child_order: Vec<u32> = child_nodes.keys().copied().collect();
         DicomRoot {
             ts,
             cs,
             dictionary,
             child_nodes,
+            child_order,
         }
     }
 
+    /// Sets the order the child nodes were parsed in. This should contain each of the tags of the
+    /// child nodes once.
+    pub fn with_child_order(mut self, child_order: Vec<u32>) -> Self {
+        self.child_order = child_order;
+        self
+    }
+
     /// Get the transfer syntax used to encode the dataset.
     pub fn get_ts(&self) -> TSRef {
         self.ts
@@ -107,6 +133,10 @@ impl<'dict> DicomNode for DicomRoot<'dict> {
         self.child_nodes.iter()
     }
 
+    fn get_child_order(&self) -> &[u32] {
+        &self.child_order
+    }
+
     fn get_item_count(&self) -> usize {
         0
     }
@@ -123,6 +153,8 @@ pub struct DicomObject {
     element: DicomElement,
     /// Child nodes which may be elements of sub-sequences.
     child_nodes: BTreeMap<u32, DicomObject>,
+    /// The tags of the child nodes in the order they were parsed.
+    child_order: Vec<u32>,
     /// Item nodes don't go into the map since there can be multiple of them.
     items: Vec<DicomObject>,
 }
@@ -132,6 +164,7 @@ impl DicomObject {
         DicomObject {
             element,
             child_nodes: BTreeMap::new(),
+            child_order: Vec::new(),
             items: Vec::new(),
         }
     }
@@ -141,13 +174,22 @@ impl DicomObject {
         child_nodes: BTreeMap<u32, DicomObject>,
         items: Vec<DicomObject>,
     ) -> DicomObject {
+        let child_order: Vec<u32> = child_nodes.keys().copied().collect();
         DicomObject {
             element,
             child_nodes,
+            child_order,
             items,
         }
     }
 
+    /// Sets the order the child nodes were parsed in. This should contain each of the tags of the
+    /// child nodes once.
+    pub fn with_child_order(mut self, child_order: Vec<u32>) -> Self {
+        self.child_order = child_order;
+        self
+    }
+
     /// Gets the underlying `DicomElement` for this `DicomObject`
     pub fn get_element(&self) -> &DicomElement {
         &self.element
@@ -167,6 +209,10 @@ impl DicomNode for DicomObject {
         self.child_nodes.iter()
     }
 
+    fn get_child_order(&self) -> &[u32] {
+        &self.child_order
+    }
+
     fn get_item_count(&self) -> usize {
         self.items.len()
     }
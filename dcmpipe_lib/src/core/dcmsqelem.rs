@@ -1,13 +1,15 @@
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
 use crate::core::charset::CSRef;
-use crate::defn::tag::TagNode;
+use crate::defn::constants::tags;
+use crate::defn::tag::{TagNode, TagPath};
 use crate::defn::ts::TSRef;
 
 /// Represents the sequence/item position of an element.
 /// For elements to track which sequence they are a part of. When an SQ element is parsed the parser
-/// adds a new `SequenceElement` to its current path which subsequent elements will clone for
-/// themselves. This allows elements to know how they exist within a dicom object.
+/// adds a new `SequenceElement` to its current path which subsequent elements will share, see
+/// `SequencePath`. This allows elements to know how they exist within a dicom object.
 #[derive(Clone)]
 pub struct SequenceElement {
     /// See Part 5 Section 7.5
@@ -106,3 +108,117 @@ impl Debug for SequenceElement {
         write!(f, "node: {:?}, end: {:?}", self.node, self.seq_end_pos)
     }
 }
+
+/// The sequences and items an element is nested within, outermost first. Every element parsed
+/// from the dataset holds the path it was parsed at, so paths are persistent lists which share
+/// their ancestors. Cloning a path is constant time and memory regardless of its depth, and
+/// modifying the innermost sequence element only copies that one sequence element.
+#[derive(Clone, Default)]
+pub struct SequencePath {
+    last: Option<Arc<SequencePathLink>>,
+    len: usize,
+}
+
+/// A sequence element within a `SequencePath` along with the path of its parent.
+#[derive(Clone)]
+struct SequencePathLink {
+    element: SequenceElement,
+    parent: Option<Arc<SequencePathLink>>,
+}
+
+impl SequencePath {
+    pub fn new() -> SequencePath {
+        SequencePath::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The outermost sequence element.
+    pub fn first(&self) -> Option<&SequenceElement> {
+        self.links().last().map(|link| &link.element)
+    }
+
+    /// The innermost sequence element.
+    pub fn last(&self) -> Option<&SequenceElement> {
+        self.last.as_ref().map(|link| &link.element)
+    }
+
+    /// The innermost sequence element, for modifying. If other paths share the sequence element
+    /// then it's copied so those paths are unaffected.
+    pub fn last_mut(&mut self) -> Option<&mut SequenceElement> {
+        self.last
+            .as_mut()
+            .map(|link| &mut Arc::make_mut(link).element)
+    }
+
+    /// Adds a sequence element as the innermost.
+    pub fn push(&mut self, element: SequenceElement) {
+        let parent: Option<Arc<SequencePathLink>> = self.last.take();
+        self.last = Some(Arc::new(SequencePathLink { element, parent }));
+        self.len += 1;
+    }
+
+    /// Removes the innermost sequence element.
+    pub fn pop(&mut self) -> Option<SequenceElement> {
+        let last: Arc<SequencePathLink> = self.last.take()?;
+        self.len -= 1;
+        match Arc::try_unwrap(last) {
+            Ok(link) => {
+                self.last = link.parent;
+                Some(link.element)
+            }
+            Err(last) => {
+                self.last = last.parent.clone();
+                Some(last.element.clone())
+            }
+        }
+    }
+
+    /// Iterates over the sequence elements, outermost first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &SequenceElement> + ExactSizeIterator {
+        let mut elements: Vec<&SequenceElement> = self.links().map(|link| &link.element).collect();
+        elements.reverse();
+        elements.into_iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<SequenceElement> {
+        self.iter().cloned().collect::<Vec<SequenceElement>>()
+    }
+
+    /// The path to an element with the given tag parsed at this sequence path.
+    pub fn get_tagpath(&self, tag: u32) -> TagPath {
+        self.iter()
+            .filter(|sq| sq.get_seq_tag() != tags::ITEM)
+            .map(|sq| sq.get_node().clone())
+            .chain(std::iter::once(tag.into()))
+            .collect::<Vec<TagNode>>()
+            .into()
+    }
+
+    /// The links of the path, innermost first.
+    fn links(&self) -> impl Iterator<Item = &SequencePathLink> {
+        std::iter::successors(self.last.as_deref(), |link| link.parent.as_deref())
+    }
+}
+
+impl From<Vec<SequenceElement>> for SequencePath {
+    fn from(elements: Vec<SequenceElement>) -> Self {
+        let mut path: SequencePath = SequencePath::new();
+        for element in elements {
+            path.push(element);
+        }
+        path
+    }
+}
+
+impl Debug for SequencePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
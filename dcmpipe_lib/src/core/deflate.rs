@@ -0,0 +1,134 @@
+//! Writing datasets with the Deflated Explicit VR Little Endian transfer syntax. The File Meta of a
+//! deflated dataset is written as-is while everything following it is compressed with deflate.
+
+use std::io::{self, Cursor, Write};
+
+use libflate::deflate::Encoder;
+
+use crate::core::dcmelement::DicomElement;
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::{
+    Result, DICOM_PREFIX, DICOM_PREFIX_LENGTH, FILE_PREAMBLE_LENGTH,
+};
+use crate::core::parser::{Parser, ParserBuilder};
+use crate::core::tagstop::TagStop;
+use crate::defn::constants::{tags, ts, uids};
+use crate::defn::ts::TSRef;
+
+/// Writes a dataset with the Deflated Explicit VR Little Endian transfer syntax. The preamble,
+/// prefix, and File Meta given to `new` are written uncompressed. All bytes written afterwards
+/// are deflated, and should be the dataset encoded as Explicit VR Little Endian.
+pub struct DeflatedWriter<W: Write> {
+    encoder: Encoder<W>,
+}
+
+impl<W: Write> DeflatedWriter<W> {
+    /// Writes the preamble, prefix, and File Meta, which should specify the Deflated Explicit VR
+    /// Little Endian transfer syntax, to the writer.
+    pub fn new(mut writer: W, file_meta: &[u8]) -> io::Result<DeflatedWriter<W>> {
+        writer.write_all(file_meta)?;
+        Ok(DeflatedWriter {
+            encoder: Encoder::new(writer),
+        })
+    }
+
+    /// Finishes deflating the dataset, returning the underlying writer.
+    pub fn finish(self) -> io::Result<W> {
+        self.encoder.finish().into_result()
+    }
+}
+
+impl<W: Write> Write for DeflatedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoder.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder.flush()
+    }
+}
+
+/// Converts a DICOM file encoded with Explicit VR Little Endian to Deflated Explicit VR Little
+/// Endian. The File Meta is written with the updated Transfer Syntax UID and group length, and the
+/// dataset following it is deflated. The file must have the preamble and DICOM prefix.
+pub fn deflate_file(file: &[u8]) -> Result<Vec<u8>> {
+    let mut parser: Parser<'_, Cursor<&[u8]>> = ParserBuilder::default()
+        .tagstop(TagStop::AfterGroup(0x0002))
+        .record_offsets(true)
+        .build(Cursor::new(file));
+    let file_meta: Vec<DicomElement> = (&mut parser).collect::<Result<Vec<DicomElement>>>()?;
+    let file_preamble: [u8; FILE_PREAMBLE_LENGTH] = parser
+        .get_file_preamble()
+        .ok_or(ParseError::MissingDICOMPrefix)?;
+    let ts: TSRef = parser.get_ts();
+    if ts != &ts::ExplicitVRLittleEndian {
+        return Err(ParseError::UnsupportedTransferSyntax { ts });
+    }
+
+    let mut dataset_start: usize = FILE_PREAMBLE_LENGTH + DICOM_PREFIX_LENGTH;
+    let mut elements: Vec<u8> = Vec::new();
+    let mut ts_written: bool = false;
+    for element in &file_meta {
+        if let Some(offsets) = element.get_offsets() {
+            dataset_start = (offsets.value_offset as usize) + element.get_data().len();
+        }
+        if element.tag > tags::TRANSFER_SYNTAX_UID && !ts_written {
+            write_deflated_ts(&mut elements);
+            ts_written = true;
+        }
+        match element.tag {
+            tags::FILE_META_INFORMATION_GROUP_LENGTH => {}
+            tags::TRANSFER_SYNTAX_UID => {
+                write_deflated_ts(&mut elements);
+                ts_written = true;
+            }
+            _ => write_element(&mut elements, element),
+        }
+    }
+    if !ts_written {
+        write_deflated_ts(&mut elements);
+    }
+
+    let mut header: Vec<u8> = file_preamble.to_vec();
+    header.extend_from_slice(DICOM_PREFIX);
+    header.extend_from_slice(&tag_bytes(tags::FILE_META_INFORMATION_GROUP_LENGTH));
+    header.extend_from_slice(b"UL");
+    header.extend_from_slice(&4u16.to_le_bytes());
+    header.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+    header.extend(elements);
+
+    let mut writer: DeflatedWriter<Vec<u8>> = DeflatedWriter::new(Vec::new(), &header)?;
+    writer.write_all(file.get(dataset_start..).unwrap_or_default())?;
+    Ok(writer.finish()?)
+}
+
+fn tag_bytes(tag: u32) -> [u8; 4] {
+    let group: [u8; 2] = ((tag >> 16) as u16).to_le_bytes();
+    let element: [u8; 2] = ((tag & 0xFFFF) as u16).to_le_bytes();
+    [group[0], group[1], element[0], element[1]]
+}
+
+/// Writes the element encoded as Explicit VR Little Endian.
+fn write_element(bytes: &mut Vec<u8>, element: &DicomElement) {
+    let value: &[u8] = element.get_data();
+    bytes.extend_from_slice(&tag_bytes(element.tag));
+    bytes.extend_from_slice(element.vr.ident.as_bytes());
+    if element.vr.has_explicit_2byte_pad {
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    } else {
+        bytes.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    }
+    bytes.extend_from_slice(value);
+}
+
+fn write_deflated_ts(bytes: &mut Vec<u8>) {
+    let mut uid: Vec<u8> = uids::DeflatedExplicitVRLittleEndian.uid.as_bytes().to_vec();
+    if uid.len() % 2 == 1 {
+        uid.push(0);
+    }
+    bytes.extend_from_slice(&tag_bytes(tags::TRANSFER_SYNTAX_UID));
+    bytes.extend_from_slice(b"UI");
+    bytes.extend_from_slice(&(uid.len() as u16).to_le_bytes());
+    bytes.extend(uid);
+}
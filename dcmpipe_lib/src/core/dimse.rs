@@ -0,0 +1,602 @@
+//! DIMSE command sets, Part 7 Section 9.3 and 10.3. A command set is the group 0000 elements of a
+//! DIMSE message, always encoded as Implicit VR Little Endian regardless of the transfer syntax
+//! negotiated for the data set which may follow it.
+
+use std::convert::TryFrom;
+
+use thiserror::Error;
+
+use crate::defn::tag::Tag;
+
+/// Command Group Length, (0000,0000)
+const COMMAND_GROUP_LENGTH: u32 = 0x0000_0000;
+/// Affected SOP Class UID, (0000,0002)
+const AFFECTED_SOP_CLASS_UID: u32 = 0x0000_0002;
+/// Requested SOP Class UID, (0000,0003)
+const REQUESTED_SOP_CLASS_UID: u32 = 0x0000_0003;
+/// Command Field, (0000,0100)
+const COMMAND_FIELD: u32 = 0x0000_0100;
+/// Message ID, (0000,0110)
+const MESSAGE_ID: u32 = 0x0000_0110;
+/// Message ID Being Responded To, (0000,0120)
+const MESSAGE_ID_BEING_RESPONDED_TO: u32 = 0x0000_0120;
+/// Move Destination, (0000,0600)
+const MOVE_DESTINATION: u32 = 0x0000_0600;
+/// Priority, (0000,0700)
+const PRIORITY: u32 = 0x0000_0700;
+/// Command Data Set Type, (0000,0800)
+const COMMAND_DATA_SET_TYPE: u32 = 0x0000_0800;
+/// Status, (0000,0900)
+const STATUS: u32 = 0x0000_0900;
+/// Error Comment, (0000,0902)
+const ERROR_COMMENT: u32 = 0x0000_0902;
+/// Error ID, (0000,0903)
+const ERROR_ID: u32 = 0x0000_0903;
+/// Affected SOP Instance UID, (0000,1000)
+const AFFECTED_SOP_INSTANCE_UID: u32 = 0x0000_1000;
+/// Requested SOP Instance UID, (0000,1001)
+const REQUESTED_SOP_INSTANCE_UID: u32 = 0x0000_1001;
+/// Event Type ID, (0000,1002)
+const EVENT_TYPE_ID: u32 = 0x0000_1002;
+/// Action Type ID, (0000,1008)
+const ACTION_TYPE_ID: u32 = 0x0000_1008;
+/// Number of Remaining Sub-operations, (0000,1020)
+const NUMBER_OF_REMAINING_SUBOPERATIONS: u32 = 0x0000_1020;
+/// Number of Completed Sub-operations, (0000,1021)
+const NUMBER_OF_COMPLETED_SUBOPERATIONS: u32 = 0x0000_1021;
+/// Number of Failed Sub-operations, (0000,1022)
+const NUMBER_OF_FAILED_SUBOPERATIONS: u32 = 0x0000_1022;
+/// Number of Warning Sub-operations, (0000,1023)
+const NUMBER_OF_WARNING_SUBOPERATIONS: u32 = 0x0000_1023;
+/// Move Originator Application Entity Title, (0000,1030)
+const MOVE_ORIGINATOR_AE_TITLE: u32 = 0x0000_1030;
+/// Move Originator Message ID, (0000,1031)
+const MOVE_ORIGINATOR_MESSAGE_ID: u32 = 0x0000_1031;
+
+/// The Command Data Set Type value indicating no data set follows the command set. Any other
+/// value indicates a data set is present.
+pub const NO_DATA_SET: u16 = 0x0101;
+/// The Command Data Set Type value used when encoding a command set which has a data set.
+pub const DATA_SET_PRESENT: u16 = 0x0000;
+
+/// The length of an element's tag and value length when encoded as Implicit VR Little Endian.
+const ELEMENT_HEADER_LENGTH: usize = 8;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+/// Errors that can occur when decoding a DIMSE message.
+pub enum DimseError {
+    #[error("command set ended within an element")]
+    /// The encoded command set ends partway through an element.
+    UnexpectedEnd,
+
+    #[error("element is not part of the command set: {}", Tag::format_tag_to_display(* .0))]
+    /// The command set contains an element outside of group 0000.
+    NotCommandElement(u32),
+
+    #[error("command group length {group_length} does not match the encoded length {actual}")]
+    /// The Command Group Length does not match the length of the elements following it.
+    GroupLengthMismatch { group_length: u32, actual: usize },
+
+    #[error("command set is missing {}", Tag::format_tag_to_display(* .0))]
+    /// A required element of the command set is not present.
+    MissingElement(u32),
+
+    #[error("invalid value for {}: {message}", Tag::format_tag_to_display(* tag))]
+    /// The value of an element is not valid for the element.
+    InvalidValue { tag: u32, message: &'static str },
+
+    #[error("unknown command field: {0:#06X}")]
+    /// The Command Field value is not a known DIMSE command.
+    UnknownCommandField(u16),
+
+    #[error("command set indicates a data set, but none is present")]
+    /// The Command Data Set Type indicates a data set, but no data set was given.
+    MissingDataSet,
+
+    #[error("command set indicates no data set, but one is present")]
+    /// The Command Data Set Type indicates no data set, but a data set was given.
+    UnexpectedDataSet,
+}
+
+/// The DIMSE commands, identified by the Command Field, Part 7 Section E.1.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CommandField {
+    CStoreRq,
+    CStoreRsp,
+    CGetRq,
+    CGetRsp,
+    CFindRq,
+    CFindRsp,
+    CMoveRq,
+    CMoveRsp,
+    CEchoRq,
+    CEchoRsp,
+    NEventReportRq,
+    NEventReportRsp,
+    NGetRq,
+    NGetRsp,
+    NSetRq,
+    NSetRsp,
+    NActionRq,
+    NActionRsp,
+    NCreateRq,
+    NCreateRsp,
+    NDeleteRq,
+    NDeleteRsp,
+    CCancelRq,
+}
+
+impl CommandField {
+    /// The value of the Command Field element for this command.
+    pub fn get_value(self) -> u16 {
+        match self {
+            CommandField::CStoreRq => 0x0001,
+            CommandField::CStoreRsp => 0x8001,
+            CommandField::CGetRq => 0x0010,
+            CommandField::CGetRsp => 0x8010,
+            CommandField::CFindRq => 0x0020,
+            CommandField::CFindRsp => 0x8020,
+            CommandField::CMoveRq => 0x0021,
+            CommandField::CMoveRsp => 0x8021,
+            CommandField::CEchoRq => 0x0030,
+            CommandField::CEchoRsp => 0x8030,
+            CommandField::NEventReportRq => 0x0100,
+            CommandField::NEventReportRsp => 0x8100,
+            CommandField::NGetRq => 0x0110,
+            CommandField::NGetRsp => 0x8110,
+            CommandField::NSetRq => 0x0120,
+            CommandField::NSetRsp => 0x8120,
+            CommandField::NActionRq => 0x0130,
+            CommandField::NActionRsp => 0x8130,
+            CommandField::NCreateRq => 0x0140,
+            CommandField::NCreateRsp => 0x8140,
+            CommandField::NDeleteRq => 0x0150,
+            CommandField::NDeleteRsp => 0x8150,
+            CommandField::CCancelRq => 0x0FFF,
+        }
+    }
+
+    /// Whether this command is a response, which are distinguished by the high bit.
+    pub fn is_response(self) -> bool {
+        self.get_value() & 0x8000 != 0
+    }
+}
+
+impl TryFrom<u16> for CommandField {
+    type Error = DimseError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0001 => Ok(CommandField::CStoreRq),
+            0x8001 => Ok(CommandField::CStoreRsp),
+            0x0010 => Ok(CommandField::CGetRq),
+            0x8010 => Ok(CommandField::CGetRsp),
+            0x0020 => Ok(CommandField::CFindRq),
+            0x8020 => Ok(CommandField::CFindRsp),
+            0x0021 => Ok(CommandField::CMoveRq),
+            0x8021 => Ok(CommandField::CMoveRsp),
+            0x0030 => Ok(CommandField::CEchoRq),
+            0x8030 => Ok(CommandField::CEchoRsp),
+            0x0100 => Ok(CommandField::NEventReportRq),
+            0x8100 => Ok(CommandField::NEventReportRsp),
+            0x0110 => Ok(CommandField::NGetRq),
+            0x8110 => Ok(CommandField::NGetRsp),
+            0x0120 => Ok(CommandField::NSetRq),
+            0x8120 => Ok(CommandField::NSetRsp),
+            0x0130 => Ok(CommandField::NActionRq),
+            0x8130 => Ok(CommandField::NActionRsp),
+            0x0140 => Ok(CommandField::NCreateRq),
+            0x8140 => Ok(CommandField::NCreateRsp),
+            0x0150 => Ok(CommandField::NDeleteRq),
+            0x8150 => Ok(CommandField::NDeleteRsp),
+            0x0FFF => Ok(CommandField::CCancelRq),
+            _ => Err(DimseError::UnknownCommandField(value)),
+        }
+    }
+}
+
+/// The priority of a C-STORE, C-FIND, C-GET, or C-MOVE request.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority {
+    /// The value of the Priority element for this priority.
+    pub fn get_value(self) -> u16 {
+        match self {
+            Priority::Low => 0x0002,
+            Priority::Medium => 0x0000,
+            Priority::High => 0x0001,
+        }
+    }
+}
+
+impl TryFrom<u16> for Priority {
+    type Error = DimseError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0002 => Ok(Priority::Low),
+            0x0000 => Ok(Priority::Medium),
+            0x0001 => Ok(Priority::High),
+            _ => Err(DimseError::InvalidValue {
+                tag: PRIORITY,
+                message: "unknown priority",
+            }),
+        }
+    }
+}
+
+/// The category of a response's Status, Part 7 Annex C.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StatusKind {
+    Success,
+    Pending,
+    Cancel,
+    Warning,
+    Failure,
+}
+
+impl From<u16> for StatusKind {
+    fn from(status: u16) -> Self {
+        match status {
+            0x0000 => StatusKind::Success,
+            0xFF00 | 0xFF01 => StatusKind::Pending,
+            0xFE00 => StatusKind::Cancel,
+            0x0001 | 0x0107 | 0x0116 | 0xB000..=0xBFFF => StatusKind::Warning,
+            _ => StatusKind::Failure,
+        }
+    }
+}
+
+/// The elements of a DIMSE command set. Which elements are present depends on the command, see
+/// the command definitions in Part 7 Sections 9.3 and 10.3. Elements not listed here are ignored
+/// when decoding.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommandSet {
+    pub command_field: CommandField,
+    pub message_id: Option<u16>,
+    pub message_id_being_responded_to: Option<u16>,
+    pub affected_sop_class_uid: Option<String>,
+    pub requested_sop_class_uid: Option<String>,
+    pub affected_sop_instance_uid: Option<String>,
+    pub requested_sop_instance_uid: Option<String>,
+    pub priority: Option<Priority>,
+    /// Whether a data set follows the command set, from the Command Data Set Type.
+    pub has_data_set: bool,
+    pub status: Option<u16>,
+    pub error_comment: Option<String>,
+    pub error_id: Option<u16>,
+    pub move_destination: Option<String>,
+    pub event_type_id: Option<u16>,
+    pub action_type_id: Option<u16>,
+    pub remaining_suboperations: Option<u16>,
+    pub completed_suboperations: Option<u16>,
+    pub failed_suboperations: Option<u16>,
+    pub warning_suboperations: Option<u16>,
+    pub move_originator_ae_title: Option<String>,
+    pub move_originator_message_id: Option<u16>,
+}
+
+impl CommandSet {
+    /// Creates a command set for the given command with no other elements.
+    pub fn new(command_field: CommandField) -> CommandSet {
+        CommandSet {
+            command_field,
+            message_id: None,
+            message_id_being_responded_to: None,
+            affected_sop_class_uid: None,
+            requested_sop_class_uid: None,
+            affected_sop_instance_uid: None,
+            requested_sop_instance_uid: None,
+            priority: None,
+            has_data_set: false,
+            status: None,
+            error_comment: None,
+            error_id: None,
+            move_destination: None,
+            event_type_id: None,
+            action_type_id: None,
+            remaining_suboperations: None,
+            completed_suboperations: None,
+            failed_suboperations: None,
+            warning_suboperations: None,
+            move_originator_ae_title: None,
+            move_originator_message_id: None,
+        }
+    }
+
+    /// The category of the Status, if present.
+    pub fn get_status_kind(&self) -> Option<StatusKind> {
+        self.status.map(StatusKind::from)
+    }
+
+    /// Encodes the command set as Implicit VR Little Endian, starting with the Command Group
+    /// Length. Elements are written in ascending tag order.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut elements: Vec<u8> = Vec::new();
+        write_uid(
+            &mut elements,
+            AFFECTED_SOP_CLASS_UID,
+            &self.affected_sop_class_uid,
+        );
+        write_uid(
+            &mut elements,
+            REQUESTED_SOP_CLASS_UID,
+            &self.requested_sop_class_uid,
+        );
+
+        write_element(
+            &mut elements,
+            COMMAND_FIELD,
+            &self.command_field.get_value().to_le_bytes(),
+        );
+        write_us(&mut elements, MESSAGE_ID, self.message_id);
+        write_us(
+            &mut elements,
+            MESSAGE_ID_BEING_RESPONDED_TO,
+            self.message_id_being_responded_to,
+        );
+        write_text(&mut elements, MOVE_DESTINATION, &self.move_destination);
+        write_us(
+            &mut elements,
+            PRIORITY,
+            self.priority.map(Priority::get_value),
+        );
+        let data_set_type: u16 = if self.has_data_set {
+            DATA_SET_PRESENT
+        } else {
+            NO_DATA_SET
+        };
+        write_us(&mut elements, COMMAND_DATA_SET_TYPE, Some(data_set_type));
+        write_us(&mut elements, STATUS, self.status);
+        write_text(&mut elements, ERROR_COMMENT, &self.error_comment);
+        write_us(&mut elements, ERROR_ID, self.error_id);
+
+        write_uid(
+            &mut elements,
+            AFFECTED_SOP_INSTANCE_UID,
+            &self.affected_sop_instance_uid,
+        );
+        write_uid(
+            &mut elements,
+            REQUESTED_SOP_INSTANCE_UID,
+            &self.requested_sop_instance_uid,
+        );
+
+        write_us(&mut elements, EVENT_TYPE_ID, self.event_type_id);
+        write_us(&mut elements, ACTION_TYPE_ID, self.action_type_id);
+        write_us(
+            &mut elements,
+            NUMBER_OF_REMAINING_SUBOPERATIONS,
+            self.remaining_suboperations,
+        );
+        write_us(
+            &mut elements,
+            NUMBER_OF_COMPLETED_SUBOPERATIONS,
+            self.completed_suboperations,
+        );
+        write_us(
+            &mut elements,
+            NUMBER_OF_FAILED_SUBOPERATIONS,
+            self.failed_suboperations,
+        );
+        write_us(
+            &mut elements,
+            NUMBER_OF_WARNING_SUBOPERATIONS,
+            self.warning_suboperations,
+        );
+        write_text(
+            &mut elements,
+            MOVE_ORIGINATOR_AE_TITLE,
+            &self.move_originator_ae_title,
+        );
+        write_us(
+            &mut elements,
+            MOVE_ORIGINATOR_MESSAGE_ID,
+            self.move_originator_message_id,
+        );
+
+        let mut encoded: Vec<u8> = Vec::with_capacity(ELEMENT_HEADER_LENGTH + 4 + elements.len());
+        write_element(
+            &mut encoded,
+            COMMAND_GROUP_LENGTH,
+            &(elements.len() as u32).to_le_bytes(),
+        );
+        encoded.extend(elements);
+        encoded
+    }
+
+    /// Decodes a command set encoded as Implicit VR Little Endian. The Command Group Length is
+    /// checked if present, and the Command Field is required.
+    pub fn decode(bytes: &[u8]) -> Result<CommandSet, DimseError> {
+        // the command field is replaced once decoded, it's required to be present
+        let mut command_field: Option<CommandField> = None;
+        let mut command: CommandSet = CommandSet::new(CommandField::CEchoRq);
+        let mut has_data_set: Option<bool> = None;
+
+        let mut position: usize = 0;
+        while position < bytes.len() {
+            let header: &[u8] = bytes
+                .get(position..position + ELEMENT_HEADER_LENGTH)
+                .ok_or(DimseError::UnexpectedEnd)?;
+            let group: u16 = u16::from_le_bytes([header[0], header[1]]);
+            let element: u16 = u16::from_le_bytes([header[2], header[3]]);
+            let tag: u32 = (u32::from(group) << 16) | u32::from(element);
+            let vl: usize =
+                u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+            position += ELEMENT_HEADER_LENGTH;
+            let value: &[u8] = bytes
+                .get(position..position.saturating_add(vl))
+                .ok_or(DimseError::UnexpectedEnd)?;
+            position += vl;
+
+            if group != 0x0000 {
+                return Err(DimseError::NotCommandElement(tag));
+            }
+
+            match tag {
+                COMMAND_GROUP_LENGTH => {
+                    let group_length: u32 = read_ul(tag, value)?;
+                    let actual: usize = bytes.len() - position;
+                    if group_length as usize != actual {
+                        return Err(DimseError::GroupLengthMismatch {
+                            group_length,
+                            actual,
+                        });
+                    }
+                }
+                AFFECTED_SOP_CLASS_UID => command.affected_sop_class_uid = Some(read_text(value)),
+                REQUESTED_SOP_CLASS_UID => command.requested_sop_class_uid = Some(read_text(value)),
+                COMMAND_FIELD => {
+                    command_field = Some(CommandField::try_from(read_us(tag, value)?)?)
+                }
+                MESSAGE_ID => command.message_id = Some(read_us(tag, value)?),
+                MESSAGE_ID_BEING_RESPONDED_TO => {
+                    command.message_id_being_responded_to = Some(read_us(tag, value)?)
+                }
+                MOVE_DESTINATION => command.move_destination = Some(read_text(value)),
+                PRIORITY => command.priority = Some(Priority::try_from(read_us(tag, value)?)?),
+                COMMAND_DATA_SET_TYPE => has_data_set = Some(read_us(tag, value)? != NO_DATA_SET),
+                STATUS => command.status = Some(read_us(tag, value)?),
+                ERROR_COMMENT => command.error_comment = Some(read_text(value)),
+                ERROR_ID => command.error_id = Some(read_us(tag, value)?),
+                AFFECTED_SOP_INSTANCE_UID => {
+                    command.affected_sop_instance_uid = Some(read_text(value))
+                }
+                REQUESTED_SOP_INSTANCE_UID => {
+                    command.requested_sop_instance_uid = Some(read_text(value))
+                }
+                EVENT_TYPE_ID => command.event_type_id = Some(read_us(tag, value)?),
+                ACTION_TYPE_ID => command.action_type_id = Some(read_us(tag, value)?),
+                NUMBER_OF_REMAINING_SUBOPERATIONS => {
+                    command.remaining_suboperations = Some(read_us(tag, value)?)
+                }
+                NUMBER_OF_COMPLETED_SUBOPERATIONS => {
+                    command.completed_suboperations = Some(read_us(tag, value)?)
+                }
+                NUMBER_OF_FAILED_SUBOPERATIONS => {
+                    command.failed_suboperations = Some(read_us(tag, value)?)
+                }
+                NUMBER_OF_WARNING_SUBOPERATIONS => {
+                    command.warning_suboperations = Some(read_us(tag, value)?)
+                }
+                MOVE_ORIGINATOR_AE_TITLE => {
+                    command.move_originator_ae_title = Some(read_text(value))
+                }
+                MOVE_ORIGINATOR_MESSAGE_ID => {
+                    command.move_originator_message_id = Some(read_us(tag, value)?)
+                }
+                _ => {}
+            }
+        }
+
+        command.command_field = command_field.ok_or(DimseError::MissingElement(COMMAND_FIELD))?;
+        command.has_data_set =
+            has_data_set.ok_or(DimseError::MissingElement(COMMAND_DATA_SET_TYPE))?;
+        Ok(command)
+    }
+}
+
+/// A DIMSE message, a command set along with the data set it indicates is present. The data set
+/// is kept encoded in the transfer syntax negotiated for the presentation context.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DimseMessage {
+    pub command: CommandSet,
+    pub data_set: Option<Vec<u8>>,
+}
+
+impl DimseMessage {
+    /// Creates a message from the command set and data set. The command set's Command Data Set
+    /// Type is set based on whether there is a data set.
+    pub fn new(mut command: CommandSet, data_set: Option<Vec<u8>>) -> DimseMessage {
+        command.has_data_set = data_set.is_some();
+        DimseMessage { command, data_set }
+    }
+
+    /// Decodes the command set and pairs it with the data set, which must be present if and only
+    /// if the command set indicates one.
+    pub fn decode(command: &[u8], data_set: Option<Vec<u8>>) -> Result<DimseMessage, DimseError> {
+        let command: CommandSet = CommandSet::decode(command)?;
+        match (command.has_data_set, data_set.is_some()) {
+            (true, false) => Err(DimseError::MissingDataSet),
+            (false, true) => Err(DimseError::UnexpectedDataSet),
+            _ => Ok(DimseMessage { command, data_set }),
+        }
+    }
+
+    /// Encodes the command set, returning it along with the data set.
+    pub fn encode(&self) -> (Vec<u8>, Option<&[u8]>) {
+        (self.command.encode(), self.data_set.as_deref())
+    }
+}
+
+fn write_element(bytes: &mut Vec<u8>, tag: u32, value: &[u8]) {
+    bytes.extend_from_slice(&((tag >> 16) as u16).to_le_bytes());
+    bytes.extend_from_slice(&((tag & 0xFFFF) as u16).to_le_bytes());
+    bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(value);
+}
+
+fn write_us(bytes: &mut Vec<u8>, tag: u32, value: Option<u16>) {
+    if let Some(value) = value {
+        write_element(bytes, tag, &value.to_le_bytes());
+    }
+}
+
+/// Writes a UI value, padded with a null.
+fn write_uid(bytes: &mut Vec<u8>, tag: u32, value: &Option<String>) {
+    if let Some(value) = value {
+        write_element(bytes, tag, &pad_value(value, b'\0'));
+    }
+}
+
+/// Writes an AE or LO value, padded with a space.
+fn write_text(bytes: &mut Vec<u8>, tag: u32, value: &Option<String>) {
+    if let Some(value) = value {
+        write_element(bytes, tag, &pad_value(value, b' '));
+    }
+}
+
+/// Values must have an even length, so odd-length values are padded with the given byte, which
+/// is null for UIs and a space for text.
+fn pad_value(value: &str, padding: u8) -> Vec<u8> {
+    let mut bytes: Vec<u8> = value.as_bytes().to_vec();
+    if bytes.len() % 2 == 1 {
+        bytes.push(padding);
+    }
+    bytes
+}
+
+fn read_us(tag: u32, value: &[u8]) -> Result<u16, DimseError> {
+    match value {
+        [b0, b1] => Ok(u16::from_le_bytes([*b0, *b1])),
+        _ => Err(DimseError::InvalidValue {
+            tag,
+            message: "expected 2 bytes",
+        }),
+    }
+}
+
+fn read_ul(tag: u32, value: &[u8]) -> Result<u32, DimseError> {
+    match value {
+        [b0, b1, b2, b3] => Ok(u32::from_le_bytes([*b0, *b1, *b2, *b3])),
+        _ => Err(DimseError::InvalidValue {
+            tag,
+            message: "expected 4 bytes",
+        }),
+    }
+}
+
+/// Reads a UI, AE, or LO value, removing its padding. Command set text is limited to the default
+/// character repertoire so invalid bytes are replaced rather than rejected.
+fn read_text(value: &[u8]) -> String {
+    String::from_utf8_lossy(value)
+        .trim_end_matches(['\0', ' '])
+        .trim_start_matches(' ')
+        .to_owned()
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use crate::core::dcmsqelem::{SequenceElement, SequencePath};
+use crate::defn::constants::tags;
+
+/// The element number of Overlay Data within each overlay group.
+const OVERLAY_DATA_ELEMENT: u32 = 0x3000;
+
+/// ElementFilter specifies elements which should be skipped over while parsing a DICOM dataset
+/// rather than returned, see `ParserBuilder::filter`. Values of filtered elements are not read
+/// into memory, and if a sequence is filtered then so are all the elements within it.
+#[derive(Clone, Default)]
+pub struct ElementFilter {
+    /// Specific tags to filter.
+    tags: HashSet<u32>,
+    /// Ranges of tags to filter, inclusive of both ends.
+    ranges: Vec<RangeInclusive<u32>>,
+    /// Whether elements in private (odd-numbered) groups are filtered.
+    private_groups: bool,
+    /// Whether Group Length elements, `(gggg,0000)`, are filtered.
+    group_lengths: bool,
+    /// Whether Overlay Data elements, `(60xx,3000)`, are filtered.
+    overlay_data: bool,
+    /// Whether Item Delimitation Item and Sequence Delimitation Item elements are filtered.
+    delimiters: bool,
+}
+
+impl ElementFilter {
+    pub fn new() -> ElementFilter {
+        ElementFilter::default()
+    }
+
+    /// Adds the given tags to be filtered.
+    pub fn tags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// Adds a range of tags to be filtered, such as `0x0009_0000..=0x0009_FFFF` for a group.
+    pub fn tag_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.ranges.push(range);
+        self
+    }
+
+    /// Sets whether elements in private groups are filtered, which includes their private
+    /// creator elements.
+    pub fn private_groups(mut self, private_groups: bool) -> Self {
+        self.private_groups = private_groups;
+        self
+    }
+
+    /// Sets whether Group Length elements are filtered. These are deprecated outside of the File
+    /// Meta, see note on Part 5 Section 7.2.
+    pub fn group_lengths(mut self, group_lengths: bool) -> Self {
+        self.group_lengths = group_lengths;
+        self
+    }
+
+    /// Sets whether Overlay Data elements of all overlay groups are filtered.
+    pub fn overlay_data(mut self, overlay_data: bool) -> Self {
+        self.overlay_data = overlay_data;
+        self
+    }
+
+    /// Sets whether the delimiters of items and sequences are filtered. The elements within
+    /// sequences still track which sequence and item they're within.
+    pub fn delimiters(mut self, delimiters: bool) -> Self {
+        self.delimiters = delimiters;
+        self
+    }
+
+    /// Whether the element with the given tag should be filtered, not considering the sequences
+    /// it's within.
+    pub fn is_filtered(&self, tag: u32) -> bool {
+        let group: u32 = tag >> 16;
+        if self.private_groups && group % 2 == 1 {
+            return true;
+        }
+        if self.group_lengths && tag.trailing_zeros() >= 16 {
+            return true;
+        }
+        if self.overlay_data && group & 0xFF00 == 0x6000 && tag & 0xFFFF == OVERLAY_DATA_ELEMENT {
+            return true;
+        }
+        if self.delimiters
+            && (tag == tags::ITEM_DELIMITATION_ITEM || tag == tags::SEQUENCE_DELIMITATION_ITEM)
+        {
+            return true;
+        }
+        self.tags.contains(&tag) || self.ranges.iter().any(|range| range.contains(&tag))
+    }
+
+    /// Whether the element with the given tag parsed at the given sequence path should be
+    /// filtered, which is also the case if any of the sequences it's within are filtered.
+    pub fn is_filtered_at(&self, tag: u32, path: &SequencePath) -> bool {
+        self.is_filtered(tag)
+            || path
+                .iter()
+                .map(SequenceElement::get_seq_tag)
+                .filter(|seq_tag| *seq_tag != tags::ITEM)
+                .any(|seq_tag| self.is_filtered(seq_tag))
+    }
+}
@@ -1,6 +1,19 @@
 pub mod parser;
+pub mod bulkdata;
 pub mod charset;
+pub mod codedconcept;
+pub mod datetime;
 pub mod dcmelement;
 pub mod dcmobject;
 pub mod dcmsqelem;
+#[cfg(feature = "deflate")]
+pub mod deflate;
+pub mod dimse;
+pub mod filter;
+pub mod numeric;
+pub mod personname;
+pub mod seekable;
+#[cfg(feature = "archive")]
+pub mod source;
 pub mod tagstop;
+pub mod validate;
@@ -0,0 +1,277 @@
+//! Age String (AS), Decimal String (DS), and Integer String (IS) values, see Part 5 Section 6.2
+//! Value Representation. Decimal and integer strings can be parsed leniently, tolerating values
+//! which are commonly found in the wild, or strictly following the length and character rules of
+//! their VR.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::core::dcmelement::{self, DicomElement};
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::Result;
+
+/// The maximum length of a single Decimal String value.
+pub const DS_MAX_LENGTH: usize = 16;
+/// The maximum length of a single Integer String value.
+pub const IS_MAX_LENGTH: usize = 12;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("invalid {vr} value {value:?}: {reason}")]
+/// A value could not be parsed as an age, decimal string, or integer string.
+pub struct InvalidNumericString {
+    /// The VR the value was parsed as.
+    pub vr: &'static str,
+    pub value: String,
+    /// A description of why the value is invalid.
+    pub reason: &'static str,
+}
+
+/// How strictly Decimal String and Integer String values are parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum NumericStringMode {
+    /// Surrounding whitespace is ignored and the length of the value is not checked. Integer
+    /// strings may be encoded as decimals as long as they have no fractional part, such as `12.0`.
+    #[default]
+    Lenient,
+    /// Values must follow the length and character rules of their VR. Only leading and trailing
+    /// spaces are allowed.
+    Strict,
+}
+
+/// The unit of an Age String value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AgeUnit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl AgeUnit {
+    /// The character used for this unit in an Age String value.
+    pub fn get_code(self) -> char {
+        match self {
+            AgeUnit::Days => 'D',
+            AgeUnit::Weeks => 'W',
+            AgeUnit::Months => 'M',
+            AgeUnit::Years => 'Y',
+        }
+    }
+
+    fn from_code(code: char) -> Option<AgeUnit> {
+        match code {
+            'D' => Some(AgeUnit::Days),
+            'W' => Some(AgeUnit::Weeks),
+            'M' => Some(AgeUnit::Months),
+            'Y' => Some(AgeUnit::Years),
+            _ => None,
+        }
+    }
+}
+
+/// An Age String (AS) value, `nnnD`, `nnnW`, `nnnM`, or `nnnY`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Age {
+    pub count: u16,
+    pub unit: AgeUnit,
+}
+
+impl FromStr for Age {
+    type Err = InvalidNumericString;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || InvalidNumericString {
+            vr: "AS",
+            value: value.to_owned(),
+            reason: "expected the format nnnD, nnnW, nnnM or nnnY",
+        };
+
+        if value.len() != 4 || !value.is_char_boundary(3) {
+            return Err(invalid());
+        }
+        let (count, unit): (&str, &str) = value.split_at(3);
+        if !count.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let unit: AgeUnit = unit
+            .chars()
+            .next()
+            .and_then(AgeUnit::from_code)
+            .ok_or_else(invalid)?;
+        let count: u16 = count.parse::<u16>().map_err(|_| invalid())?;
+        Ok(Age { count, unit })
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:03}{}", self.count, self.unit.get_code())
+    }
+}
+
+impl TryFrom<&DicomElement> for Age {
+    type Error = ParseError;
+
+    /// Parses the value of an AS element.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        let age: String = Vec::<String>::try_from(value)?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        age.parse::<Age>()
+            .map_err(|e: InvalidNumericString| dcmelement::error(&e.to_string(), value))
+    }
+}
+
+/// Parses a single Decimal String value.
+pub fn parse_decimal_string(
+    value: &str,
+    mode: NumericStringMode,
+) -> std::result::Result<f64, InvalidNumericString> {
+    let invalid = |reason: &'static str| InvalidNumericString {
+        vr: "DS",
+        value: value.to_owned(),
+        reason,
+    };
+
+    let trimmed: &str = trim_numeric_string(value, mode);
+    if trimmed.is_empty() {
+        return Err(invalid("value is empty"));
+    }
+    if mode == NumericStringMode::Strict {
+        if value.len() > DS_MAX_LENGTH {
+            return Err(invalid("exceeds 16 characters"));
+        }
+        if !trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '+' || c == '-' || c == 'E' || c == 'e' || c == '.')
+        {
+            return Err(invalid(
+                "only digits, plus, minus, E and period are allowed",
+            ));
+        }
+    }
+    if !is_decimal_syntax(trimmed) {
+        return Err(invalid("expected a fixed or floating point number"));
+    }
+
+    let parsed: f64 = trimmed
+        .parse::<f64>()
+        .map_err(|_| invalid("expected a fixed or floating point number"))?;
+    if !parsed.is_finite() {
+        return Err(invalid("value is out of range"));
+    }
+    Ok(parsed)
+}
+
+/// Parses a single Integer String value.
+pub fn parse_integer_string(
+    value: &str,
+    mode: NumericStringMode,
+) -> std::result::Result<i32, InvalidNumericString> {
+    let invalid = |reason: &'static str| InvalidNumericString {
+        vr: "IS",
+        value: value.to_owned(),
+        reason,
+    };
+
+    let trimmed: &str = trim_numeric_string(value, mode);
+    if trimmed.is_empty() {
+        return Err(invalid("value is empty"));
+    }
+    if mode == NumericStringMode::Strict {
+        if value.len() > IS_MAX_LENGTH {
+            return Err(invalid("exceeds 12 characters"));
+        }
+        if !trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '+' || c == '-')
+        {
+            return Err(invalid("only digits, plus and minus are allowed"));
+        }
+    }
+
+    if is_integer_syntax(trimmed) {
+        // the syntax is already verified so this only fails if the value does not fit
+        return trimmed
+            .parse::<i32>()
+            .map_err(|_| invalid("value is out of range"));
+    }
+    if mode == NumericStringMode::Strict || !is_decimal_syntax(trimmed) {
+        return Err(invalid("expected an integer"));
+    }
+
+    let parsed: f64 = trimmed
+        .parse::<f64>()
+        .map_err(|_| invalid("expected an integer"))?;
+    if parsed.fract() != 0.0 {
+        return Err(invalid("value has a fractional part"));
+    }
+    if parsed < f64::from(i32::MIN) || parsed > f64::from(i32::MAX) {
+        return Err(invalid("value is out of range"));
+    }
+    Ok(parsed as i32)
+}
+
+/// Parses all values of a DS element. Empty values within a multi-valued element are skipped.
+pub fn parse_decimal_strings(element: &DicomElement, mode: NumericStringMode) -> Result<Vec<f64>> {
+    parse_numeric_strings(element, |value: &str| parse_decimal_string(value, mode))
+}
+
+/// Parses all values of an IS element. Empty values within a multi-valued element are skipped.
+pub fn parse_integer_strings(element: &DicomElement, mode: NumericStringMode) -> Result<Vec<i32>> {
+    parse_numeric_strings(element, |value: &str| parse_integer_string(value, mode))
+}
+
+fn parse_numeric_strings<T, F>(element: &DicomElement, parse: F) -> Result<Vec<T>>
+where
+    F: Fn(&str) -> std::result::Result<T, InvalidNumericString>,
+{
+    Vec::<String>::try_from(element)?
+        .iter()
+        .filter(|value: &&String| !value.trim_matches(' ').is_empty())
+        .map(|value: &String| {
+            parse(value)
+                .map_err(|e: InvalidNumericString| dcmelement::error(&e.to_string(), element))
+        })
+        .collect::<Result<Vec<T>>>()
+}
+
+/// Strict values may only have leading and trailing spaces. Lenient values may additionally be
+/// surrounded by other whitespace or null bytes.
+fn trim_numeric_string(value: &str, mode: NumericStringMode) -> &str {
+    match mode {
+        NumericStringMode::Strict => value.trim_matches(' '),
+        NumericStringMode::Lenient => value.trim_matches(|c: char| c.is_whitespace() || c == '\0'),
+    }
+}
+
+/// Whether the value is an optionally signed sequence of digits.
+fn is_integer_syntax(value: &str) -> bool {
+    let digits: &str = value.strip_prefix(['+', '-']).unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Whether the value is a fixed point or floating point number, `[+-]ddd.ddd[Ee][+-]ddd`, where
+/// either the integer or the fractional digits may be omitted.
+fn is_decimal_syntax(value: &str) -> bool {
+    let (mantissa, exponent): (&str, Option<&str>) = match value.find(['E', 'e']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+    if exponent.map_or(false, |exponent| !is_integer_syntax(exponent)) {
+        return false;
+    }
+
+    let mantissa: &str = mantissa.strip_prefix(['+', '-']).unwrap_or(mantissa);
+    let (integer, fraction): (&str, &str) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None => (mantissa, ""),
+    };
+    (!integer.is_empty() || !fraction.is_empty())
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
@@ -1,24 +1,58 @@
-use std::io::{Read};
+use std::io::{Read, Seek};
 
-use crate::core::charset::{DEFAULT_CHARACTER_SET};
+use crate::core::charset::{UnsupportedCharsetPolicy, DEFAULT_CHARACTER_SET};
 use crate::core::parser::ds::dataset::Dataset;
 use super::parser::ParseState;
-use super::parser::Parser;
+use super::parser::{self, Parser};
+use super::push::PushParser;
+#[cfg(feature = "async")]
+use super::stream::AsyncParser;
+use super::slice::SliceParser;
+use crate::core::dcmobject::DuplicateTagPolicy;
+use crate::core::dcmsqelem::SequencePath;
+use crate::core::filter::ElementFilter;
 use crate::core::tagstop::TagStop;
 use crate::defn::constants::lookup::MINIMAL_DICOM_DICTIONARY;
 use crate::defn::constants::{ts};
 use crate::defn::dcmdict::DicomDictionary;
 
 /// A builder for constructing `Parser` with common default states.
+#[derive(Clone)]
 pub struct ParserBuilder<'dict> {
     /// Initial parse state. Default is `ParseState::DetectState`.
     state: Option<ParseState>,
     /// When to stop parsing the dataset. Default is `TagStop::EndOfDataset`.
     tagstop: Option<TagStop>,
+    /// Which elements are skipped over rather than returned. Default is `None`, all elements are
+    /// returned.
+    filter: Option<ElementFilter>,
     /// The `DicomDictionary` to be used when parsing elements. Default is `MinimalDicomDictionary`.
     dictionary: &'dict dyn DicomDictionary,
     /// The dataset will be wrapped in a `BufReader`, this lets the buffere size be set.
     buffsize: usize,
+    /// What to do when the Specific Character Set is not supported. Default is
+    /// `UnsupportedCharsetPolicy::FallbackToDefault`.
+    unsupported_charset: UnsupportedCharsetPolicy,
+    /// What `parse_into_object` does when a tag appears more than once within a dataset or item.
+    /// Default is `DuplicateTagPolicy::KeepLast`.
+    duplicate_tags: DuplicateTagPolicy,
+    /// Values longer than this many bytes are skipped over instead of being read into memory.
+    /// Default is `None`, all values are read.
+    bulk_data_threshold: Option<u32>,
+    /// Whether the position of each element within the dataset is recorded. Default is `false`.
+    record_offsets: bool,
+    /// Whether the parser recovers from errors in the dataset. Default is `false`.
+    recover: bool,
+    /// Whether the parser rejects deviations from the standard. Default is `false`.
+    strict: bool,
+    /// The longest value the parser reads into memory. Default is `None`, no limit.
+    max_value_length: Option<u32>,
+    /// The most bytes the parser reads from the dataset. Default is `None`, no limit.
+    max_bytes: Option<u64>,
+    /// The deepest an element can be nested within sequences. Default is `None`, no limit.
+    max_depth: Option<usize>,
+    /// The most elements the parser reads from the dataset. Default is `None`, no limit.
+    max_elements: Option<usize>,
 }
 
 impl<'dict> ParserBuilder<'dict> {
@@ -28,6 +62,15 @@ impl<'dict> ParserBuilder<'dict> {
         self
     }
 
+    /// Sets the `ElementFilter` for elements which are skipped over rather than returned. Values of
+    /// filtered elements are read past without being read into memory, except for those needed
+    /// for parsing the rest of the dataset such as the Specific Character Set, which are read but
+    /// still not returned.
+    pub fn filter(mut self, filter: ElementFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Sets the DICOM dictionary. The parser uses `get_ts_by_uid` to identify transfer syntax for
     /// parsing through the stream, and `get_tag_by_number` for resolving VR of parsed elements. The
     /// VR is not strictly necessary for parsing elements however there is potential for sequences
@@ -43,11 +86,101 @@ impl<'dict> ParserBuilder<'dict> {
         self
     }
 
+    /// Sets what the parser should do when the Specific Character Set of the dataset is not
+    /// supported. Unless `UnsupportedCharsetPolicy::Strict` is used, elements whose values are
+    /// decoded with a substitute character set can be identified with
+    /// `DicomElement::is_cs_substituted`.
+    pub fn unsupported_charset(mut self, policy: UnsupportedCharsetPolicy) -> Self {
+        self.unsupported_charset = policy;
+        self
+    }
+
+    /// Sets what `parse_into_object` does when a tag appears more than once within the dataset or
+    /// within an item. Unless `DuplicateTagPolicy::Error` is used, each duplicate is recorded as a
+    /// `Diagnostic`, as is each tag which is out of order.
+    pub fn duplicate_tags(mut self, policy: DuplicateTagPolicy) -> Self {
+        self.duplicate_tags = policy;
+        self
+    }
+
+    /// Sets the length in bytes above which element values are not read into memory. The parser
+    /// skips over these values and returns their elements with a `BulkDataRef` locating the value
+    /// within the dataset, which can be loaded later if the dataset implements `Seek`. Elements
+    /// needed for parsing the rest of the dataset are always read, as are values of datasets using
+    /// a deflated transfer syntax since their locations can't be seeked to. Skipped values are read
+    /// and discarded unless the parser is constructed with `build_seekable`.
+    pub fn bulk_data_threshold(mut self, threshold: u32) -> Self {
+        self.bulk_data_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets whether the parser records the position of each element within the dataset, available
+    /// from `DicomElement::get_offsets`. Positions are relative to where the parser started reading
+    /// the dataset. For datasets using a deflated transfer syntax the positions of elements after
+    /// the File Meta are within the inflated stream and not the source.
+    pub fn record_offsets(mut self, record_offsets: bool) -> Self {
+        self.record_offsets = record_offsets;
+        self
+    }
+
+    /// Sets whether the parser recovers from errors in the dataset rather than stopping at the
+    /// first one. Elements with an unknown explicit VR are read as implicit VR, values truncated by
+    /// the end of the dataset are returned as-is, and when an element's value length overruns the
+    /// item or sequence containing it the parser scans forward for the next plausible element.
+    /// Each problem is recorded as a `Diagnostic`, available from `Parser::get_diagnostics`.
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Sets whether the parser rejects deviations from the standard which it otherwise leniently
+    /// parses. In strict mode the dataset must start with the file preamble and DICOM prefix,
+    /// the File Meta must be encoded as Explicit VR Little Endian, value lengths must be even,
+    /// only sequences, items and encapsulated pixel data may have undefined length, and tags must
+    /// be in ascending order within the dataset and each item. Each of these results in its own
+    /// `ParseError`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the longest value in bytes the parser reads into memory. Elements with a longer value
+    /// result in `ParseError::ValueLengthExceedsLimit` rather than allocating for it. Values skipped
+    /// over as bulk data aren't read into memory and are not limited by this.
+    pub fn max_value_length(mut self, max_value_length: u32) -> Self {
+        self.max_value_length = Some(max_value_length);
+        self
+    }
+
+    /// Sets the most bytes the parser reads from the dataset, which for a deflated dataset is the
+    /// number of bytes after inflating. Once an element's value would extend past this the parser
+    /// results in `ParseError::DatasetSizeExceedsLimit`.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the most sequences and items an element can be nested within. Nesting any deeper
+    /// results in `ParseError::NestingDepthExceedsLimit`. This also bounds the recursion of
+    /// `parse_into_object`.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the most elements the parser reads from the dataset, including items and delimiters.
+    /// Reading any more results in `ParseError::ElementCountExceedsLimit`.
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = Some(max_elements);
+        self
+    }
+
     /// Constructs the parser from this builder.
     pub fn build<DatasetType: Read>(&self, dataset: DatasetType) -> Parser<'dict, DatasetType> {
         Parser {
             dataset: Dataset::new(dataset, self.buffsize),
             tagstop: self.tagstop.clone().unwrap_or(TagStop::EndOfDataset),
+            filter: self.filter.clone(),
             dictionary: self.dictionary,
             state: self
                 .state
@@ -66,10 +199,63 @@ impl<'dict> ParserBuilder<'dict> {
             detected_ts: &ts::ExplicitVRLittleEndian,
             dataset_ts: None,
             cs: DEFAULT_CHARACTER_SET,
-            current_path: Vec::new(),
+            unsupported_charset: self.unsupported_charset,
+            duplicate_tags: self.duplicate_tags,
+            element_offset: 0,
+            bulk_data_threshold: self.bulk_data_threshold,
+            record_offsets: self.record_offsets,
+            skip_bytes: None,
+            recover: self.recover,
+            diagnostics: Vec::new(),
+            error_context: None,
+            strict: self.strict,
+            previous_tags: Vec::new(),
+            max_value_length: self.max_value_length,
+            max_bytes: self.max_bytes,
+            max_depth: self.max_depth,
+            max_elements: self.max_elements,
+            elements_read: 0,
+            root_elements_read: 0,
+            tagstop_reached: false,
+            current_path: SequencePath::new(),
             iterator_ended: false,
         }
     }
+
+    /// Constructs a parser over a dataset which implements `Seek`. Values which aren't read into
+    /// memory, those above the bulk data threshold and those of filtered elements, are seeked past
+    /// rather than read and discarded. Values within a deflated dataset are still read.
+    pub fn build_seekable<DatasetType: Read + Seek>(
+        &self,
+        dataset: DatasetType,
+    ) -> Parser<'dict, DatasetType> {
+        let mut parser: Parser<'dict, DatasetType> = self.build(dataset);
+        parser.skip_bytes = Some(parser::seek_bytes::<DatasetType>);
+        parser
+    }
+
+    /// Constructs a parser over a dataset which is already in memory, such as a memory-mapped
+    /// file. Element values are returned as slices of the dataset rather than being copied, see
+    /// `SliceParser`. The buffer size and bulk data threshold of this builder are not used.
+    pub fn build_slice<'buf>(&self, dataset: &'buf [u8]) -> SliceParser<'dict, 'buf> {
+        SliceParser::new(self, dataset)
+    }
+
+    /// Constructs a parser which is fed the dataset in chunks rather than reading it, see
+    /// `PushParser`. The buffer size of this builder is not used.
+    pub fn build_push(&self) -> PushParser<'dict> {
+        PushParser::new(self)
+    }
+
+    /// Constructs a parser which reads the dataset asynchronously, see `AsyncParser`. The dataset
+    /// is read in chunks of the buffer size.
+    #[cfg(feature = "async")]
+    pub fn build_async<DatasetType: tokio::io::AsyncRead + Unpin>(
+        &self,
+        dataset: DatasetType,
+    ) -> AsyncParser<'dict, DatasetType> {
+        AsyncParser::new(self.build_push(), dataset, self.buffsize)
+    }
 }
 
 impl<'dict> Default for ParserBuilder<'dict> {
@@ -77,9 +263,20 @@ impl<'dict> Default for ParserBuilder<'dict> {
         ParserBuilder {
             state: None,
             tagstop: None,
+            filter: None,
             dictionary: &MINIMAL_DICOM_DICTIONARY,
             // BufReader's current default buffer size is 8k
             buffsize: 8 * 1024,
+            unsupported_charset: UnsupportedCharsetPolicy::default(),
+            duplicate_tags: DuplicateTagPolicy::default(),
+            bulk_data_threshold: None,
+            record_offsets: false,
+            recover: false,
+            strict: false,
+            max_value_length: None,
+            max_bytes: None,
+            max_depth: None,
+            max_elements: None,
         }
     }
 }
\ No newline at end of file
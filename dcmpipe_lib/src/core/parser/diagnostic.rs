@@ -0,0 +1,86 @@
+//! Problems found in a dataset by a parser which recovers from errors, see
+//! `ParserBuilder::recover`, or by `parse_into_object`.
+
+use std::fmt::{Display, Formatter};
+
+use crate::defn::tag::{Tag, TagPath};
+
+/// A problem found while parsing a dataset which the parser recovered from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    /// The byte position of the start of the element the problem was found in, relative to where
+    /// the parser started reading the dataset.
+    pub offset: u64,
+    /// The path of the element the problem was found in.
+    pub tagpath: TagPath,
+    /// What the problem was and how the parser recovered from it.
+    pub kind: DiagnosticKind,
+}
+
+/// The problems a parser can recover from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DiagnosticKind {
+    /// The explicit VR was not recognized. The element was read as implicit VR, with the VR looked
+    /// up from the dictionary.
+    UnknownExplicitVR(u16),
+
+    /// The value length extends past the end of the item or sequence containing the element. The
+    /// value was not read and the parser resynchronized on the next element.
+    ValueLengthOverrun { vl: u32, remaining: u64 },
+
+    /// The dataset ended before the full value length was read. The element is returned with the
+    /// value which was read.
+    TruncatedValue { vl: u32, read: u64 },
+
+    /// The value of an element which affects parsing the rest of the dataset could not be read.
+    /// Parsing continued as though the element were not present.
+    InvalidValue(String),
+
+    /// Bytes were skipped over to find the start of the next element.
+    SkippedBytes(u64),
+
+    /// The tag was already parsed within the same dataset or item by `parse_into_object`. Which of
+    /// the elements is kept depends on the `DuplicateTagPolicy`.
+    DuplicateTag,
+
+    /// The tag is lower than the tag preceding it within the same dataset or item, found by
+    /// `parse_into_object`. The order the elements were parsed in is kept by the object.
+    TagOutOfOrder { previous: u32 },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} @ {}: {}", self.tagpath.display(), self.offset, self.kind)
+    }
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticKind::UnknownExplicitVR(code) => {
+                write!(f, "unknown explicit vr {:#06X}, read as implicit vr", code)
+            }
+            DiagnosticKind::ValueLengthOverrun { vl, remaining } => write!(
+                f,
+                "value length {} overruns its sequence/item by {} bytes",
+                vl,
+                u64::from(*vl) - remaining
+            ),
+            DiagnosticKind::TruncatedValue { vl, read } => write!(
+                f,
+                "dataset ended after {} bytes of value length {}",
+                read, vl
+            ),
+            DiagnosticKind::InvalidValue(message) => write!(f, "invalid value: {}", message),
+            DiagnosticKind::SkippedBytes(skipped) => {
+                write!(f, "skipped {} bytes to the next element", skipped)
+            }
+            DiagnosticKind::DuplicateTag => write!(f, "duplicate tag"),
+            DiagnosticKind::TagOutOfOrder { previous } => write!(
+                f,
+                "tag out of order, follows {}",
+                Tag::format_tag_to_display(*previous)
+            ),
+        }
+    }
+}
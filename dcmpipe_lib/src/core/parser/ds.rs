@@ -1,12 +1,47 @@
+/// The number of bytes kept in `ReadHistory`.
+pub(crate) const READ_HISTORY_LENGTH: usize = 16;
+
+/// The bytes most recently read from a dataset, for showing where in the dataset an error occurred.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ReadHistory {
+    /// The bytes read, with the most recent last.
+    bytes: [u8; READ_HISTORY_LENGTH],
+    len: usize,
+}
+
+impl ReadHistory {
+    fn record(&mut self, read: &[u8]) {
+        if read.len() >= READ_HISTORY_LENGTH {
+            self.bytes.copy_from_slice(&read[read.len() - READ_HISTORY_LENGTH..]);
+            self.len = READ_HISTORY_LENGTH;
+        } else {
+            self.bytes.copy_within(read.len().., 0);
+            self.bytes[READ_HISTORY_LENGTH - read.len()..].copy_from_slice(read);
+            self.len = (self.len + read.len()).min(READ_HISTORY_LENGTH);
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[READ_HISTORY_LENGTH - self.len..]
+    }
+}
 
 #[cfg(feature = "deflate")]
 pub(crate) mod dataset {
     use libflate::deflate::Decoder;
-    use std::io::{BufReader, Read, Result};
+    use std::convert::TryFrom;
+    use std::io::{BufRead, BufReader, Read, Result};
+
+    use super::ReadHistory;
+    use crate::core::parser::parser::SkipBytes;
 
     pub(crate) struct Dataset<DatasetType: Read> {
         deflated: Decoder<BufReader<DatasetType>>,
         read_deflated: bool,
+        /// Whether the dataset is inflated before being read here, in which case it's never read
+        /// through the decoder.
+        pre_inflated: bool,
+        history: ReadHistory,
     }
 
     impl<DatasetType: Read> Dataset<DatasetType> {
@@ -14,44 +49,141 @@ pub(crate) mod dataset {
             Dataset {
                 deflated: Decoder::new(BufReader::with_capacity(buffsize, dataset)),
                 read_deflated: false,
+                pre_inflated: false,
+                history: ReadHistory::default(),
             }
         }
 
         pub fn set_read_deflated(&mut self, read_deflated: bool) {
             self.read_deflated = read_deflated;
         }
+
+        pub fn set_pre_inflated(&mut self, pre_inflated: bool) {
+            self.pre_inflated = pre_inflated;
+        }
+
+        pub fn get_mut(&mut self) -> &mut DatasetType {
+            self.deflated.as_inner_mut().get_mut()
+        }
+
+        /// Whether bytes can be skipped over without reading them, which isn't possible while the
+        /// dataset is being inflated.
+        pub fn can_skip(&self) -> bool {
+            !self.read_deflated || self.pre_inflated
+        }
+
+        /// Advances past `len` bytes, consuming any which are buffered and skipping the rest of the
+        /// underlying dataset using `skip_bytes`, returning the number of bytes skipped. The bytes
+        /// are not read so they aren't part of the history. This must only be used if `can_skip`.
+        pub fn skip(&mut self, len: u64, skip_bytes: SkipBytes<DatasetType>) -> Result<u64> {
+            self.history = ReadHistory::default();
+            let reader: &mut BufReader<DatasetType> = self.deflated.as_inner_mut();
+            let buffered: usize = usize::try_from(len)
+                .map_or(reader.buffer().len(), |len| len.min(reader.buffer().len()));
+            reader.consume(buffered);
+            let skipped: u64 = skip_bytes(reader.get_mut(), len - buffered as u64)?;
+            Ok(buffered as u64 + skipped)
+        }
+
+        pub fn get_history(&self) -> ReadHistory {
+            self.history
+        }
+
+        pub fn set_history(&mut self, history: ReadHistory) {
+            self.history = history;
+        }
+
+        /// Returns up to `len` of the next bytes without consuming them. Only bytes which are
+        /// already buffered are returned when reading through the decoder.
+        pub fn peek(&mut self, len: usize) -> Vec<u8> {
+            if self.read_deflated && !self.pre_inflated {
+                return Vec::new();
+            }
+            match self.deflated.as_inner_mut().fill_buf() {
+                Ok(buf) => buf[..len.min(buf.len())].to_vec(),
+                Err(_) => Vec::new(),
+            }
+        }
     }
 
     impl<DatasetType: Read> Read for Dataset<DatasetType> {
         fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-            if self.read_deflated {
-                self.deflated.read(buf)
+            let read: usize = if self.read_deflated && !self.pre_inflated {
+                self.deflated.read(buf)?
             } else {
-                self.deflated.as_inner_mut().read(buf)
-            }
+                self.deflated.as_inner_mut().read(buf)?
+            };
+            self.history.record(&buf[..read]);
+            Ok(read)
         }
     }
 }
 
 #[cfg(not(feature = "deflate"))]
 pub(crate) mod dataset {
-    use std::io::{BufReader, Read, Result};
+    use std::convert::TryFrom;
+    use std::io::{BufRead, BufReader, Read, Result};
+
+    use super::ReadHistory;
+    use crate::core::parser::parser::SkipBytes;
 
     pub(crate) struct Dataset<DatasetType: Read> {
         dataset: BufReader<DatasetType>,
+        history: ReadHistory,
     }
 
     impl<DatasetType: Read> Dataset<DatasetType> {
         pub fn new(dataset: DatasetType, buffsize: usize) -> Dataset<DatasetType> {
             Dataset {
                 dataset: BufReader::with_capacity(buffsize, dataset),
+                history: ReadHistory::default(),
+            }
+        }
+
+        pub fn get_mut(&mut self) -> &mut DatasetType {
+            self.dataset.get_mut()
+        }
+
+        /// Whether bytes can be skipped over without reading them.
+        pub fn can_skip(&self) -> bool {
+            true
+        }
+
+        /// Advances past `len` bytes, consuming any which are buffered and skipping the rest of the
+        /// underlying dataset using `skip_bytes`, returning the number of bytes skipped. The bytes
+        /// are not read so they aren't part of the history.
+        pub fn skip(&mut self, len: u64, skip_bytes: SkipBytes<DatasetType>) -> Result<u64> {
+            self.history = ReadHistory::default();
+            let buffered: usize = usize::try_from(len).map_or(self.dataset.buffer().len(), |len| {
+                len.min(self.dataset.buffer().len())
+            });
+            self.dataset.consume(buffered);
+            let skipped: u64 = skip_bytes(self.dataset.get_mut(), len - buffered as u64)?;
+            Ok(buffered as u64 + skipped)
+        }
+
+        pub fn get_history(&self) -> ReadHistory {
+            self.history
+        }
+
+        pub fn set_history(&mut self, history: ReadHistory) {
+            self.history = history;
+        }
+
+        /// Returns up to `len` of the next bytes without consuming them.
+        pub fn peek(&mut self, len: usize) -> Vec<u8> {
+            match self.dataset.fill_buf() {
+                Ok(buf) => buf[..len.min(buf.len())].to_vec(),
+                Err(_) => Vec::new(),
             }
         }
     }
 
     impl<DatasetType: Read> Read for Dataset<DatasetType> {
         fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-            self.dataset.read(buf)
+            let read: usize = self.dataset.read(buf)?;
+            self.history.record(&buf[..read]);
+            Ok(read)
         }
     }
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
+use std::fmt::{Display, Formatter};
+
 use thiserror::Error;
 use crate::defn::vr::{VRRef};
-use crate::defn::tag::Tag;
+use crate::defn::tag::{Tag, TagPath};
+use crate::defn::ts::TSRef;
 use super::parser::DICOM_PREFIX_LENGTH;
 
 
@@ -36,6 +39,71 @@ pub enum ParseError {
         detail: String,
     },
 
+    #[error("value length overruns its sequence/item: {} vl: {vl}, remaining: {remaining}", Tag::format_tag_to_display(* tag))]
+    /// The value length of an element extends past the end of the item or sequence containing it.
+    /// This is only checked when the parser recovers from errors, see `ParserBuilder::recover`.
+    ValueLengthOverrun { tag: u32, vl: u32, remaining: u64 },
+
+    #[error("value length exceeds the limit: {} vl: {vl}, max: {max}", Tag::format_tag_to_display(* tag))]
+    /// The value length of an element is longer than the parser is limited to reading into
+    /// memory, see `ParserBuilder::max_value_length`.
+    ValueLengthExceedsLimit { tag: u32, vl: u32, max: u32 },
+
+    #[error("dataset exceeds the size limit of {max} bytes")]
+    /// The dataset is larger than the parser is limited to reading, see
+    /// `ParserBuilder::max_bytes`.
+    DatasetSizeExceedsLimit { max: u64 },
+
+    #[error("sequences are nested deeper than the limit of {max}")]
+    /// Sequences and items are nested deeper than the parser is limited to, see
+    /// `ParserBuilder::max_depth`.
+    NestingDepthExceedsLimit { max: usize },
+
+    #[error("dataset has more elements than the limit of {max}")]
+    /// The dataset has more elements than the parser is limited to reading, see
+    /// `ParserBuilder::max_elements`.
+    ElementCountExceedsLimit { max: usize },
+
+    #[error("dataset is missing the DICM prefix")]
+    /// The dataset does not start with the file preamble and DICOM prefix. This is only checked
+    /// in strict mode, see `ParserBuilder::strict`, and when converting with `deflate_file`.
+    MissingDICOMPrefix,
+
+    #[error("file meta is not encoded as explicit vr little endian: {}", ts.uid.ident)]
+    /// The File Meta elements are encoded with a transfer syntax other than Explicit VR Little
+    /// Endian. This is only checked in strict mode, see `ParserBuilder::strict`.
+    FileMetaNotExplicitVRLittleEndian { ts: TSRef },
+
+    #[error("unsupported transfer syntax: {}", ts.uid.ident)]
+    /// The dataset is encoded with a transfer syntax the parser is unable to read, such as a
+    /// deflated transfer syntax when the `deflate` feature is not enabled.
+    UnsupportedTransferSyntax { ts: TSRef },
+
+    #[error("odd value length: {} vl: {vl}", Tag::format_tag_to_display(* tag))]
+    /// The value length of an element is not even. This is only checked in strict mode, see
+    /// `ParserBuilder::strict`.
+    OddValueLength { tag: u32, vl: u32 },
+
+    #[error("undefined value length: {} vr: {}", Tag::format_tag_to_display(* tag), vr.ident)]
+    /// An element other than a sequence, item, or encapsulated pixel data has undefined length.
+    /// This is only checked in strict mode, see `ParserBuilder::strict`.
+    UnexpectedUndefinedLength { tag: u32, vr: VRRef },
+
+    #[error("tag not in ascending order: {} follows {}", Tag::format_tag_to_display(* tag), Tag::format_tag_to_display(* previous))]
+    /// An element's tag is not greater than the tag of the element preceding it within the same
+    /// dataset or item. This is only checked in strict mode, see `ParserBuilder::strict`.
+    TagOutOfOrder { tag: u32, previous: u32 },
+
+    #[error("duplicate tag: {}", tagpath.display())]
+    /// The same tag was parsed more than once within a dataset or item by `parse_into_object`,
+    /// with `DuplicateTagPolicy::Error`.
+    DuplicateTag { tagpath: TagPath },
+
+    #[error("unsupported specific character set: {values:?}")]
+    /// The Specific Character Set is not supported and the parser is configured with
+    /// `UnsupportedCharsetPolicy::Strict`.
+    UnsupportedCharacterSet { values: Vec<String> },
+
     #[error("error parsing element value: {} [{vr:?}], {message} {bytes:?}", Tag::format_tag_to_display(* tag))]
     ValueParseError {
         message: String,
@@ -43,4 +111,50 @@ pub enum ParseError {
         vr: VRRef,
         bytes: Vec<u8>,
     },
-}
\ No newline at end of file
+}
+
+/// Where in a dataset an error occurred, see `Parser::get_error_context`.
+#[derive(Debug, Clone)]
+pub struct ErrorContext {
+    /// The number of bytes parsed from the dataset when the error occurred.
+    pub offset: u64,
+    /// The path of the element being parsed, if the error occurred within an element.
+    pub tagpath: Option<TagPath>,
+    /// The transfer syntax elements were being parsed with.
+    pub ts: TSRef,
+    /// The bytes last read from the dataset prior to the error.
+    pub bytes_before: Vec<u8>,
+    /// The bytes of the dataset following the error, if they could be read without consuming them.
+    pub bytes_after: Vec<u8>,
+}
+
+impl ErrorContext {
+    /// Renders the bytes around where the error occurred as hex, with `|` marking the point of
+    /// failure.
+    pub fn hex_excerpt(&self) -> String {
+        let to_hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        format!(
+            "{} | {}",
+            to_hex(&self.bytes_before),
+            to_hex(&self.bytes_after)
+        )
+        .trim()
+        .to_owned()
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at byte {}", self.offset)?;
+        if let Some(tagpath) = &self.tagpath {
+            write!(f, " in {}", tagpath.display())?;
+        }
+        write!(f, " with {}", self.ts.uid.ident)
+    }
+}
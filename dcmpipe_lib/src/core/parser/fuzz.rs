@@ -0,0 +1,71 @@
+//! An entry point for fuzzing the parser with arbitrary input, for example from a `cargo fuzz`
+//! target:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| dcmpipe_lib::core::parser::fuzz::fuzz_parse(data));
+//! ```
+//!
+//! Parsing is limited in the same way it should be for datasets from untrusted sources, so input
+//! which claims huge values or deeply nested sequences results in errors rather than exhausting
+//! memory or the stack.
+
+use crate::core::dcmobject::{DicomNode, DicomObject};
+use crate::core::parser::builder::ParserBuilder;
+use crate::core::parser::push::{PushEvent, PushParser};
+use crate::core::parser::util::parse_into_object;
+
+/// The longest value read into memory while fuzzing.
+pub const MAX_VALUE_LENGTH: u32 = 16 * 1024 * 1024;
+/// The most bytes read from a dataset while fuzzing, which limits how far deflated data inflates.
+pub const MAX_BYTES: u64 = 64 * 1024 * 1024;
+/// The most sequences and items an element can be nested within while fuzzing.
+pub const MAX_DEPTH: usize = 64;
+/// The most elements read from a dataset while fuzzing.
+pub const MAX_ELEMENTS: usize = 100_000;
+
+/// Parses the input with each of the parsers, with and without recovering from errors, and
+/// parses the value of each element. Errors are expected and ignored, this should only ever fail
+/// by panicking.
+pub fn fuzz_parse(data: &[u8]) {
+    let builder: ParserBuilder<'_> = ParserBuilder::default()
+        .max_value_length(MAX_VALUE_LENGTH)
+        .max_bytes(MAX_BYTES)
+        .max_depth(MAX_DEPTH)
+        .max_elements(MAX_ELEMENTS);
+
+    for builder in &[builder.clone(), builder.clone().recover(true)] {
+        for element in builder.build(data).flatten() {
+            let _ = element.parse_value();
+        }
+
+        if let Ok(Some(root)) = parse_into_object(&mut builder.build(data)) {
+            for (_tag, dcmobj) in root.iter_child_nodes() {
+                parse_object_values(dcmobj);
+            }
+        }
+    }
+
+    for element in builder.build_slice(data).flatten() {
+        let _ = element.into_element().parse_value();
+    }
+
+    let mut parser: PushParser<'_> = builder.build_push();
+    parser.feed(data);
+    parser.finish();
+    while let Ok(PushEvent::Element(element)) = parser.next_event() {
+        let _ = element.parse_value();
+    }
+}
+
+/// Parses the values of the object and its children.
+fn parse_object_values(dcmobj: &DicomObject) {
+    let _ = dcmobj.get_element().parse_value();
+    for (_tag, child) in dcmobj.iter_child_nodes() {
+        parse_object_values(child);
+    }
+    for index in 1..=dcmobj.get_item_count() {
+        if let Some(item) = dcmobj.get_item_by_index(index) {
+            parse_object_values(item);
+        }
+    }
+}
@@ -20,7 +20,7 @@ impl<'dict, DatasetType: Read> Iterator for Parser<'dict, DatasetType> {
             }
             Err(e) => {
                 self.iterator_ended = true;
-                Some(Err(e))
+                Some(Err(self.locate_error(e)))
             }
             Ok(None) => {
                 self.iterator_ended = true;
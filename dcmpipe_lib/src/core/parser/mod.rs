@@ -4,12 +4,23 @@ pub use parser::ParseState;
 pub use parser::DICOM_PREFIX;
 pub use parser::DICOM_PREFIX_LENGTH;
 pub use parser::FILE_PREAMBLE_LENGTH;
-pub use error::ParseError;
+pub use error::{ErrorContext, ParseError};
 pub use builder::ParserBuilder;
+pub use diagnostic::{Diagnostic, DiagnosticKind};
+pub use push::{PushEvent, PushParser};
+#[cfg(feature = "async")]
+pub use stream::AsyncParser;
+pub use slice::SliceParser;
 
 pub mod builder;
+pub mod diagnostic;
 pub(crate) mod ds;
 pub mod error;
+pub mod fuzz;
 pub mod iter;
 pub mod parser;
+pub mod push;
+pub mod slice;
+#[cfg(feature = "async")]
+pub mod stream;
 pub mod util;
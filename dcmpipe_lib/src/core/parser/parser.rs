@@ -1,16 +1,23 @@
+use std::cmp::Ordering;
 use std::convert::TryFrom;
-use std::io::{Cursor, ErrorKind, Read};
+use std::io::{self, Cursor, ErrorKind, Read, Seek, SeekFrom};
 
-use crate::core::charset::{self, CSRef};
+use crate::core::bulkdata::BulkDataRef;
+use crate::core::charset::{self, CSRef, UnsupportedCharsetPolicy};
 use crate::core::dcmelement::DicomElement;
-use crate::core::parser::ds::dataset::Dataset;
+use crate::core::dcmobject::DuplicateTagPolicy;
+use crate::core::dcmsqelem::{SequenceElement, SequencePath};
+use crate::core::filter::ElementFilter;
 use crate::core::parser;
-use crate::core::parser::error::{ParseError};
-use crate::core::dcmsqelem::SequenceElement;
+use crate::core::parser::diagnostic::{Diagnostic, DiagnosticKind};
+use crate::core::parser::ds::dataset::Dataset;
+use crate::core::parser::ds::{ReadHistory, READ_HISTORY_LENGTH};
+use crate::core::parser::error::{ErrorContext, ParseError};
+use crate::core::seekable::ElementOffsets;
 use crate::core::tagstop::TagStop;
 use crate::defn::constants::{tags, ts};
 use crate::defn::dcmdict::DicomDictionary;
-use crate::defn::tag::Tag;
+use crate::defn::tag::{Tag, TagPath};
 use crate::defn::ts::TSRef;
 use crate::defn::vl::ValueLength;
 use crate::defn::vr::{self, VRRef};
@@ -21,11 +28,26 @@ const MAX_VALUE_LENGTH_IN_DETECT: u32 = 100;
 
 pub static DICOM_PREFIX: &[u8; DICOM_PREFIX_LENGTH] = b"DICM";
 
-
-
 /// The `Result` type of the parser
 pub type Result<T> = core::result::Result<T, ParseError>;
 
+/// A function advancing a dataset past a number of bytes without reading them, returning the
+/// number of bytes skipped.
+pub(crate) type SkipBytes<DatasetType> = fn(&mut DatasetType, u64) -> io::Result<u64>;
+
+/// Skips values by seeking past them, for datasets which implement `Seek`. Seeking isn't limited
+/// by the end of the dataset, so this stops at the end in order to report how many bytes remained.
+pub(crate) fn seek_bytes<DatasetType: Seek>(
+    dataset: &mut DatasetType,
+    len: u64,
+) -> io::Result<u64> {
+    let start: u64 = dataset.stream_position()?;
+    let end: u64 = dataset.seek(SeekFrom::End(0))?;
+    let target: u64 = start.saturating_add(len).min(end.max(start));
+    dataset.seek(SeekFrom::Start(target))?;
+    Ok(target - start)
+}
+
 /// The different parsing behaviors of the dataset.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum ParseState {
@@ -48,7 +70,33 @@ pub enum ParseState {
     Element,
 }
 
-
+/// The state of a `Parser` at a point within the dataset, excluding the dataset itself. Restoring a
+/// checkpoint lets parsing resume from that point as long as the dataset is also positioned back
+/// to where it was when the checkpoint was taken.
+#[derive(Clone)]
+pub(crate) struct ParserCheckpoint {
+    state: ParseState,
+    bytes_read: u64,
+    file_preamble: Option<[u8; FILE_PREAMBLE_LENGTH]>,
+    dicom_prefix: Option<[u8; DICOM_PREFIX_LENGTH]>,
+    fmi_start: u64,
+    fmi_grouplength: u32,
+    tag_last_read: u32,
+    partial_tag: Option<u32>,
+    partial_vr: Option<VRRef>,
+    partial_vl: Option<ValueLength>,
+    detected_ts: TSRef,
+    dataset_ts: Option<TSRef>,
+    cs: CSRef,
+    current_path: SequencePath,
+    iterator_ended: bool,
+    diagnostics_len: usize,
+    history: ReadHistory,
+    elements_read: usize,
+    previous_tags: Vec<Option<u32>>,
+    root_elements_read: usize,
+    tagstop_reached: bool,
+}
 
 /// Provides an iterator that parses through a dicom dataset returning dicom elements.
 pub struct Parser<'dict, DatasetType: Read> {
@@ -62,6 +110,9 @@ pub struct Parser<'dict, DatasetType: Read> {
     /// can be used for only partially parsing through a dataset.
     pub(crate) tagstop: TagStop,
 
+    /// Elements which are skipped over rather than returned, see `ParserBuilder::filter`.
+    pub(crate) filter: Option<ElementFilter>,
+
     /// The DICOM dictionary. Parsing uses `get_ts_by_uid` to identify transfer syntax for parsing
     /// through the stream, and `get_tag_by_number` for resolving VR of parsed elements. The VR is
     /// not strictly necessary for parsing elements however there is potential for sequences to not
@@ -141,12 +192,76 @@ pub struct Parser<'dict, DatasetType: Read> {
     /// element.
     pub(crate) cs: CSRef,
 
+    /// What to do when the specific character set is not supported. Unless this is `Strict` a
+    /// substitute character set is used in its place.
+    pub(crate) unsupported_charset: UnsupportedCharsetPolicy,
+
+    /// Element values longer than this are skipped over instead of being read into memory, and
+    /// the element is returned with a `BulkDataRef` locating its value.
+    pub(crate) bulk_data_threshold: Option<u32>,
+
+    /// Whether the position of each element within the dataset is recorded on the element.
+    pub(crate) record_offsets: bool,
+
+    /// Advances the dataset past the given number of bytes without reading them, returning how
+    /// many bytes were skipped. When set this is used to skip values rather than reading and
+    /// discarding them unless the dataset is being inflated, see `SliceParser` and
+    /// `ParserBuilder::build_seekable`.
+    pub(crate) skip_bytes: Option<SkipBytes<DatasetType>>,
+
+    /// Whether errors in the dataset are recovered from rather than ending parsing, see
+    /// `ParserBuilder::recover`.
+    pub(crate) recover: bool,
+
+    /// The problems found in the dataset which were recovered from.
+    pub(crate) diagnostics: Vec<Diagnostic>,
+
+    /// Where in the dataset the last error returned occurred, see `get_error_context`.
+    pub(crate) error_context: Option<ErrorContext>,
+
+    /// What `parse_into_object` does with duplicate tags, see `ParserBuilder::duplicate_tags`.
+    pub(crate) duplicate_tags: DuplicateTagPolicy,
+
+    /// The byte position of the start of the element last read, for `parse_into_object` to
+    /// record diagnostics at.
+    pub(crate) element_offset: u64,
+
+    /// Whether deviations from the standard are rejected, see `ParserBuilder::strict`.
+    pub(crate) strict: bool,
+
+    /// In strict mode, the tag of the last element read at each depth of `self.current_path`,
+    /// for checking tags are in ascending order. Items and delimiters are not recorded.
+    pub(crate) previous_tags: Vec<Option<u32>>,
+
+    /// The longest value read into memory, see `ParserBuilder::max_value_length`.
+    pub(crate) max_value_length: Option<u32>,
+
+    /// The most bytes read from the dataset, see `ParserBuilder::max_bytes`.
+    pub(crate) max_bytes: Option<u64>,
+
+    /// The most sequences and items an element can be nested within, see
+    /// `ParserBuilder::max_depth`.
+    pub(crate) max_depth: Option<usize>,
+
+    /// The most elements read from the dataset, see `ParserBuilder::max_elements`.
+    pub(crate) max_elements: Option<usize>,
+
+    /// The number of elements read from the dataset, checked against `self.max_elements`.
+    pub(crate) elements_read: usize,
+
+    /// The number of elements read at the root of the dataset, for `TagStop::AfterRootElements`.
+    pub(crate) root_elements_read: usize,
+
+    /// Whether an element was returned which `self.tagstop` indicates should be the last.
+    pub(crate) tagstop_reached: bool,
+
     /// The current sequence stack. Whenever an SQ element is parsed a new `SequenceElement` is
     /// appened to this stack. The last element is popped of when the sequence ends (via byte
     /// position or `SequenceDelimitationItem`). This also tracks the current `Item` within a
     /// sequence. Whenever an `Item` element is read the last element in this list has its item
-    /// count initialized/incremented. Every element parsed from the dataset clones this stack.
-    pub(crate) current_path: Vec<SequenceElement>,
+    /// count initialized/incremented. Every element parsed from the dataset clones this stack,
+    /// which shares its sequence elements with the clone rather than copying them.
+    pub(crate) current_path: SequencePath,
 
     /// When the `next()` returns an `Error` or `None` future calls to `next()` should not attempt
     /// to read from the dataset. This is used to track when the iterator should be considered fully
@@ -181,11 +296,165 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         self.cs
     }
 
+    /// Get what the parser does when the specific character set is not supported.
+    pub fn get_unsupported_charset(&self) -> UnsupportedCharsetPolicy {
+        self.unsupported_charset
+    }
+
+    /// Get the length in bytes above which element values are not read into memory.
+    pub fn get_bulk_data_threshold(&self) -> Option<u32> {
+        self.bulk_data_threshold
+    }
+
+    /// Get whether the position of each element within the dataset is recorded.
+    pub fn is_recording_offsets(&self) -> bool {
+        self.record_offsets
+    }
+
+    /// Get whether errors in the dataset are recovered from rather than ending parsing.
+    pub fn is_recovering(&self) -> bool {
+        self.recover
+    }
+
+    /// Get the problems found in the dataset so far which were recovered from. This is always
+    /// empty unless the parser is recovering from errors, or the parser is used with
+    /// `parse_into_object` which records duplicate and out of order tags.
+    pub fn get_diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the problems found in the dataset so far.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Get what `parse_into_object` does when a tag appears more than once.
+    pub fn get_duplicate_tag_policy(&self) -> DuplicateTagPolicy {
+        self.duplicate_tags
+    }
+
+    /// Get whether deviations from the standard are rejected.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Get the longest value read into memory.
+    pub fn get_max_value_length(&self) -> Option<u32> {
+        self.max_value_length
+    }
+
+    /// Get the most bytes read from the dataset.
+    pub fn get_max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    /// Get the most sequences and items an element can be nested within.
+    pub fn get_max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Get the most elements read from the dataset.
+    pub fn get_max_elements(&self) -> Option<usize> {
+        self.max_elements
+    }
+
     /// Get the dictionary used during parsing.
     pub fn get_dictionary(&self) -> &'dict dyn DicomDictionary {
         self.dictionary
     }
 
+    /// The underlying dataset being parsed.
+    pub(crate) fn get_dataset_mut(&mut self) -> &mut DatasetType {
+        self.dataset.get_mut()
+    }
+
+    /// Captures the current state of parsing, see `ParserCheckpoint`.
+    pub(crate) fn checkpoint(&self) -> ParserCheckpoint {
+        ParserCheckpoint {
+            state: self.state,
+            bytes_read: self.bytes_read,
+            file_preamble: self.file_preamble,
+            dicom_prefix: self.dicom_prefix,
+            fmi_start: self.fmi_start,
+            fmi_grouplength: self.fmi_grouplength,
+            tag_last_read: self.tag_last_read,
+            partial_tag: self.partial_tag,
+            partial_vr: self.partial_vr,
+            partial_vl: self.partial_vl,
+            detected_ts: self.detected_ts,
+            dataset_ts: self.dataset_ts,
+            cs: self.cs,
+            current_path: self.current_path.clone(),
+            iterator_ended: self.iterator_ended,
+            diagnostics_len: self.diagnostics.len(),
+            history: self.dataset.get_history(),
+            elements_read: self.elements_read,
+            previous_tags: self.previous_tags.clone(),
+            root_elements_read: self.root_elements_read,
+            tagstop_reached: self.tagstop_reached,
+        }
+    }
+
+    /// Restores the state of parsing to a checkpoint taken earlier.
+    pub(crate) fn restore(&mut self, checkpoint: ParserCheckpoint) {
+        self.state = checkpoint.state;
+        self.bytes_read = checkpoint.bytes_read;
+        self.file_preamble = checkpoint.file_preamble;
+        self.dicom_prefix = checkpoint.dicom_prefix;
+        self.fmi_start = checkpoint.fmi_start;
+        self.fmi_grouplength = checkpoint.fmi_grouplength;
+        self.tag_last_read = checkpoint.tag_last_read;
+        self.partial_tag = checkpoint.partial_tag;
+        self.partial_vr = checkpoint.partial_vr;
+        self.partial_vl = checkpoint.partial_vl;
+        self.detected_ts = checkpoint.detected_ts;
+        self.dataset_ts = checkpoint.dataset_ts;
+        self.cs = checkpoint.cs;
+        self.current_path = checkpoint.current_path;
+        self.iterator_ended = checkpoint.iterator_ended;
+        self.diagnostics.truncate(checkpoint.diagnostics_len);
+        self.dataset.set_history(checkpoint.history);
+        self.elements_read = checkpoint.elements_read;
+        self.previous_tags = checkpoint.previous_tags;
+        self.root_elements_read = checkpoint.root_elements_read;
+        self.tagstop_reached = checkpoint.tagstop_reached;
+    }
+
+    /// Records where in the dataset the error occurred, see `get_error_context`, returning the
+    /// error unchanged.
+    pub(crate) fn locate_error(&mut self, error: ParseError) -> ParseError {
+        if let ParseError::ExpectedEOF = error {
+            return error;
+        }
+
+        let (tagpath, ts): (Option<TagPath>, TSRef) = match self.state {
+            ParseState::Element => (
+                Some(self.current_path.get_tagpath(self.tag_last_read)),
+                self.get_element_ts(),
+            ),
+            ParseState::GroupLength | ParseState::FileMeta if self.tag_last_read != 0 => (
+                Some(self.tag_last_read.into()),
+                &ts::ExplicitVRLittleEndian,
+            ),
+            _ => (None, self.detected_ts),
+        };
+        self.error_context = Some(ErrorContext {
+            offset: self.bytes_read,
+            tagpath,
+            ts,
+            bytes_before: self.dataset.get_history().as_slice().to_vec(),
+            bytes_after: self.dataset.peek(READ_HISTORY_LENGTH),
+        });
+        error
+    }
+
+    /// Where in the dataset the error last returned by the parser occurred, such as the tag path
+    /// of the element being parsed and the bytes around the failure. This is `None` if no error
+    /// has been returned.
+    pub fn get_error_context(&self) -> Option<&ErrorContext> {
+        self.error_context.as_ref()
+    }
+
     /// Get the file preamble (128-bytes) read from the dataset. If the dataset did not have a file
     /// preamble or if it has not yet been read from the dataset then this will be `None`.
     pub fn get_file_preamble(&self) -> &Option<[u8; FILE_PREAMBLE_LENGTH]> {
@@ -217,9 +486,35 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
                 |(to_check, current)| current > to_check,
             ),
             TagStop::AfterBytePos(byte_pos) => self.bytes_read > *byte_pos,
+            TagStop::BeforeTagPath(tagpath) => {
+                let position: TagPath = self.current_path.get_tagpath(self.tag_last_read);
+                TagStop::cmp_tagpath(tagpath, &position) != Ordering::Less
+            }
+            TagStop::AfterTagPath(tagpath) => {
+                let position: TagPath = self.current_path.get_tagpath(self.tag_last_read);
+                TagStop::cmp_tagpath(tagpath, &position) == Ordering::Greater
+            }
+            TagStop::AfterRootElements(count) => {
+                self.current_path.is_empty() && self.root_elements_read >= *count
+            }
+            TagStop::AfterGroup(group) => {
+                let root_tag: u32 = self
+                    .current_path
+                    .first()
+                    .map_or(self.tag_last_read, SequenceElement::get_seq_tag);
+                (root_tag >> 16) as u16 > *group
+            }
+            TagStop::AfterPredicate(_) => false,
         }
     }
 
+    /// Checks if the element should be skipped over rather than returned, based on `self.filter`.
+    fn is_filtered(&self, element: &DicomElement) -> bool {
+        self.filter.as_ref().map_or(false, |filter| {
+            filter.is_filtered_at(element.tag, element.get_sequence_path())
+        })
+    }
+
     /// Checks if the current path is within a pixeldata tag.
     fn is_in_pixeldata(&self) -> bool {
         for seq_elem in self.current_path.iter().rev() {
@@ -275,6 +570,15 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
     /// Reads the remainder of the dicom element from the dataset. This assumes `self.read_tag()`
     /// was called just prior and its result passed as the tag parameter here.
     fn read_dicom_element(&mut self, tag: u32, ts: TSRef) -> Result<DicomElement> {
+        if let Some(max_elements) = self.max_elements {
+            if self.elements_read >= max_elements {
+                return Err(ParseError::ElementCountExceedsLimit { max: max_elements });
+            }
+        }
+        if self.strict {
+            self.check_strict_tag(tag, ts)?;
+        }
+
         // Part 5, Section 7.5
         // There are three special SQ related Data Elements that are not ruled by the VR encoding
         // rules conveyed by the Transfer Syntax. They shall be encoded as Implicit VR. These
@@ -291,11 +595,18 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
             ts
         };
 
+        // when recovering, an unknown explicit VR is assumed to be the start of an implicit VR
+        // value length
+        let mut unknown_vr: Option<u16> = None;
         let vr_ts: (VRRef, TSRef) = if let Some(partial_vr) = self.partial_vr {
             self.partial_vr.take();
             (partial_vr, ts)
         } else if ts.explicit_vr {
             match self.read_vr(tag, ts) {
+                Err(ParseError::UnknownExplicitVR(code)) if self.recover => {
+                    unknown_vr = Some(code);
+                    (self.lookup_vr(tag).unwrap_or(&vr::UN), ts)
+                }
                 Err(ParseError::UnknownExplicitVR(_code)) => (&vr::INVALID, ts),
                 Err(e) => return Err(e),
                 Ok(vr_ts) => vr_ts,
@@ -317,10 +628,38 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         let vl: ValueLength = if let Some(partial_vl) = self.partial_vl {
             self.partial_vl.take();
             partial_vl
+        } else if let Some(code) = unknown_vr {
+            self.read_value_length_after_vr(code, ts)?
         } else {
             self.read_value_length(vl_read_4bytes, ts)?
         };
 
+        // the header has been fully read at this point, including when it was read during detection
+        let header_length: u32 = if unknown_vr.is_some() {
+            8
+        } else {
+            4 + if ts.explicit_vr { 2 } else { 0 }
+                + if ts.explicit_vr && vr.has_explicit_2byte_pad {
+                    2
+                } else {
+                    0
+                }
+                + if vl_read_4bytes { 4 } else { 2 }
+        };
+        let value_offset: u64 = self.bytes_read;
+        let element_offset: u64 = value_offset - u64::from(header_length);
+        self.element_offset = element_offset;
+
+        if let Some(code) = unknown_vr {
+            self.diagnose(element_offset, tag, DiagnosticKind::UnknownExplicitVR(code));
+        }
+        if self.recover {
+            self.check_value_length(element_offset, tag, vl)?;
+        }
+        if self.strict {
+            self.check_strict_value_length(tag, vr, vl)?;
+        }
+
         let parse_as_seq: bool = parser::util::is_non_standard_seq(tag, vr, vl);
         let ts: TSRef = if parse_as_seq {
             &ts::ImplicitVRLittleEndian
@@ -338,14 +677,35 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         let skip_bytes: bool =
             vr == &vr::SQ || (tag == tags::ITEM && !in_pixeldata) || parse_as_seq;
 
+        // values of filtered elements aren't needed unless they affect parsing the dataset
+        let filtered: bool = self.filter.as_ref().map_or(false, |filter| {
+            filter.is_filtered_at(tag, &self.current_path)
+                && tag != tags::FILE_META_INFORMATION_GROUP_LENGTH
+                && tag != tags::TRANSFER_SYNTAX_UID
+                && tag != tags::SPECIFIC_CHARACTER_SET
+        });
+
+        if !skip_bytes {
+            self.check_value_limits(tag, vl, filtered)?;
+        }
+
         // eprintln!("{:?}: Tag: {}, VR: {:?}, VL: {:?}, ts: {}, bytesread: {}", self.state, Tag::format_tag_to_display(tag), vr, vl, ts.uid.ident, self.bytes_read);
+        let mut bulk_data: Option<BulkDataRef> = None;
         let bytes: Vec<u8> = if skip_bytes {
             Vec::new()
+        } else if let (true, ValueLength::Explicit(value_length)) = (filtered, vl) {
+            self.skip_value_field(tag, value_length)?;
+            Vec::new()
+        } else if let Some(value_length) = self.get_bulk_data_length(tag, vl) {
+            bulk_data = Some(self.skip_value_field(tag, value_length)?);
+            Vec::new()
+        } else if self.recover {
+            self.read_value_field_recovering(element_offset, tag, vl)?
         } else {
             self.read_value_field(tag, vl)?
         };
 
-        let ancestors: Vec<SequenceElement> = self.current_path.clone();
+        let ancestors: SequencePath = self.current_path.clone();
 
         let cs: CSRef = if let Some(sq) = ancestors.last() {
             sq.get_cs()
@@ -353,7 +713,95 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
             self.cs
         };
 
-        Ok(DicomElement::new(tag, vr, vl, ts, cs, bytes, ancestors))
+        let mut element: DicomElement = if let Some(bulk_data) = bulk_data {
+            DicomElement::new_bulk_data(tag, vr, vl, ts, cs, bulk_data, ancestors)
+        } else {
+            DicomElement::new(tag, vr, vl, ts, cs, bytes, ancestors)
+        };
+        if self.record_offsets {
+            element.set_offsets(ElementOffsets {
+                element_offset,
+                header_length,
+                value_offset,
+            });
+        }
+        self.elements_read += 1;
+        if element.get_sequence_path().is_empty() && !self.is_filtered(&element) {
+            self.root_elements_read += 1;
+        }
+        Ok(element)
+    }
+
+    /// Checks the tag of an element being read in strict mode, along with that the dataset had the
+    /// DICOM prefix and the transfer syntax of File Meta elements.
+    fn check_strict_tag(&mut self, tag: u32, ts: TSRef) -> Result<()> {
+        if self.dicom_prefix.is_none() {
+            return Err(ParseError::MissingDICOMPrefix);
+        }
+        let is_file_meta: bool =
+            (tags::FILE_META_INFORMATION_GROUP_LENGTH..=tags::FILE_META_GROUP_END).contains(&tag);
+        if is_file_meta && ts != &ts::ExplicitVRLittleEndian {
+            return Err(ParseError::FileMetaNotExplicitVRLittleEndian { ts });
+        }
+
+        // elements read at a shallower depth end the sequences and items of deeper ones
+        let depth: usize = self.current_path.len();
+        self.previous_tags.truncate(depth + 1);
+        self.previous_tags.resize(depth + 1, None);
+        // items and delimiters repeat within a sequence
+        if tag >> 16 == tags::ITEM >> 16 {
+            return Ok(());
+        }
+        if let Some(previous) = self.previous_tags[depth] {
+            if tag <= previous {
+                return Err(ParseError::TagOutOfOrder { tag, previous });
+            }
+        }
+        self.previous_tags[depth] = Some(tag);
+        Ok(())
+    }
+
+    /// Checks the value length of an element in strict mode.
+    fn check_strict_value_length(&self, tag: u32, vr: VRRef, vl: ValueLength) -> Result<()> {
+        match vl {
+            ValueLength::Explicit(len) if len % 2 == 1 => {
+                Err(ParseError::OddValueLength { tag, vl: len })
+            }
+            ValueLength::UndefinedLength
+                if vr != &vr::SQ && tag != tags::ITEM && tag != tags::PIXEL_DATA =>
+            {
+                Err(ParseError::UnexpectedUndefinedLength { tag, vr })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the value of an element against `self.max_bytes`, and against
+    /// `self.max_value_length` if the value is to be read into memory. Values of filtered elements
+    /// are skipped over rather than read into memory.
+    fn check_value_limits(&self, tag: u32, vl: ValueLength, filtered: bool) -> Result<()> {
+        let value_length: u32 = match vl {
+            ValueLength::Explicit(value_length) => value_length,
+            ValueLength::UndefinedLength => return Ok(()),
+        };
+        if let Some(max_bytes) = self.max_bytes {
+            if self.bytes_read + u64::from(value_length) > max_bytes {
+                return Err(ParseError::DatasetSizeExceedsLimit { max: max_bytes });
+            }
+        }
+        if let Some(max_value_length) = self.max_value_length {
+            if value_length > max_value_length
+                && !filtered
+                && self.get_bulk_data_length(tag, vl).is_none()
+            {
+                return Err(ParseError::ValueLengthExceedsLimit {
+                    tag,
+                    vl: value_length,
+                    max: max_value_length,
+                });
+            }
+        }
+        Ok(())
     }
 
     /// Reads an explicit VR attribute from the dataset. This returns a tuple of `(VRRef, TSRef)`
@@ -421,6 +869,43 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         result
     }
 
+    /// Reads the remainder of an implicit VR value length whose first two bytes were read as an
+    /// unknown explicit VR.
+    fn read_value_length_after_vr(&mut self, code: u16, ts: TSRef) -> Result<ValueLength> {
+        let mut buf: [u8; 4] = [0; 4];
+        buf[0..2].copy_from_slice(&code.to_be_bytes());
+        self.dataset.read_exact(&mut buf[2..4])?;
+        self.bytes_read += 2;
+        parser::util::read_value_length_from_dataset(&mut Cursor::new(&buf), true, ts.big_endian)
+    }
+
+    /// Checks that the value of an element ends within the item or sequence containing it.
+    fn check_value_length(&mut self, element_offset: u64, tag: u32, vl: ValueLength) -> Result<()> {
+        let end_pos: Option<u64> = self
+            .current_path
+            .last()
+            .and_then(SequenceElement::get_seq_end_pos);
+        if let (ValueLength::Explicit(value_length), Some(end_pos)) = (vl, end_pos) {
+            let remaining: u64 = end_pos.saturating_sub(self.bytes_read);
+            if u64::from(value_length) > remaining {
+                self.diagnose(
+                    element_offset,
+                    tag,
+                    DiagnosticKind::ValueLengthOverrun {
+                        vl: value_length,
+                        remaining,
+                    },
+                );
+                return Err(ParseError::ValueLengthOverrun {
+                    tag,
+                    vl: value_length,
+                    remaining,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Reads the value field of the dicom element into a byte array. If the `ValueLength` is
     /// undefined then this returns an empty array as elements with undefined length should have
     /// their contents parsed as dicom elements.
@@ -478,6 +963,253 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         }
     }
 
+    /// Reads the value field like `read_value_field` except if the dataset ends before the full
+    /// value is read, in which case the value which was read is returned.
+    fn read_value_field_recovering(
+        &mut self,
+        element_offset: u64,
+        tag: u32,
+        vl: ValueLength,
+    ) -> Result<Vec<u8>> {
+        let value_length: u32 = match vl {
+            ValueLength::Explicit(value_length) => value_length,
+            ValueLength::UndefinedLength => return Ok(Vec::new()),
+        };
+
+        // the value length may not be trustworthy so don't allocate for it up front
+        let mut buffer: Vec<u8> = Vec::new();
+        let read: u64 = (&mut self.dataset)
+            .take(u64::from(value_length))
+            .read_to_end(&mut buffer)
+            .map_err(|source| ParseError::DetailedIOError {
+                source,
+                detail: format!(
+                    "reading tag: {}, vl: {}",
+                    Tag::format_tag_to_display(tag),
+                    value_length
+                ),
+            })? as u64;
+        self.bytes_read += read;
+
+        if read < u64::from(value_length) {
+            // see the same handling in `read_value_field`
+            if tag == tags::DATASET_TRAILING_PADDING {
+                return Err(ParseError::ExpectedEOF);
+            }
+            self.diagnose(
+                element_offset,
+                tag,
+                DiagnosticKind::TruncatedValue {
+                    vl: value_length,
+                    read,
+                },
+            );
+        }
+
+        if buffer.len() % 2 != 0 {
+            buffer.push(0);
+        }
+        Ok(buffer)
+    }
+
+    /// Records a problem found in the element with the given tag at the current sequence path.
+    fn diagnose(&mut self, offset: u64, tag: u32, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic {
+            offset,
+            tagpath: self.current_path.get_tagpath(tag),
+            kind,
+        });
+    }
+
+    /// Scans forward through the dataset for the start of the next element after one could not be
+    /// parsed. The next element is the first whose tag follows the tag last read, or an item or
+    /// delimiter when within a sequence, and whose VR and value length are plausible. If the
+    /// current item or sequence has an explicit length then scanning stops at its end. The tag,
+    /// VR, and value length read while scanning are left for the next iteration to continue from.
+    fn resync(&mut self) -> Result<()> {
+        let ts: TSRef = self.get_element_ts();
+        let failed_tag: u32 = self.tag_last_read;
+        let start_pos: u64 = self.bytes_read;
+        let end_pos: Option<u64> = self
+            .current_path
+            .last()
+            .and_then(SequenceElement::get_seq_end_pos);
+        self.partial_tag = None;
+        self.partial_vr = None;
+        self.partial_vl = None;
+
+        // enough to hold a tag and value length, or a tag, explicit VR, and 2-byte value length
+        let mut window: [u8; 8] = [0; 8];
+        let mut window_len: usize = 0;
+        let mut found: bool = false;
+        while end_pos.map_or(true, |end_pos| self.bytes_read < end_pos) {
+            if window_len == window.len() {
+                window.copy_within(1.., 0);
+                window_len -= 1;
+            }
+            let result: io::Result<()> = self
+                .dataset
+                .read_exact(&mut window[window_len..=window_len]);
+            match result {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e.into()),
+            }
+            self.bytes_read += 1;
+            window_len += 1;
+
+            if window_len == window.len() {
+                if let Some((tag, vr, vl)) = self.check_plausible_element(&window, ts, failed_tag) {
+                    self.partial_tag = Some(tag);
+                    self.partial_vr = vr;
+                    self.partial_vl = vl;
+                    found = true;
+                    break;
+                }
+            }
+        }
+
+        let skipped: u64 =
+            self.bytes_read - start_pos - if found { window.len() as u64 } else { 0 };
+        if skipped > 0 {
+            self.diagnose(start_pos, failed_tag, DiagnosticKind::SkippedBytes(skipped));
+        }
+        if !found && end_pos.is_none() {
+            return Err(ParseError::ExpectedEOF);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the bytes of `window` look like the start of the next element, returning its
+    /// tag along with its VR and value length if they were included in `window`.
+    fn check_plausible_element(
+        &self,
+        window: &[u8],
+        ts: TSRef,
+        failed_tag: u32,
+    ) -> Option<(u32, Option<VRRef>, Option<ValueLength>)> {
+        let mut cursor: Cursor<&[u8]> = Cursor::new(window);
+        let tag: u32 = parser::util::read_tag_from_dataset(&mut cursor, ts.big_endian).ok()?;
+        let end_pos: Option<u64> = self
+            .current_path
+            .last()
+            .and_then(SequenceElement::get_seq_end_pos);
+        let fits = |vl: ValueLength| match (vl, end_pos) {
+            (ValueLength::Explicit(len), Some(end_pos)) => {
+                self.bytes_read + u64::from(len) <= end_pos
+            }
+            _ => true,
+        };
+
+        if tag == tags::ITEM
+            || tag == tags::ITEM_DELIMITATION_ITEM
+            || tag == tags::SEQUENCE_DELIMITATION_ITEM
+        {
+            let vl: ValueLength =
+                parser::util::read_value_length_from_dataset(&mut cursor, true, ts.big_endian)
+                    .ok()?;
+            let is_delimiter: bool = tag != tags::ITEM;
+            if self.current_path.is_empty()
+                || (is_delimiter && vl != ValueLength::Explicit(0))
+                || !fits(vl)
+            {
+                return None;
+            }
+            return Some((tag, None, Some(vl)));
+        }
+
+        if tag <= failed_tag || (tag >> 16) < 0x0008 || (tag >> 16) == 0xFFFE {
+            return None;
+        }
+
+        if ts.explicit_vr {
+            let vr: VRRef = parser::util::read_vr_from_dataset(&mut cursor).ok()?;
+            if vr.has_explicit_2byte_pad {
+                // the padding filled the rest of the window, the value length is read after
+                return Some((tag, Some(vr), None));
+            }
+            let vl: ValueLength =
+                parser::util::read_value_length_from_dataset(&mut cursor, false, ts.big_endian)
+                    .ok()?;
+            return if fits(vl) {
+                Some((tag, Some(vr), Some(vl)))
+            } else {
+                None
+            };
+        }
+
+        self.lookup_vr(tag)?;
+        let vl: ValueLength =
+            parser::util::read_value_length_from_dataset(&mut cursor, true, ts.big_endian).ok()?;
+        if fits(vl) {
+            Some((tag, None, Some(vl)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value length if the value of the element should be skipped over as bulk data
+    /// based on `self.bulk_data_threshold`. Values of elements which affect parsing the rest of the dataset
+    /// are always read, as are values within a deflated dataset where positions within the stream
+    /// don't correspond to positions within the source.
+    fn get_bulk_data_length(&self, tag: u32, vl: ValueLength) -> Option<u32> {
+        match (self.bulk_data_threshold, vl) {
+            (Some(threshold), ValueLength::Explicit(len))
+                if len > threshold
+                    && self.state == ParseState::Element
+                    && !self.get_ts().deflated
+                    && tag != tags::TRANSFER_SYNTAX_UID
+                    && tag != tags::SPECIFIC_CHARACTER_SET =>
+            {
+                Some(len)
+            }
+            _ => None,
+        }
+    }
+
+    /// Skips over the value field of the dicom element without reading it into memory, returning
+    /// the location of the value within the dataset. The dataset is only required to implement
+    /// `Read` so unless `self.skip_bytes` is set the bytes are read and discarded rather than
+    /// seeked past. Values within a deflated dataset are always read, as the position within the
+    /// inflated dataset can't be seeked to.
+    fn skip_value_field(&mut self, tag: u32, value_length: u32) -> Result<BulkDataRef> {
+        let offset: u64 = self.bytes_read;
+        let detailed_error = |source: io::Error| ParseError::DetailedIOError {
+            source,
+            detail: format!(
+                "skipping tag: {}, vl: {}",
+                Tag::format_tag_to_display(tag),
+                value_length
+            ),
+        };
+
+        let skipped: u64 = match self.skip_bytes {
+            Some(skip_bytes) if self.dataset.can_skip() => self
+                .dataset
+                .skip(u64::from(value_length), skip_bytes)
+                .map_err(detailed_error)?,
+            _ => io::copy(
+                &mut (&mut self.dataset).take(u64::from(value_length)),
+                &mut io::sink(),
+            )
+            .map_err(detailed_error)?,
+        };
+        self.bytes_read += skipped;
+
+        if skipped < u64::from(value_length) {
+            // see the same handling when reading the value field
+            if tag == tags::DATASET_TRAILING_PADDING {
+                return Err(ParseError::ExpectedEOF);
+            }
+            return Err(detailed_error(io::Error::from(ErrorKind::UnexpectedEof)));
+        }
+
+        Ok(BulkDataRef {
+            offset,
+            length: value_length,
+        })
+    }
+
     /// Parses the value of the given element as the transfer syntax return. If the transfer syntax
     /// cannot be resolved then this sets it to the default DICOM transfer syntax which is IVRLE.
     fn parse_transfer_syntax(&mut self, element: &DicomElement) -> Result<Option<TSRef>> {
@@ -488,22 +1220,39 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
     /// Parses the value of the given element as the specific character set and sets the `cs` value
     /// on this iterator to affect the parsing of further text-type element values.
     fn parse_specific_character_set(&mut self, element: &DicomElement) -> Result<CSRef> {
-        let new_cs: Option<String> = Vec::<String>::try_from(element)?
-            .into_iter()
-            .find(|cs_entry: &String| !cs_entry.is_empty());
-
-        // TODO: There are options for what to do if we can't support the character repertoire
-        //       See note on Ch 5 Part 6.1.2.3 under "Considerations on the Handling of
-        //       Unsupported Character Sets"
+        let values: Vec<String> = Vec::<String>::try_from(element)?;
+        if let Some(cs) = charset::lookup_specific_character_set(&values) {
+            return Ok(cs);
+        }
 
-        Ok(new_cs
-            .and_then(|cs: String| charset::lookup_charset(&cs))
-            .unwrap_or(charset::DEFAULT_CHARACTER_SET))
+        // See note on Ch 5 Part 6.1.2.3 under "Considerations on the Handling of Unsupported
+        // Character Sets"
+        self.unsupported_charset
+            .get_substitute()
+            .ok_or(ParseError::UnsupportedCharacterSet { values })
     }
 
     /// Performs the primary iteration for the parser but the return type is consistent for error
     /// handling and not iteration. This should be called once for each invocation of `next()`.
     pub(crate) fn iterate(&mut self) -> Result<Option<DicomElement>> {
+        if self.tagstop_reached {
+            return Ok(None);
+        }
+        let mut element: Option<DicomElement> = self.iterate_state()?;
+        while element
+            .as_ref()
+            .map_or(false, |element| self.is_filtered(element))
+        {
+            element = self.iterate_state()?;
+        }
+        if let (TagStop::AfterPredicate(predicate), Some(element)) = (&self.tagstop, &element) {
+            self.tagstop_reached = predicate(element);
+        }
+        Ok(element)
+    }
+
+    /// Performs the iteration for the current parse state, see `iterate`.
+    fn iterate_state(&mut self) -> Result<Option<DicomElement>> {
         // The earlier parse states will read non-elements from the dataset and move to another
         // state. A loop is used so once those succeed they continue the loop and move to next
         // states which will eventually return a dicom element.
@@ -545,7 +1294,13 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
                     };
                 }
                 ParseState::Element => {
-                    return self.iterate_element();
+                    return match self.iterate_element() {
+                        Err(ParseError::ValueLengthOverrun { .. }) if self.recover => {
+                            self.resync()?;
+                            continue;
+                        }
+                        result => result,
+                    };
                 }
             }
         }
@@ -885,25 +1640,31 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
         Ok(Some(element))
     }
 
-    /// Performs the `ParserState::Element` iteration
-    fn iterate_element(&mut self) -> Result<Option<DicomElement>> {
-        // if we're in a sequence we need to use the sequence's transfer syntax
-        let ts: TSRef = self
-            .current_path
+    /// The transfer syntax elements are currently encoded in. If we're in a sequence we need to use
+    /// the sequence's transfer syntax.
+    fn get_element_ts(&self) -> TSRef {
+        self.current_path
             .last()
             .map(SequenceElement::get_ts)
             .or(self.dataset_ts)
-            .unwrap_or(self.detected_ts);
+            .unwrap_or(self.detected_ts)
+    }
+
+    /// Performs the `ParserState::Element` iteration
+    fn iterate_element(&mut self) -> Result<Option<DicomElement>> {
+        let ts: TSRef = self.get_element_ts();
 
         #[cfg(feature = "deflate")]
         if ts.deflated {
             self.dataset.set_read_deflated(true);
         }
+        // without an inflater the deflated bytes would be misread as elements
+        #[cfg(not(feature = "deflate"))]
+        if ts.deflated {
+            return Err(self.locate_error(ParseError::UnsupportedTransferSyntax { ts }));
+        }
 
         let tag: u32 = self.read_tag(ts)?;
-        if self.is_at_tag_stop() {
-            return Ok(None);
-        }
 
         // check after reading a tag - some items seem to have 0-length and are followed by another
         // item. without popping here it will create an item-in-item structure. also need to check
@@ -926,6 +1687,11 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
             }
         }
 
+        // check once the current path reflects where the element is, for stops within sequences
+        if self.is_at_tag_stop() {
+            return Ok(None);
+        }
+
         let element: DicomElement = self.read_dicom_element(tag, ts)?;
         // if the file-meta state was skipped due to the initial detection we may still need to
         // switch transfer syntax -- only do this if the element is at the root of the dataset
@@ -984,6 +1750,12 @@ impl<'dict, DatasetType: Read> Parser<'dict, DatasetType> {
                 self.cs
             };
 
+            if let Some(max_depth) = self.max_depth {
+                if self.current_path.len() >= max_depth {
+                    return Err(ParseError::NestingDepthExceedsLimit { max: max_depth });
+                }
+            }
+
             self.current_path
                 .push(SequenceElement::new(tag, seq_end_pos, sq_ts, sq_cs));
         }
@@ -0,0 +1,265 @@
+//! Parsing of a dataset which arrives in chunks, such as P-DATA fragments received over the network
+//! or the body of a streamed HTTP response, where waiting on a blocking `Read` isn't possible.
+
+use std::io::{self, ErrorKind, Read};
+
+#[cfg(feature = "deflate")]
+use libflate::non_blocking::deflate::Decoder;
+
+use crate::core::dcmelement::DicomElement;
+use crate::core::parser::builder::ParserBuilder;
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::{ParseState, Parser, ParserCheckpoint, Result};
+#[cfg(not(feature = "deflate"))]
+use crate::defn::ts::TSRef;
+
+/// The result of attempting to parse the next element from the data fed to a `PushParser`.
+pub enum PushEvent {
+    /// An element was parsed.
+    Element(DicomElement),
+    /// The data fed so far does not contain a complete element. Parsing continues once more data
+    /// is fed.
+    NeedMoreData,
+    /// The end of the dataset was reached, or the parser's `TagStop` was reached.
+    EndOfDataset,
+}
+
+/// The data fed to a `PushParser` which has not yet been parsed.
+#[derive(Default)]
+pub struct PushBuffer {
+    buffer: Vec<u8>,
+    /// The position within `buffer` of the next byte to be read.
+    position: usize,
+    /// The length `buffer` must reach before parsing is attempted again, after running out of data.
+    needed: usize,
+    /// Whether reading ran out of data since last being reset.
+    starved: bool,
+    /// Whether all data has been fed, after which running out of data is the end of the dataset.
+    finished: bool,
+    /// Once the dataset is found to use a deflated transfer syntax the data fed is inflated into
+    /// `buffer` as it arrives.
+    #[cfg(feature = "deflate")]
+    inflater: Option<Decoder<DeflatedInput>>,
+}
+
+impl PushBuffer {
+    /// Drops the bytes which have been read, as they belong to elements already returned.
+    fn consume(&mut self) {
+        self.buffer.drain(..self.position);
+        self.position = 0;
+        self.needed = 0;
+    }
+
+    fn feed(&mut self, chunk: &[u8]) {
+        #[cfg(feature = "deflate")]
+        if let Some(inflater) = &mut self.inflater {
+            inflater.as_inner_mut().data.extend_from_slice(chunk);
+            return;
+        }
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    fn finish(&mut self) {
+        #[cfg(feature = "deflate")]
+        if let Some(inflater) = &mut self.inflater {
+            inflater.as_inner_mut().finished = true;
+            return;
+        }
+        self.finished = true;
+    }
+
+    /// Treats the data not yet read as the start of a deflated dataset.
+    #[cfg(feature = "deflate")]
+    fn start_inflating(&mut self) {
+        let input: DeflatedInput = DeflatedInput {
+            data: self.buffer.split_off(self.position),
+            position: 0,
+            finished: self.finished,
+        };
+        self.finished = false;
+        self.inflater = Some(Decoder::new(input));
+    }
+
+    /// Inflates as much of the deflated data fed so far as possible.
+    #[cfg(feature = "deflate")]
+    fn inflate(&mut self) -> io::Result<()> {
+        let inflater: &mut Decoder<DeflatedInput> = match &mut self.inflater {
+            Some(inflater) => inflater,
+            None => return Ok(()),
+        };
+        let mut buf: [u8; 8 * 1024] = [0; 8 * 1024];
+        loop {
+            match inflater.read(&mut buf) {
+                Ok(0) => {
+                    self.finished = true;
+                    break;
+                }
+                Ok(len) => self.buffer.extend_from_slice(&buf[..len]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        inflater.as_inner_mut().consume();
+        Ok(())
+    }
+}
+
+impl Read for PushBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available: &[u8] = &self.buffer[self.position..];
+        if available.is_empty() && !buf.is_empty() && !self.finished {
+            self.starved = true;
+            self.needed = self.buffer.len() + buf.len();
+            return Err(io::Error::new(
+                ErrorKind::WouldBlock,
+                "waiting for more data to be fed",
+            ));
+        }
+        let len: usize = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Deflated data fed to a `PushParser` which has not yet been inflated.
+#[cfg(feature = "deflate")]
+struct DeflatedInput {
+    data: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+#[cfg(feature = "deflate")]
+impl DeflatedInput {
+    fn consume(&mut self) {
+        self.data.drain(..self.position);
+        self.position = 0;
+    }
+}
+
+#[cfg(feature = "deflate")]
+impl Read for DeflatedInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available: &[u8] = &self.data[self.position..];
+        if available.is_empty() && !buf.is_empty() && !self.finished {
+            return Err(io::Error::from(ErrorKind::WouldBlock));
+        }
+        let len: usize = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// A parser which is fed the dataset in chunks rather than reading it. This uses the same parsing
+/// as `Parser`, with each attempt to parse an element either returning the element or rolling back
+/// to the start of the element if the data fed so far ends partway through it.
+///
+/// Bytes are kept until the element they are part of is returned, so an element is held in memory
+/// in its entirety before being returned. Datasets which use a deflated transfer syntax are only
+/// supported with the `deflate` feature. Constructed with `ParserBuilder::build_push`.
+pub struct PushParser<'dict> {
+    parser: Parser<'dict, PushBuffer>,
+}
+
+impl<'dict> PushParser<'dict> {
+    pub(crate) fn new(builder: &ParserBuilder<'dict>) -> Self {
+        // the buffer must not read ahead of the parser so that it can be rolled back
+        let parser: Parser<'dict, PushBuffer> =
+            builder.clone().buffsize(0).build(PushBuffer::default());
+        // deflated data is inflated as it's fed, so the parser only reads inflated data
+        #[cfg(feature = "deflate")]
+        let parser: Parser<'dict, PushBuffer> = {
+            let mut parser = parser;
+            parser.dataset.set_pre_inflated(true);
+            parser
+        };
+        PushParser { parser }
+    }
+
+    /// The underlying parser, for inspecting its state such as the transfer syntax.
+    pub fn get_parser(&self) -> &Parser<'dict, PushBuffer> {
+        &self.parser
+    }
+
+    /// Records where in the dataset the error occurred, see `Parser::get_error_context`.
+    #[cfg(feature = "async")]
+    pub(crate) fn locate_error(&mut self, error: ParseError) -> ParseError {
+        self.parser.locate_error(error)
+    }
+
+    /// Adds the next chunk of the dataset.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.parser.get_dataset_mut().feed(chunk);
+    }
+
+    /// Indicates that the entire dataset has been fed. Data remaining after this which does not
+    /// make up a complete element results in an error rather than `PushEvent::NeedMoreData`.
+    pub fn finish(&mut self) {
+        self.parser.get_dataset_mut().finish();
+    }
+
+    /// Attempts to parse the next element from the data fed so far. Once
+    /// `PushEvent::EndOfDataset` or an error is returned parsing does not continue.
+    pub fn next_event(&mut self) -> Result<PushEvent> {
+        if self.parser.iterator_ended {
+            return Ok(PushEvent::EndOfDataset);
+        }
+        let deflated: bool =
+            self.parser.get_parser_state() == ParseState::Element && self.parser.get_ts().deflated;
+
+        #[cfg(not(feature = "deflate"))]
+        if deflated {
+            self.parser.iterator_ended = true;
+            let ts: TSRef = self.parser.get_ts();
+            return Err(self
+                .parser
+                .locate_error(ParseError::UnsupportedTransferSyntax { ts }));
+        }
+
+        let buffer: &mut PushBuffer = self.parser.get_dataset_mut();
+        #[cfg(feature = "deflate")]
+        {
+            if deflated && buffer.inflater.is_none() {
+                buffer.start_inflating();
+            }
+            if let Err(source) = buffer.inflate() {
+                self.parser.iterator_ended = true;
+                return Err(self.parser.locate_error(ParseError::DetailedIOError {
+                    source,
+                    detail: "inflating deflated dataset".to_owned(),
+                }));
+            }
+        }
+        if !buffer.finished && buffer.buffer.len() < buffer.needed {
+            return Ok(PushEvent::NeedMoreData);
+        }
+        buffer.starved = false;
+        let start: usize = buffer.position;
+
+        let checkpoint: ParserCheckpoint = self.parser.checkpoint();
+        let result: Result<Option<DicomElement>> = self.parser.iterate();
+
+        let buffer: &mut PushBuffer = self.parser.get_dataset_mut();
+        if buffer.starved {
+            buffer.position = start;
+            self.parser.restore(checkpoint);
+            return Ok(PushEvent::NeedMoreData);
+        }
+        buffer.consume();
+
+        match result {
+            Ok(Some(element)) => Ok(PushEvent::Element(element)),
+            Ok(None) | Err(ParseError::ExpectedEOF) => {
+                self.parser.iterator_ended = true;
+                Ok(PushEvent::EndOfDataset)
+            }
+            Err(e) => {
+                self.parser.iterator_ended = true;
+                Err(self.parser.locate_error(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Parsing of a dataset which is already in memory, such as a memory-mapped file, where element
+//! values borrow from the dataset instead of being copied.
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io;
+
+use crate::core::dcmelement::DicomElement;
+use crate::core::parser::builder::ParserBuilder;
+use crate::core::parser::parser::{Parser, Result};
+use crate::core::seekable::ElementOffsets;
+use crate::defn::vl::ValueLength;
+
+/// An element parsed by `SliceParser` along with its value.
+pub struct SliceElement<'buf> {
+    /// The parsed element. Its value is only populated for elements which affect parsing the rest
+    /// of the dataset, such as the Specific Character Set, and elements within a deflated dataset.
+    /// Use `into_element` for an element with its value populated.
+    pub element: DicomElement,
+    /// The value of the element. This borrows from the dataset unless the value had to be read in
+    /// order to parse the dataset. As with values read by `Parser`, values with odd length are
+    /// padded with a zero to be even length, so these are copied rather than borrowed.
+    pub value: Cow<'buf, [u8]>,
+}
+
+impl<'buf> SliceElement<'buf> {
+    /// The position of the element within the dataset.
+    pub fn get_offsets(&self) -> Option<ElementOffsets> {
+        self.element.get_offsets()
+    }
+
+    /// Converts into an element with its value copied from the dataset, for use with the value
+    /// parsing methods of `DicomElement`.
+    pub fn into_element(self) -> DicomElement {
+        let element: DicomElement = self.element;
+        if !element.get_data().is_empty() || self.value.is_empty() {
+            return element;
+        }
+        let data: Vec<u8> = self.value.into_owned();
+        DicomElement::new(
+            element.tag,
+            element.vr,
+            element.vl,
+            element.get_ts(),
+            element.get_cs(),
+            data,
+            element.get_sequence_path().clone(),
+        )
+    }
+}
+
+/// A parser over a dataset which is in memory. This uses the same parsing as `Parser` but skips
+/// over values by advancing its position in the dataset instead of reading them, so that values
+/// are returned as slices of the dataset without being copied, other than those with odd length.
+/// Constructed with `ParserBuilder::build_slice`.
+pub struct SliceParser<'dict, 'buf> {
+    dataset: &'buf [u8],
+    parser: Parser<'dict, &'buf [u8]>,
+}
+
+impl<'dict, 'buf> SliceParser<'dict, 'buf> {
+    pub(crate) fn new(builder: &ParserBuilder<'dict>, dataset: &'buf [u8]) -> Self {
+        // without a buffer reads go directly to the slice, and all values other than those needed
+        // for parsing are skipped over so they can be borrowed using their recorded position
+        let mut parser: Parser<'dict, &'buf [u8]> = builder
+            .clone()
+            .buffsize(0)
+            .bulk_data_threshold(0)
+            .record_offsets(true)
+            .build(dataset);
+        parser.skip_bytes = Some(skip_bytes);
+        SliceParser { dataset, parser }
+    }
+
+    /// The underlying parser, for inspecting its state such as the transfer syntax.
+    pub fn get_parser(&self) -> &Parser<'dict, &'buf [u8]> {
+        &self.parser
+    }
+
+    /// Gets the value of the element as a slice of the dataset.
+    fn get_value(&self, element: &DicomElement) -> Cow<'buf, [u8]> {
+        let dataset: &'buf [u8] = self.dataset;
+        match (element.get_bulk_data(), element.get_offsets(), element.vl) {
+            (Some(_), Some(offsets), ValueLength::Explicit(len)) => {
+                let start: usize = offsets.value_offset as usize;
+                let value: &'buf [u8] = &dataset[start..start + len as usize];
+                if value.len() % 2 != 0 {
+                    let mut padded: Vec<u8> = value.to_vec();
+                    padded.push(0);
+                    Cow::Owned(padded)
+                } else {
+                    Cow::Borrowed(value)
+                }
+            }
+            _ => Cow::Owned(element.get_data().clone()),
+        }
+    }
+}
+
+/// Skips values by advancing the remaining slice of the dataset, without copying the value.
+fn skip_bytes(dataset: &mut &[u8], len: u64) -> io::Result<u64> {
+    let skipped: usize = usize::try_from(len).map_or(dataset.len(), |len| len.min(dataset.len()));
+    *dataset = &dataset[skipped..];
+    Ok(skipped as u64)
+}
+
+impl<'dict, 'buf> Iterator for SliceParser<'dict, 'buf> {
+    type Item = Result<SliceElement<'buf>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element: DicomElement = match self.parser.next()? {
+            Ok(element) => element,
+            Err(e) => return Some(Err(e)),
+        };
+        let value: Cow<'buf, [u8]> = self.get_value(&element);
+        Some(Ok(SliceElement { element, value }))
+    }
+}
@@ -0,0 +1,81 @@
+//! Parsing of a dataset read asynchronously, for use within async services where blocking on a
+//! `Read` would stall other tasks. Requires the `async` feature.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::AsyncRead;
+
+use crate::core::dcmelement::DicomElement;
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::{Parser, Result};
+use crate::core::parser::push::{PushBuffer, PushEvent, PushParser};
+
+/// A stream of elements parsed from a dataset read through `tokio::io::AsyncRead`. Data is read in
+/// chunks and fed to a `PushParser`, so this uses the same parsing as `Parser`. Constructed with
+/// `ParserBuilder::build_async`.
+pub struct AsyncParser<'dict, DatasetType: AsyncRead + Unpin> {
+    dataset: DatasetType,
+    parser: PushParser<'dict>,
+    /// The buffer chunks of the dataset are read into.
+    chunk: Vec<u8>,
+    /// Whether reading from the dataset failed, after which the stream ends.
+    read_failed: bool,
+}
+
+impl<'dict, DatasetType: AsyncRead + Unpin> AsyncParser<'dict, DatasetType> {
+    pub(crate) fn new(
+        parser: PushParser<'dict>,
+        dataset: DatasetType,
+        chunk_size: usize,
+    ) -> AsyncParser<'dict, DatasetType> {
+        AsyncParser {
+            dataset,
+            parser,
+            chunk: vec![0; chunk_size.max(1)],
+            read_failed: false,
+        }
+    }
+
+    /// The underlying parser, for inspecting its state such as the transfer syntax.
+    pub fn get_parser(&self) -> &Parser<'dict, PushBuffer> {
+        self.parser.get_parser()
+    }
+
+    /// Returns the dataset, positioned wherever it was last read from.
+    pub fn into_inner(self) -> DatasetType {
+        self.dataset
+    }
+}
+
+impl<'dict, DatasetType: AsyncRead + Unpin> Stream for AsyncParser<'dict, DatasetType> {
+    type Item = Result<DicomElement>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this: &mut AsyncParser<'dict, DatasetType> = self.get_mut();
+        if this.read_failed {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this.parser.next_event() {
+                Ok(PushEvent::Element(element)) => return Poll::Ready(Some(Ok(element))),
+                Ok(PushEvent::EndOfDataset) => return Poll::Ready(None),
+                Ok(PushEvent::NeedMoreData) => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+
+            match Pin::new(&mut this.dataset).poll_read(cx, &mut this.chunk) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => this.parser.finish(),
+                Poll::Ready(Ok(len)) => this.parser.feed(&this.chunk[..len]),
+                Poll::Ready(Err(source)) => {
+                    this.read_failed = true;
+                    let error: ParseError = ParseError::IOError { source };
+                    return Poll::Ready(Some(Err(this.parser.locate_error(error))));
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,8 @@ use std::collections::BTreeMap;
 use std::io::{ErrorKind, Read};
 
 use crate::core::dcmelement::DicomElement;
-use crate::core::dcmobject::{DicomObject, DicomRoot};
+use crate::core::dcmobject::{DicomObject, DicomRoot, DuplicateTagPolicy};
+use crate::core::parser::diagnostic::{Diagnostic, DiagnosticKind};
 use crate::core::parser::error::{ParseError};
 use super::parser::{Parser, Result};
 use crate::defn::constants::tags;
@@ -137,10 +138,16 @@ pub fn parse_into_object<'dict, DatasetType: Read>(
     parser: &mut Parser<'dict, DatasetType>,
 ) -> Result<Option<DicomRoot<'dict>>> {
     let mut child_nodes: BTreeMap<u32, DicomObject> = BTreeMap::new();
+    let mut child_order: Vec<u32> = Vec::new();
     let mut items: Vec<DicomObject> = Vec::new();
 
-    let parse_result: Option<Result<DicomElement>> =
-        parse_into_object_recurse(parser, &mut child_nodes, &mut items, true);
+    let parse_result: Option<Result<DicomElement>> = parse_into_object_recurse(
+        parser,
+        &mut child_nodes,
+        &mut child_order,
+        &mut items,
+        true,
+    );
     if let Some(Err(e)) = parse_result {
         return Err(e);
     }
@@ -157,7 +164,8 @@ pub fn parse_into_object<'dict, DatasetType: Read>(
         parser.get_cs(),
         parser.get_dictionary(),
         child_nodes,
-    );
+    )
+    .with_child_order(child_order);
     Ok(Some(root))
 }
 
@@ -168,11 +176,13 @@ pub fn parse_into_object<'dict, DatasetType: Read>(
 /// be the end of multiple sequences).
 /// `parser` The parser elements are being read from
 /// `child_nodes` The map of nodes which elements should be parsed into
+/// `child_order` The tags of `child_nodes` in the order they were parsed
 /// `item_nodes` The list of nodes which item elements should be parsed into
 /// `is_first_level` Whether the root level is being parsed, or within child nodes
 fn parse_into_object_recurse<DatasetType: Read>(
     parser: &mut Parser<'_, DatasetType>,
     child_nodes: &mut BTreeMap<u32, DicomObject>,
+    child_order: &mut Vec<u32>,
     items: &mut Vec<DicomObject>,
     is_root_level: bool,
 ) -> Option<Result<DicomElement>> {
@@ -188,6 +198,8 @@ fn parse_into_object_recurse<DatasetType: Read>(
 
     while let Some(Ok(element)) = next_element {
         let tag: u32 = element.tag;
+        // the element being added is always the one last read, either here or by the recursion
+        let element_offset: u64 = parser.element_offset;
         let cur_seq_path_len: usize = element.get_sequence_path().len() + 1;
 
         if prev_seq_path_len == 0 {
@@ -208,17 +220,26 @@ fn parse_into_object_recurse<DatasetType: Read>(
             || (tag == tags::ITEM && element.vl != ValueLength::Explicit(0))
         {
             let mut child_nodes: BTreeMap<u32, DicomObject> = BTreeMap::new();
+            let mut child_order: Vec<u32> = Vec::new();
             let mut items: Vec<DicomObject> = Vec::new();
-            possible_next_elem =
-                parse_into_object_recurse(parser, &mut child_nodes, &mut items, false);
+            possible_next_elem = parse_into_object_recurse(
+                parser,
+                &mut child_nodes,
+                &mut child_order,
+                &mut items,
+                false,
+            );
             DicomObject::new_with_children(element, child_nodes, items)
+                .with_child_order(child_order)
         } else {
             DicomObject::new(element)
         };
         if tag == tags::ITEM {
             items.push(dcmobj);
-        } else {
-            child_nodes.insert(tag, dcmobj);
+        } else if let Err(e) =
+            insert_child_node(parser, child_nodes, child_order, dcmobj, element_offset)
+        {
+            return Some(Err(e));
         }
 
         prev_seq_path_len = cur_seq_path_len;
@@ -253,4 +274,53 @@ fn parse_into_object_recurse<DatasetType: Read>(
 
     // return the last value from the parser which will either be None or Error
     next_element
-}
\ No newline at end of file
+}
+
+/// Adds the node as a child, checking whether its tag is a duplicate or is out of order based on
+/// the order child nodes were parsed in. Problems are recorded as diagnostics of the parser, and
+/// duplicates are handled based on the parser's `DuplicateTagPolicy`.
+fn insert_child_node<DatasetType: Read>(
+    parser: &mut Parser<'_, DatasetType>,
+    child_nodes: &mut BTreeMap<u32, DicomObject>,
+    child_order: &mut Vec<u32>,
+    dcmobj: DicomObject,
+    element_offset: u64,
+) -> Result<()> {
+    let tag: u32 = dcmobj.get_element().tag;
+    let policy: DuplicateTagPolicy = parser.duplicate_tags;
+    let mut diagnose = |kind: DiagnosticKind| {
+        parser.diagnostics.push(Diagnostic {
+            offset: element_offset,
+            tagpath: dcmobj.get_element().get_tagpath(),
+            kind,
+        });
+    };
+
+    if let Some(previous) = child_order.last().copied() {
+        if tag < previous {
+            diagnose(DiagnosticKind::TagOutOfOrder { previous });
+        }
+    }
+
+    if child_nodes.contains_key(&tag) {
+        match policy {
+            DuplicateTagPolicy::Error => {
+                return Err(ParseError::DuplicateTag {
+                    tagpath: dcmobj.get_element().get_tagpath(),
+                });
+            }
+            DuplicateTagPolicy::KeepFirst => {
+                diagnose(DiagnosticKind::DuplicateTag);
+                return Ok(());
+            }
+            DuplicateTagPolicy::KeepLast => {
+                diagnose(DiagnosticKind::DuplicateTag);
+                child_order.retain(|child_tag| *child_tag != tag);
+            }
+        }
+    }
+
+    child_order.push(tag);
+    child_nodes.insert(tag, dcmobj);
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Person Name (PN) values, see Part 5 Section 6.2.1 Person Name (PN) Value Representation.
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+use encoding::types::EncoderTrap;
+
+use crate::core::charset::CSRef;
+use crate::core::dcmelement::DicomElement;
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::Result;
+use crate::defn::vr;
+
+/// Separates the component groups of a person name.
+pub const COMPONENT_GROUP_SEPARATOR: char = '=';
+/// Separates the components within a component group of a person name.
+pub const COMPONENT_SEPARATOR: char = '^';
+
+/// One component group of a person name, with up to five components.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PersonNameGroup {
+    pub family: String,
+    pub given: String,
+    pub middle: String,
+    pub prefix: String,
+    pub suffix: String,
+}
+
+impl PersonNameGroup {
+    /// Whether all components of this group are empty.
+    pub fn is_empty(&self) -> bool {
+        self.components()
+            .iter()
+            .all(|component| component.is_empty())
+    }
+
+    /// The components in the order they are encoded: family, given, middle, prefix, suffix.
+    pub fn components(&self) -> [&str; 5] {
+        [
+            &self.family,
+            &self.given,
+            &self.middle,
+            &self.prefix,
+            &self.suffix,
+        ]
+    }
+}
+
+impl From<&str> for PersonNameGroup {
+    /// Parses a component group. Components beyond the fifth are kept as part of the suffix.
+    fn from(value: &str) -> Self {
+        let mut components = value.splitn(5, COMPONENT_SEPARATOR).map(str::to_owned);
+        PersonNameGroup {
+            family: components.next().unwrap_or_default(),
+            given: components.next().unwrap_or_default(),
+            middle: components.next().unwrap_or_default(),
+            prefix: components.next().unwrap_or_default(),
+            suffix: components.next().unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for PersonNameGroup {
+    /// Formats the components separated by `^`, omitting trailing empty components.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components: [&str; 5] = self.components();
+        let num_components: usize = components
+            .iter()
+            .rposition(|component| !component.is_empty())
+            .map_or(0, |index| index + 1);
+        write!(
+            f,
+            "{}",
+            components[..num_components].join(&COMPONENT_SEPARATOR.to_string())
+        )
+    }
+}
+
+/// A person name, with its alphabetic, ideographic, and phonetic component groups.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PersonName {
+    pub alphabetic: PersonNameGroup,
+    pub ideographic: PersonNameGroup,
+    pub phonetic: PersonNameGroup,
+}
+
+impl PersonName {
+    /// Whether all component groups of this name are empty.
+    pub fn is_empty(&self) -> bool {
+        self.groups().iter().all(|group| group.is_empty())
+    }
+
+    /// The component groups in the order they are encoded: alphabetic, ideographic, phonetic.
+    pub fn groups(&self) -> [&PersonNameGroup; 3] {
+        [&self.alphabetic, &self.ideographic, &self.phonetic]
+    }
+
+    /// Encodes this name with the given character set, for use as an element value. Each component
+    /// group is encoded separately so that character sets using code extensions return to their
+    /// initial state before each `=`. The result is padded with a space to an even length.
+    /// Components containing `^`, `=`, or `\` are an error as they would change the structure
+    /// of the encoded name.
+    pub fn encode(&self, cs: CSRef) -> std::result::Result<Vec<u8>, Cow<'static, str>> {
+        let delimiters: [char; 3] = [COMPONENT_SEPARATOR, COMPONENT_GROUP_SEPARATOR, '\\'];
+        for group in self.groups().iter() {
+            if let Some(component) = group
+                .components()
+                .iter()
+                .find(|component| component.contains(delimiters))
+            {
+                return Err(Cow::Owned(format!(
+                    "person name component contains a delimiter: {}",
+                    component
+                )));
+            }
+        }
+
+        let groups: Vec<String> = self.formatted_groups();
+        let mut bytes: Vec<u8> = Vec::new();
+        for (index, group) in groups.iter().enumerate() {
+            if index > 0 {
+                bytes.push(COMPONENT_GROUP_SEPARATOR as u8);
+            }
+            bytes.extend(cs.encode(group, EncoderTrap::Strict)?);
+        }
+        if bytes.len() % 2 != 0 {
+            bytes.push(vr::SPACE_PADDING);
+        }
+        Ok(bytes)
+    }
+
+    /// The formatted component groups, omitting trailing empty groups.
+    fn formatted_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = self
+            .groups()
+            .iter()
+            .map(|group| group.to_string())
+            .collect::<Vec<String>>();
+        while groups.last().map_or(false, |group| group.is_empty()) {
+            groups.pop();
+        }
+        groups
+    }
+}
+
+impl From<&str> for PersonName {
+    /// Parses a single person name value. Component groups beyond the third are ignored.
+    fn from(value: &str) -> Self {
+        let mut groups = value
+            .split(COMPONENT_GROUP_SEPARATOR)
+            .map(PersonNameGroup::from);
+        PersonName {
+            alphabetic: groups.next().unwrap_or_default(),
+            ideographic: groups.next().unwrap_or_default(),
+            phonetic: groups.next().unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for PersonName {
+    /// Formats the component groups separated by `=`, omitting trailing empty groups.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.formatted_groups()
+                .join(&COMPONENT_GROUP_SEPARATOR.to_string())
+        )
+    }
+}
+
+impl TryFrom<&DicomElement> for PersonName {
+    type Error = ParseError;
+
+    /// Parses the first value of the element as a person name. The element's character set is
+    /// reset at each `=` and `^` so each component group is decoded in its own repertoire.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        Ok(Vec::<PersonName>::try_from(value)?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+}
+
+impl TryFrom<&DicomElement> for Vec<PersonName> {
+    type Error = ParseError;
+
+    /// Parses all values of the element as person names.
+    fn try_from(value: &DicomElement) -> Result<Self> {
+        Ok(Vec::<String>::try_from(value)?
+            .iter()
+            .map(|name: &String| PersonName::from(name.trim_end()))
+            .collect::<Vec<PersonName>>())
+    }
+}
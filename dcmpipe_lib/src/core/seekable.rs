@@ -0,0 +1,316 @@
+//! Random access to the elements of a dataset whose source implements `Seek`. The dataset is parsed
+//! once to record where each element is, after which values and frames of pixel data can be read
+//! directly from the source.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::core::dcmelement::{self, DicomElement};
+use crate::core::numeric::{self, NumericStringMode};
+use crate::core::parser::error::ParseError;
+use crate::core::parser::parser::Result;
+use crate::core::parser::{Parser, ParserBuilder};
+use crate::defn::constants::tags;
+use crate::defn::tag::TagPath;
+use crate::defn::vl::ValueLength;
+use crate::defn::vr;
+
+/// The position of an element within the dataset it was parsed from, relative to where the parser
+/// started reading the dataset.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElementOffsets {
+    /// The byte position of the start of the element's tag.
+    pub element_offset: u64,
+    /// The number of bytes in the element's tag, VR, and value length.
+    pub header_length: u32,
+    /// The byte position of the start of the element's value field.
+    pub value_offset: u64,
+}
+
+/// An item within encapsulated pixel data.
+#[derive(Clone, Copy)]
+struct Fragment {
+    element_offset: u64,
+    value_offset: u64,
+    length: u32,
+}
+
+/// A dataset which has been parsed once to record the position of each element, allowing element
+/// values and frames of pixel data to be read later without parsing the dataset again.
+///
+/// Values of elements are kept as they were parsed, so using a `bulk_data_threshold` on the parser
+/// avoids holding large values such as pixel data in memory.
+pub struct SeekableDataset<SourceType: Read + Seek> {
+    source: SourceType,
+    /// The position within the source at which parsing started.
+    start: u64,
+    /// All elements parsed from the dataset, in the order they appear.
+    elements: Vec<DicomElement>,
+    /// Index into `elements` by the path of each element.
+    index: HashMap<TagPath, usize>,
+}
+
+impl<SourceType: Read + Seek> SeekableDataset<SourceType> {
+    /// Parses the dataset from the source's current position using the given builder, recording
+    /// the position of each element. The dataset must not use a deflated transfer syntax.
+    pub fn open(builder: ParserBuilder<'_>, mut source: SourceType) -> Result<Self> {
+        let start: u64 = source.stream_position()?;
+
+        let mut elements: Vec<DicomElement> = Vec::new();
+        {
+            let mut parser: Parser<'_, &mut SourceType> =
+                builder.record_offsets(true).build(&mut source);
+            for element in parser.by_ref() {
+                elements.push(element?);
+            }
+            if parser.get_ts().deflated {
+                return Err(ParseError::DetailedIOError {
+                    source: std::io::Error::from(std::io::ErrorKind::Unsupported),
+                    detail: "positions within a deflated dataset can't be seeked to".to_owned(),
+                });
+            }
+        }
+
+        let mut index: HashMap<TagPath, usize> = HashMap::new();
+        for (position, element) in elements.iter().enumerate() {
+            index.entry(element.get_tagpath()).or_insert(position);
+        }
+
+        Ok(SeekableDataset {
+            source,
+            start,
+            elements,
+            index,
+        })
+    }
+
+    /// All elements of the dataset, in the order they appear.
+    pub fn get_elements(&self) -> &[DicomElement] {
+        &self.elements
+    }
+
+    /// Gets the element at the given path, as it was parsed.
+    pub fn get_element(&self, tagpath: &TagPath) -> Option<&DicomElement> {
+        self.index
+            .get(tagpath)
+            .and_then(|position| self.elements.get(*position))
+    }
+
+    /// Reads the element at the given path from the source, including its value.
+    pub fn read_element(&mut self, tagpath: &TagPath) -> Result<Option<DicomElement>> {
+        let position: usize = match self.index.get(tagpath) {
+            Some(position) => *position,
+            None => return Ok(None),
+        };
+        let data: Vec<u8> = self.read_value_at(position)?;
+        let element: &DicomElement = &self.elements[position];
+        Ok(Some(DicomElement::new(
+            element.tag,
+            element.vr,
+            element.vl,
+            element.get_ts(),
+            element.get_cs(),
+            data,
+            element.get_sequence_path().clone(),
+        )))
+    }
+
+    /// Reads the value of the element at the given path from the source.
+    pub fn read_value(&mut self, tagpath: &TagPath) -> Result<Option<Vec<u8>>> {
+        match self.index.get(tagpath) {
+            Some(position) => Ok(Some(self.read_value_at(*position)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The number of frames of pixel data, zero if the dataset has no pixel data.
+    pub fn get_frame_count(&self) -> usize {
+        if !self.index.contains_key(&TagPath::from(tags::PIXEL_DATA)) {
+            return 0;
+        }
+        self.get_root_int(tags::NUMBER_OF_FRAMES)
+            .map_or(1, |frames| frames.max(1) as usize)
+    }
+
+    /// Reads a frame of pixel data from the source. The index is 0-based. For encapsulated pixel
+    /// data the fragments of the frame are concatenated, see Part 5 Section A.4.
+    pub fn read_frame(&mut self, index: usize) -> Result<Vec<u8>> {
+        let pixel_data: &DicomElement = self
+            .get_element(&TagPath::from(tags::PIXEL_DATA))
+            .ok_or_else(|| ParseError::ValueParseError {
+                message: "dataset has no pixel data".to_owned(),
+                tag: tags::PIXEL_DATA,
+                vr: &vr::OB,
+                bytes: Vec::new(),
+            })?;
+        let num_frames: usize = self.get_frame_count();
+        if index >= num_frames {
+            return Err(dcmelement::error(
+                &format!("frame {} is beyond the {} frames", index, num_frames),
+                pixel_data,
+            ));
+        }
+
+        match pixel_data.vl {
+            ValueLength::Explicit(len) => {
+                let value_offset: u64 = offsets_of(pixel_data)?.value_offset;
+                let frame_size: u64 = self.get_native_frame_size();
+                let frame_offset: u64 = frame_size * index as u64;
+                if frame_size == 0 || frame_offset + frame_size > u64::from(len) {
+                    return Err(dcmelement::error(
+                        "frame size does not agree with the length of pixel data",
+                        pixel_data,
+                    ));
+                }
+                self.read_at(value_offset + frame_offset, frame_size as usize)
+            }
+            ValueLength::UndefinedLength => {
+                let fragments: Vec<Fragment> = self.get_fragments()?;
+                let frame_fragments: Vec<Fragment> =
+                    self.get_frame_fragments(&fragments, index, num_frames)?;
+                let mut frame: Vec<u8> = Vec::new();
+                for fragment in frame_fragments {
+                    frame.extend(self.read_at(fragment.value_offset, fragment.length as usize)?);
+                }
+                Ok(frame)
+            }
+        }
+    }
+
+    /// Returns the source, positioned wherever it was last read from.
+    pub fn into_inner(self) -> SourceType {
+        self.source
+    }
+
+    fn read_value_at(&mut self, position: usize) -> Result<Vec<u8>> {
+        let element: &DicomElement = &self.elements[position];
+        match element.vl {
+            ValueLength::Explicit(len) if len > 0 => {
+                let value_offset: u64 = offsets_of(element)?.value_offset;
+                let mut value: Vec<u8> = self.read_at(value_offset, len as usize)?;
+                // values parsed from the dataset are always padded to even length
+                if value.len() % 2 != 0 {
+                    value.push(0);
+                }
+                Ok(value)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// The value of an integer element at the root of the dataset.
+    fn get_root_int(&self, tag: u32) -> Option<i64> {
+        let element: &DicomElement = self.get_element(&TagPath::from(tag))?;
+        if element.vr.is_character_string {
+            numeric::parse_integer_strings(element, NumericStringMode::Lenient)
+                .ok()
+                .and_then(|values| values.first().map(|value| i64::from(*value)))
+        } else {
+            u16::try_from(element).ok().map(i64::from)
+        }
+    }
+
+    /// The size in bytes of each frame of native (not encapsulated) pixel data.
+    fn get_native_frame_size(&self) -> u64 {
+        let dims: Vec<u64> = [
+            tags::ROWS,
+            tags::COLUMNS,
+            tags::SAMPLES_PER_PIXEL,
+            tags::BITS_ALLOCATED,
+        ]
+        .iter()
+        .map(|tag| self.get_root_int(*tag).unwrap_or(0).max(0) as u64)
+        .collect::<Vec<u64>>();
+        let bits: u64 = dims.iter().product::<u64>();
+        (bits + 7) / 8
+    }
+
+    /// The item elements within encapsulated pixel data. The Basic Offset Table is the first.
+    fn get_fragments(&self) -> Result<Vec<Fragment>> {
+        let mut fragments: Vec<Fragment> = Vec::new();
+        for element in self.elements.iter() {
+            let in_pixel_data: bool = element.tag == tags::ITEM
+                && element
+                    .get_sequence_path()
+                    .first()
+                    .map_or(false, |sq| sq.get_seq_tag() == tags::PIXEL_DATA);
+            if !in_pixel_data {
+                continue;
+            }
+            let offsets: ElementOffsets = offsets_of(element)?;
+            fragments.push(Fragment {
+                element_offset: offsets.element_offset,
+                value_offset: offsets.value_offset,
+                length: match element.vl {
+                    ValueLength::Explicit(len) => len,
+                    ValueLength::UndefinedLength => 0,
+                },
+            });
+        }
+        Ok(fragments)
+    }
+
+    /// The fragments which make up the given frame. The Basic Offset Table is used if present,
+    /// otherwise each fragment is assumed to be a frame unless there's only one frame.
+    fn get_frame_fragments(
+        &mut self,
+        fragments: &[Fragment],
+        index: usize,
+        num_frames: usize,
+    ) -> Result<Vec<Fragment>> {
+        let (table, fragments): (&Fragment, &[Fragment]) = match fragments.split_first() {
+            Some(split) => split,
+            None => return Ok(Vec::new()),
+        };
+
+        // offsets in the table are relative to the first fragment following the table
+        let offsets: Vec<u64> = self
+            .read_at(table.value_offset, table.length as usize)?
+            .chunks_exact(4)
+            .map(|chunk| u64::from(u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])))
+            .collect::<Vec<u64>>();
+        if offsets.len() == num_frames {
+            let first: u64 = fragments.first().map_or(0, |f| f.element_offset);
+            let start: u64 = offsets[index];
+            let end: u64 = offsets.get(index + 1).copied().unwrap_or(u64::MAX);
+            return Ok(fragments
+                .iter()
+                .filter(|f| {
+                    let relative: u64 = f.element_offset - first;
+                    relative >= start && relative < end
+                })
+                .copied()
+                .collect::<Vec<Fragment>>());
+        }
+        if num_frames == 1 {
+            return Ok(fragments.to_vec());
+        }
+        if fragments.len() == num_frames {
+            return Ok(vec![fragments[index]]);
+        }
+        Err(ParseError::ValueParseError {
+            message: format!(
+                "unable to determine the fragments of frame {} from {} fragments",
+                index,
+                fragments.len()
+            ),
+            tag: tags::PIXEL_DATA,
+            vr: &vr::OB,
+            bytes: Vec::new(),
+        })
+    }
+
+    fn read_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.source.seek(SeekFrom::Start(self.start + offset))?;
+        let mut buffer: Vec<u8> = vec![0; len];
+        self.source.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+fn offsets_of(element: &DicomElement) -> Result<ElementOffsets> {
+    element
+        .get_offsets()
+        .ok_or_else(|| dcmelement::error("element has no recorded offsets", element))
+}
@@ -0,0 +1,162 @@
+//! Reading DICOM datasets directly from gzip, zip, and tar containers without extracting them to
+//! disk. Each file within a container is an entry, named by the container's path and the entry's
+//! path within it joined by `ENTRY_SEPARATOR`, e.g. `study.zip!/IMG0001`.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::Path;
+
+use libflate::gzip;
+
+use crate::core::parser::{Parser, ParserBuilder};
+
+/// Separates the path of a container from the path of an entry within it.
+pub const ENTRY_SEPARATOR: &str = "!/";
+
+/// The kinds of sources DICOM datasets are read from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SourceKind {
+    /// A file which is the dataset itself.
+    File,
+    /// A gzip-compressed dataset.
+    Gzip,
+    /// A zip archive of datasets.
+    Zip,
+    /// A tar archive of datasets.
+    Tar,
+    /// A gzip-compressed tar archive of datasets.
+    TarGzip,
+}
+
+impl SourceKind {
+    /// Determines the kind of source from the extension of the path. Paths with an unrecognized
+    /// extension are treated as a `SourceKind::File`.
+    pub fn from_path(path: &Path) -> SourceKind {
+        let filename: String = path
+            .file_name()
+            .map(|filename| filename.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
+            SourceKind::TarGzip
+        } else if filename.ends_with(".tar") {
+            SourceKind::Tar
+        } else if filename.ends_with(".zip") {
+            SourceKind::Zip
+        } else if filename.ends_with(".gz") {
+            SourceKind::Gzip
+        } else {
+            SourceKind::File
+        }
+    }
+
+    /// Whether the source may contain multiple entries.
+    pub fn is_container(self) -> bool {
+        matches!(
+            self,
+            SourceKind::Zip | SourceKind::Tar | SourceKind::TarGzip
+        )
+    }
+}
+
+/// Formats the name of an entry within a container.
+pub fn entry_name(container: &str, entry: &str) -> String {
+    format!("{}{}{}", container, ENTRY_SEPARATOR, entry)
+}
+
+/// Splits the name of an entry into the path of its container and its path within the container.
+/// Names which don't refer to an entry within a container are returned as-is with no entry path.
+pub fn split_entry_name(name: &str) -> (&str, Option<&str>) {
+    match name.find(ENTRY_SEPARATOR) {
+        Some(index) => (&name[..index], Some(&name[index + ENTRY_SEPARATOR.len()..])),
+        None => (name, None),
+    }
+}
+
+/// Opens the file at the given path and calls `visit` with the name and contents of each of its
+/// entries, based on the `SourceKind` of the path. A file which is not a container is visited as
+/// a single entry named by its path. Directories and links within containers are not visited.
+pub fn for_each_entry<E, F>(path: &Path, visit: F) -> Result<(), E>
+where
+    E: From<io::Error>,
+    F: FnMut(&str, &mut dyn Read) -> Result<(), E>,
+{
+    let file: File = File::open(path)?;
+    let name: String = path.display().to_string();
+    for_each_entry_in(SourceKind::from_path(path), &name, file, visit)
+}
+
+/// Calls `visit` with the name and contents of each entry of the given source, see
+/// `for_each_entry`. The `name` is used for the source itself when naming its entries.
+pub fn for_each_entry_in<R, E, F>(
+    kind: SourceKind,
+    name: &str,
+    source: R,
+    mut visit: F,
+) -> Result<(), E>
+where
+    R: Read + Seek,
+    E: From<io::Error>,
+    F: FnMut(&str, &mut dyn Read) -> Result<(), E>,
+{
+    match kind {
+        SourceKind::File => visit(name, &mut BufReader::new(source)),
+        SourceKind::Gzip => {
+            let mut decoder: gzip::Decoder<BufReader<R>> =
+                gzip::Decoder::new(BufReader::new(source))?;
+            visit(name, &mut decoder)
+        }
+        SourceKind::Zip => {
+            let mut archive: zip::ZipArchive<BufReader<R>> =
+                zip::ZipArchive::new(BufReader::new(source)).map_err(io::Error::from)?;
+            for index in 0..archive.len() {
+                let mut entry: zip::read::ZipFile<'_> =
+                    archive.by_index(index).map_err(io::Error::from)?;
+                if entry.is_dir() {
+                    continue;
+                }
+                let entry_name: String = entry_name(name, entry.name());
+                visit(&entry_name, &mut entry)?;
+            }
+            Ok(())
+        }
+        SourceKind::Tar => visit_tar_entries(name, BufReader::new(source), visit),
+        SourceKind::TarGzip => {
+            let decoder: gzip::Decoder<BufReader<R>> = gzip::Decoder::new(BufReader::new(source))?;
+            visit_tar_entries(name, decoder, visit)
+        }
+    }
+}
+
+/// Calls `visit` with a `Parser` for each entry of the file at the given path, built from the
+/// given `ParserBuilder`, see `for_each_entry`.
+pub fn for_each_parser<'dict, E, F>(
+    path: &Path,
+    builder: &ParserBuilder<'dict>,
+    mut visit: F,
+) -> Result<(), E>
+where
+    E: From<io::Error>,
+    F: FnMut(&str, Parser<'dict, &mut dyn Read>) -> Result<(), E>,
+{
+    for_each_entry(path, |name: &str, entry: &mut dyn Read| {
+        visit(name, builder.build(entry))
+    })
+}
+
+fn visit_tar_entries<R, E, F>(name: &str, source: R, mut visit: F) -> Result<(), E>
+where
+    R: Read,
+    E: From<io::Error>,
+    F: FnMut(&str, &mut dyn Read) -> Result<(), E>,
+{
+    let mut archive: tar::Archive<R> = tar::Archive::new(source);
+    for entry in archive.entries()? {
+        let mut entry: tar::Entry<'_, R> = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let entry_name: String = entry_name(name, &entry.path()?.to_string_lossy());
+        visit(&entry_name, &mut entry)?;
+    }
+    Ok(())
+}
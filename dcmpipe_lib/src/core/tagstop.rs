@@ -1,8 +1,15 @@
+use std::cmp::Ordering;
 use std::iter::once;
+use std::sync::Arc;
 
-use crate::core::dcmsqelem::SequenceElement;
+use crate::core::dcmelement::DicomElement;
+use crate::core::dcmsqelem::{SequenceElement, SequencePath};
+use crate::defn::constants::tags;
 use crate::defn::tag::{TagNode, TagPath};
 
+/// A predicate for `TagStop::AfterPredicate`.
+pub type TagStopPredicate = Arc<dyn Fn(&DicomElement) -> bool + Send + Sync>;
+
 /// TagStop specifies the stopping point at which parsing of a DICOM dataset should end.
 #[derive(Clone)]
 pub enum TagStop {
@@ -18,9 +25,36 @@ pub enum TagStop {
     /// is in the middle of an element then bytes from that dataset will continue to be read until
     /// the elment is fully parsed.
     AfterBytePos(u64),
+    /// Read all tag elements up to (but not including) the element at the specified path, which
+    /// may be within sequences. Nodes of the path which specify an item number refer to that item
+    /// of the sequence, otherwise any item of the sequence. Parsing stops at the first element
+    /// positioned at or after the path, so this also stops if the dataset has no such element.
+    BeforeTagPath(TagPath),
+    /// Read all tag elements up to (and including) the element at the specified path, which may be
+    /// within sequences, along with its contents if it's a sequence. A path ending with a sequence
+    /// and item number, such as `ReferencedImageSequence[1]`, stops after the end of that item.
+    AfterTagPath(TagPath),
+    /// Read the specified number of elements at the root of the dataset, including File Meta
+    /// elements, along with the contents of any of those which are sequences.
+    AfterRootElements(usize),
+    /// Read all tag elements up to the end of the specified group. Like `AfterTag` this is
+    /// interpreted only at the root of an object, so sequences within the group are fully read.
+    AfterGroup(u16),
+    /// Read all tag elements up to (and including) the first element the predicate returns `true`
+    /// for, which may be within a sequence. The contents of a matching sequence are not read.
+    /// Elements are passed to the predicate before their values are populated by `SliceParser`.
+    AfterPredicate(TagStopPredicate),
 }
 
 impl TagStop {
+    /// Creates a `TagStop::AfterPredicate` from the given predicate.
+    pub fn after_predicate<F>(predicate: F) -> TagStop
+    where
+        F: Fn(&DicomElement) -> bool + Send + Sync + 'static,
+    {
+        TagStop::AfterPredicate(Arc::new(predicate))
+    }
+
     /// Evaluates the given tagpath against the current sequence path and the last tag read, based
     /// on the given predicate `f`.
     ///
@@ -37,7 +71,7 @@ impl TagStop {
     ///       be true.
     pub(crate) fn eval_tagpath<F>(
         tagpath: &TagPath,
-        current_path: &[SequenceElement],
+        current_path: &SequencePath,
         tag_last_read: u32,
         f: F,
     ) -> bool
@@ -56,4 +90,32 @@ impl TagStop {
             )
             .any(f)
     }
+
+    /// Compares the position of an element within the dataset, given by its `position` path,
+    /// against `tagpath`. This returns `Ordering::Equal` if the element is at `tagpath` or within
+    /// it, otherwise whether the element comes before or after it. The position of an item is the
+    /// start of the item, which comes before its contents.
+    pub(crate) fn cmp_tagpath(tagpath: &TagPath, position: &TagPath) -> Ordering {
+        for (target, current) in tagpath.0.iter().zip(position.0.iter()) {
+            if current.get_tag() == tags::ITEM {
+                return Ordering::Less;
+            }
+            if current.get_tag() != target.get_tag() {
+                return current.get_tag().cmp(&target.get_tag());
+            }
+            match (target.get_item(), current.get_item()) {
+                (Some(target_item), Some(current_item)) if target_item != current_item => {
+                    return current_item.cmp(&target_item);
+                }
+                // the sequence element itself comes before its items
+                (Some(_), None) => return Ordering::Less,
+                _ => {}
+            }
+        }
+        if position.0.len() < tagpath.0.len() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
 }
@@ -0,0 +1,440 @@
+//! Validation of a parsed DICOM dataset against the dictionary and the IOD of its SOP Class.
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::core::datetime::{DicomDate, DicomDateTime, DicomTime};
+use crate::core::dcmelement::DicomElement;
+use crate::core::dcmobject::{DicomNode, DicomRoot};
+use crate::core::numeric::{self, Age, NumericStringMode};
+use crate::defn::constants::tags;
+use crate::defn::dcmdict::DicomDictionary;
+use crate::defn::iod::{IODRef, ModuleUsage};
+use crate::defn::module::ModuleAttribute;
+use crate::defn::tag::{Tag, TagNode, TagPath, TagRef};
+use crate::defn::vl::ValueLength;
+use crate::defn::vm::VM;
+use crate::defn::vr::{self, VRRef};
+
+/// The kind of problem found by validation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ValidationIssueKind {
+    /// A Type 1 or Type 2 attribute of a mandatory module is not present.
+    MissingAttribute,
+    /// A Type 1 or Type 1C attribute is present but has no value.
+    EmptyValue,
+    /// The number of values does not match the VM of the dictionary.
+    VMViolation,
+    /// The VR of the element does not match the VR of the dictionary.
+    VRMismatch,
+    /// The value contains characters or a format not allowed by its VR.
+    InvalidValue,
+    /// The value has an odd length, all values are required to have even length.
+    OddLength,
+    /// The File Meta Information does not agree with the dataset.
+    FileMetaMismatch,
+}
+
+impl ValidationIssueKind {
+    /// A short name for the kind of issue, suitable for machine-readable output.
+    pub fn get_ident(self) -> &'static str {
+        match self {
+            ValidationIssueKind::MissingAttribute => "MissingAttribute",
+            ValidationIssueKind::EmptyValue => "EmptyValue",
+            ValidationIssueKind::VMViolation => "VMViolation",
+            ValidationIssueKind::VRMismatch => "VRMismatch",
+            ValidationIssueKind::InvalidValue => "InvalidValue",
+            ValidationIssueKind::OddLength => "OddLength",
+            ValidationIssueKind::FileMetaMismatch => "FileMetaMismatch",
+        }
+    }
+}
+
+/// A problem found by validation, along with the path to the element it applies to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    pub tagpath: TagPath,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}",
+            self.tagpath.display(),
+            self.kind.get_ident(),
+            self.message
+        )
+    }
+}
+
+/// The result of validating a dataset.
+#[derive(Debug, Default)]
+pub struct ValidationResult {
+    /// The identifier of the IOD the dataset was validated against, if its SOP Class is known
+    pub iod: Option<&'static str>,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationResult {
+    /// Whether validation found no issues.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Validates a dataset which has been parsed into a `DicomRoot`, using the root's dictionary for
+/// looking up tags and the IOD of the dataset's SOP Class.
+///
+/// Module requirements are only checked for modules which are mandatory for the IOD, as the
+/// conditions of conditional modules and attributes are not evaluated. Type 1 and Type 1C
+/// attributes of any of the IOD's modules are required to have a value when present.
+pub fn validate(root: &DicomRoot<'_>) -> ValidationResult {
+    let dict: &dyn DicomDictionary = root.get_dictionary();
+    let mut result: ValidationResult = ValidationResult::default();
+
+    let mut path: Vec<TagNode> = Vec::new();
+    validate_elements(root, dict, &mut path, &mut result.issues);
+
+    validate_file_meta(root, &mut result.issues);
+
+    let iod: Option<IODRef> = root
+        .get_child_by_tag(tags::SOP_CLASS_UID)
+        .and_then(|obj| get_string(obj.get_element()))
+        .and_then(|uid| dict.get_iod_by_sop_class_uid(&uid));
+    if let Some(iod) = iod {
+        result.iod = Some(iod.get_ident());
+        for iod_module in iod.get_modules() {
+            let mandatory: bool = iod_module.usage == ModuleUsage::Mandatory;
+            validate_attributes(
+                root,
+                iod_module.module.get_attributes(),
+                mandatory,
+                &mut path,
+                &mut result.issues,
+            );
+        }
+    }
+
+    // attributes shared by several modules would otherwise be reported once per module
+    let mut reported: HashSet<(ValidationIssueKind, TagPath)> = HashSet::new();
+    result.issues.retain(|issue| match issue.kind {
+        ValidationIssueKind::MissingAttribute | ValidationIssueKind::EmptyValue => {
+            reported.insert((issue.kind, issue.tagpath.clone()))
+        }
+        _ => true,
+    });
+
+    result
+}
+
+/// Checks the attributes of a module are present and have values as required by their type,
+/// recursing into the items of sequences which are present.
+fn validate_attributes(
+    node: &impl DicomNode,
+    attributes: &[ModuleAttribute],
+    mandatory: bool,
+    path: &mut Vec<TagNode>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for attr in attributes {
+        path.push(TagNode::from(attr.tag));
+        match node.get_child_by_tag(attr.tag) {
+            None => {
+                if mandatory && attr.attr_type.is_required() {
+                    issues.push(ValidationIssue {
+                        kind: ValidationIssueKind::MissingAttribute,
+                        tagpath: TagPath(path.clone()),
+                        message: format!("Type {:?} attribute is missing", attr.attr_type),
+                    });
+                }
+            }
+            Some(obj) => {
+                let elem: &DicomElement = obj.get_element();
+                let empty: bool = if elem.is_seq_like() {
+                    obj.get_item_count() == 0
+                } else {
+                    elem.is_empty()
+                };
+                if empty && attr.attr_type.requires_value() {
+                    issues.push(ValidationIssue {
+                        kind: ValidationIssueKind::EmptyValue,
+                        tagpath: TagPath(path.clone()),
+                        message: format!("Type {:?} attribute has no value", attr.attr_type),
+                    });
+                }
+
+                if !attr.items.is_empty() {
+                    for index in 1..=obj.get_item_count() {
+                        if let Some(item) = obj.get_item_by_index(index) {
+                            if let Some(last) = path.last_mut() {
+                                *last.get_item_mut() = Some(index);
+                            }
+                            validate_attributes(item, attr.items, mandatory, path, issues);
+                        }
+                    }
+                }
+            }
+        }
+        path.pop();
+    }
+}
+
+/// Checks each element of the dataset against the dictionary and the rules of its VR.
+fn validate_elements(
+    node: &impl DicomNode,
+    dict: &dyn DicomDictionary,
+    path: &mut Vec<TagNode>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for (tag, obj) in node.iter_child_nodes() {
+        // delimiters and items are structural and have no values to validate
+        if (tag >> 16) == 0xFFFE {
+            continue;
+        }
+
+        path.push(TagNode::from(*tag));
+        validate_element(obj.get_element(), dict, path, issues);
+
+        for index in 1..=obj.get_item_count() {
+            if let Some(item) = obj.get_item_by_index(index) {
+                if let Some(last) = path.last_mut() {
+                    *last.get_item_mut() = Some(index);
+                }
+                validate_elements(item, dict, path, issues);
+            }
+        }
+        if let Some(last) = path.last_mut() {
+            *last.get_item_mut() = None;
+        }
+        if obj.get_child_count() > 0 {
+            validate_elements(obj, dict, path, issues);
+        }
+        path.pop();
+    }
+}
+
+fn validate_element(
+    elem: &DicomElement,
+    dict: &dyn DicomDictionary,
+    path: &[TagNode],
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut add_issue = |kind: ValidationIssueKind, message: String| {
+        issues.push(ValidationIssue {
+            kind,
+            tagpath: TagPath(path.to_vec()),
+            message,
+        });
+    };
+
+    if let ValueLength::Explicit(len) = elem.vl {
+        if len % 2 != 0 {
+            add_issue(
+                ValidationIssueKind::OddLength,
+                format!("value has odd length {}", len),
+            );
+        }
+    }
+
+    let tag: Option<TagRef> = dict.get_tag_by_number(elem.tag);
+    if let Some(tag) = tag {
+        if let Some(dict_vr) = tag.implicit_vr {
+            if elem.get_ts().is_explicit_vr() && !is_compatible_vr(elem.vr, dict_vr) {
+                add_issue(
+                    ValidationIssueKind::VRMismatch,
+                    format!(
+                        "VR is {} but dictionary defines {} as {}",
+                        elem.vr.ident, tag.ident, dict_vr.ident
+                    ),
+                );
+            }
+        }
+    }
+
+    // the values of bulk data elements are not available to check
+    if elem.is_seq_like() || elem.is_empty() || elem.get_bulk_data().is_some() {
+        return;
+    }
+
+    if elem.vr.is_character_string {
+        let values: Vec<String> = match Vec::<String>::try_from(elem) {
+            Ok(values) => values,
+            Err(e) => {
+                add_issue(ValidationIssueKind::InvalidValue, format!("{}", e));
+                return;
+            }
+        };
+
+        for value in values.iter() {
+            if let Some(problem) = check_string_value(elem.vr, value) {
+                add_issue(
+                    ValidationIssueKind::InvalidValue,
+                    format!("\"{}\" is not a valid {}: {}", value, elem.vr.ident, problem),
+                );
+            }
+        }
+
+        if let Some(tag) = tag {
+            if !elem.vr.allows_backslash_text_value && !is_vm_valid(tag, values.len()) {
+                add_issue(
+                    ValidationIssueKind::VMViolation,
+                    format!("{} values does not satisfy VM {:?}", values.len(), tag.vm),
+                );
+            }
+        }
+    } else if let (Some(tag), Some(size)) = (tag, get_binary_value_size(elem.vr)) {
+        let count: usize = elem.get_data().len() / size;
+        if !is_vm_valid(tag, count) {
+            add_issue(
+                ValidationIssueKind::VMViolation,
+                format!("{} values does not satisfy VM {:?}", count, tag.vm),
+            );
+        }
+    }
+}
+
+/// Checks the File Meta Information refers to the same SOP Class and Instance as the dataset.
+fn validate_file_meta(root: &DicomRoot<'_>, issues: &mut Vec<ValidationIssue>) {
+    let pairs: [(u32, u32); 2] = [
+        (tags::MEDIA_STORAGE_SOP_CLASS_UID, tags::SOP_CLASS_UID),
+        (tags::MEDIA_STORAGE_SOP_INSTANCE_UID, tags::SOP_INSTANCE_UID),
+    ];
+    for (meta_tag, ds_tag) in pairs.iter() {
+        let meta_val: Option<String> = root
+            .get_child_by_tag(*meta_tag)
+            .and_then(|obj| get_string(obj.get_element()));
+        let ds_val: Option<String> = root
+            .get_child_by_tag(*ds_tag)
+            .and_then(|obj| get_string(obj.get_element()));
+        if let (Some(meta_val), Some(ds_val)) = (meta_val, ds_val) {
+            if meta_val != ds_val {
+                issues.push(ValidationIssue {
+                    kind: ValidationIssueKind::FileMetaMismatch,
+                    tagpath: TagPath::from(*meta_tag),
+                    message: format!(
+                        "\"{}\" does not match {} \"{}\"",
+                        meta_val,
+                        Tag::format_tag_to_display(*ds_tag),
+                        ds_val
+                    ),
+                });
+            }
+        }
+    }
+}
+
+fn get_string(elem: &DicomElement) -> Option<String> {
+    String::try_from(elem).ok()
+}
+
+/// Some attributes are defined with multiple VRs, such as `US or SS` and `OB or OW`, in which case
+/// the dictionary only lists the first. The VR `UN` is allowed for any attribute.
+fn is_compatible_vr(elem_vr: VRRef, dict_vr: VRRef) -> bool {
+    if elem_vr == dict_vr || elem_vr == &vr::UN {
+        return true;
+    }
+    let either = |a: VRRef, b: VRRef| {
+        (elem_vr == a && dict_vr == b) || (elem_vr == b && dict_vr == a)
+    };
+    either(&vr::US, &vr::SS)
+        || either(&vr::OB, &vr::OW)
+        || either(&vr::US, &vr::OW)
+        || either(&vr::SS, &vr::OW)
+}
+
+/// The size of each value for VRs whose VM is counted by the number of fixed-size values. The
+/// `O*` VRs always have a VM of 1 and are not included.
+fn get_binary_value_size(vr: VRRef) -> Option<usize> {
+    if vr == &vr::SS || vr == &vr::US {
+        Some(2)
+    } else if vr == &vr::SL || vr == &vr::UL || vr == &vr::FL || vr == &vr::AT {
+        Some(4)
+    } else if vr == &vr::FD || vr == &vr::SV || vr == &vr::UV {
+        Some(8)
+    } else {
+        None
+    }
+}
+
+fn is_vm_valid(tag: TagRef, count: usize) -> bool {
+    let count: u32 = count as u32;
+    match tag.vm {
+        VM::Distinct(num) => count == *num,
+        VM::AtLeast(num) => count >= *num,
+        VM::AtMost(num) => count >= 1 && count <= *num,
+        VM::MultipleOf(num) => *num > 0 && count >= *num && count % *num == 0,
+        VM::OneOrMore => count >= 1,
+    }
+}
+
+/// Checks a single value of a character string against the format of its VR, see Part 5, Ch 6.2.
+/// Returns a description of the problem if the value is invalid.
+fn check_string_value(vr: VRRef, value: &str) -> Option<&'static str> {
+    // values may legitimately be empty within a multi-valued element
+    if value.is_empty() {
+        return None;
+    }
+
+    if vr == &vr::CS {
+        if value.len() > 16 {
+            return Some("exceeds 16 characters");
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == ' ' || c == '_')
+        {
+            return Some("only uppercase letters, digits, space and underscore are allowed");
+        }
+    } else if vr == &vr::UI {
+        if value.len() > 64 {
+            return Some("exceeds 64 characters");
+        }
+        if !value.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Some("only digits and periods are allowed");
+        }
+    } else if vr == &vr::DA {
+        if !is_valid_date(value) {
+            return Some("expected the format YYYYMMDD");
+        }
+    } else if vr == &vr::TM {
+        if !is_valid_time(value) {
+            return Some("expected the format HHMMSS.FFFFFF");
+        }
+    } else if vr == &vr::DT {
+        if value.parse::<DicomDateTime>().is_err() {
+            return Some("expected the format YYYYMMDDHHMMSS.FFFFFF&ZZXX");
+        }
+    } else if vr == &vr::AS {
+        if let Err(e) = value.parse::<Age>() {
+            return Some(e.reason);
+        }
+    } else if vr == &vr::DS {
+        if let Err(e) = numeric::parse_decimal_string(value, NumericStringMode::Strict) {
+            return Some(e.reason);
+        }
+    } else if vr == &vr::IS {
+        if let Err(e) = numeric::parse_integer_string(value, NumericStringMode::Strict) {
+            return Some(e.reason);
+        }
+    } else if vr == &vr::AE && value.chars().any(char::is_control) {
+        return Some("control characters are not allowed");
+    }
+
+    None
+}
+
+/// Whether the value is a full `YYYYMMDD` date. Partial dates and the ACR-NEMA `YYYY.MM.DD` format
+/// accepted by `DicomDate` are not valid for DA.
+fn is_valid_date(value: &str) -> bool {
+    value.len() == 8 && value.parse::<DicomDate>().is_ok()
+}
+
+/// Whether the value is a `HH[MM[SS[.F{1-6}]]]` time. The ACR-NEMA `HH:MM:SS` format accepted by
+/// `DicomTime` is not valid for TM.
+fn is_valid_time(value: &str) -> bool {
+    !value.contains(':') && value.parse::<DicomTime>().is_ok()
+}
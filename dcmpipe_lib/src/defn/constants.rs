@@ -3,11 +3,27 @@
 /// The tags necessary for parsing a DICOM dataset.
 pub mod tags {
     pub const FILE_META_INFORMATION_GROUP_LENGTH: u32 = 0x0002_0000;
+    pub const MEDIA_STORAGE_SOP_CLASS_UID: u32 = 0x0002_0002;
+    pub const MEDIA_STORAGE_SOP_INSTANCE_UID: u32 = 0x0002_0003;
     pub const TRANSFER_SYNTAX_UID: u32 = 0x0002_0010;
     pub const FILE_META_GROUP_END: u32 = 0x0002_FFFF;
 
     pub const SPECIFIC_CHARACTER_SET: u32 = 0x0008_0005;
+    pub const SOP_CLASS_UID: u32 = 0x0008_0016;
     pub const SOP_INSTANCE_UID: u32 = 0x0008_0018;
+    pub const CODE_VALUE: u32 = 0x0008_0100;
+    pub const CODING_SCHEME_DESIGNATOR: u32 = 0x0008_0102;
+    pub const CODING_SCHEME_VERSION: u32 = 0x0008_0103;
+    pub const CODE_MEANING: u32 = 0x0008_0104;
+    pub const LONG_CODE_VALUE: u32 = 0x0008_0119;
+    pub const URN_CODE_VALUE: u32 = 0x0008_0120;
+    pub const TIMEZONE_OFFSET_FROM_UTC: u32 = 0x0008_0201;
+
+    pub const SAMPLES_PER_PIXEL: u32 = 0x0028_0002;
+    pub const NUMBER_OF_FRAMES: u32 = 0x0028_0008;
+    pub const ROWS: u32 = 0x0028_0010;
+    pub const COLUMNS: u32 = 0x0028_0011;
+    pub const BITS_ALLOCATED: u32 = 0x0028_0100;
 
     pub const FLOAT_PIXEL_DATA: u32 = 0x7FE0_0008;
     pub const DOUBLE_PIXEL_DATA: u32 = 0x7FE0_0009;
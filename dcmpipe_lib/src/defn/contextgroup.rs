@@ -0,0 +1,85 @@
+//! Context Groups
+
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+pub type ContextGroupRef = &'static ContextGroup;
+
+/// A coded concept defined by a Context Group.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Code {
+    /// The Coding Scheme Designator, `SCT`, `DCM`, etc.
+    pub scheme: &'static str,
+    /// The Code Value, unique within the coding scheme
+    pub value: &'static str,
+    /// The Code Meaning
+    pub meaning: &'static str,
+}
+
+/// Context Group
+/// Part 16, Ch 6:
+/// A set of coded concepts defined by a Context ID (CID) which are to be used in a specific
+/// context. Context Groups may include the concepts of other Context Groups.
+#[derive(Eq)]
+pub struct ContextGroup {
+    /// Some identifier or name, useful for lookup (no spaces - matches definition/name in code)
+    pub ident: &'static str,
+    /// The Context ID, `CID 4`
+    pub cid: u32,
+    /// Descriptive name
+    pub name: &'static str,
+    /// The concepts defined directly by this Context Group
+    pub codes: &'static [Code],
+    /// Other Context Groups whose concepts are included in this one
+    pub includes: &'static [ContextGroupRef],
+}
+
+impl ContextGroup {
+    pub fn get_ident(&self) -> &'static str {
+        self.ident
+    }
+
+    pub fn get_cid(&self) -> u32 {
+        self.cid
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Look up a concept of this Context Group, including those of included Context Groups.
+    /// Code values are compared exactly, the coding scheme designator is compared ignoring case.
+    pub fn get_code(&self, scheme: &str, value: &str) -> Option<&'static Code> {
+        self.codes
+            .iter()
+            .find(|code| code.value == value && code.scheme.eq_ignore_ascii_case(scheme))
+            .or_else(|| {
+                self.includes
+                    .iter()
+                    .find_map(|include| include.get_code(scheme, value))
+            })
+    }
+
+    /// Whether this Context Group, or those it includes, defines the given concept.
+    pub fn contains(&self, scheme: &str, value: &str) -> bool {
+        self.get_code(scheme, value).is_some()
+    }
+}
+
+impl PartialEq for ContextGroup {
+    fn eq(&self, other: &ContextGroup) -> bool {
+        self.cid.eq(&other.cid)
+    }
+}
+
+impl Hash for ContextGroup {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cid.hash(state);
+    }
+}
+
+impl Debug for ContextGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CID {} {}", self.cid, self.ident)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::defn::contextgroup::ContextGroupRef;
+use crate::defn::iod::IODRef;
+use crate::defn::module::ModuleRef;
 use crate::defn::tag::TagRef;
 use crate::defn::ts::TSRef;
 use crate::defn::uid::UIDRef;
@@ -16,4 +19,26 @@ pub trait DicomDictionary {
     fn get_uid_by_uid(&self, uid: &str) -> Option<UIDRef>;
     /// Look up a `UID` definition by its name
     fn get_uid_by_name(&self, name: &str) -> Option<UIDRef>;
+    /// Look up an `IOD` definition by the SOP Class UID of the instances it defines. Dictionaries
+    /// without IOD definitions need not implement this or the other IOD and module lookups.
+    fn get_iod_by_sop_class_uid(&self, _uid: &str) -> Option<IODRef> {
+        None
+    }
+    /// Look up an `IOD` definition by its name
+    fn get_iod_by_name(&self, _name: &str) -> Option<IODRef> {
+        None
+    }
+    /// Look up a `Module` definition by its name
+    fn get_module_by_name(&self, _name: &str) -> Option<ModuleRef> {
+        None
+    }
+    /// Look up a `ContextGroup` definition by its Context ID. Dictionaries without context groups
+    /// need not implement this or `get_context_group_by_name`.
+    fn get_context_group_by_cid(&self, _cid: u32) -> Option<ContextGroupRef> {
+        None
+    }
+    /// Look up a `ContextGroup` definition by its name
+    fn get_context_group_by_name(&self, _name: &str) -> Option<ContextGroupRef> {
+        None
+    }
 }
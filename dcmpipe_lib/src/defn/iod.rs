@@ -0,0 +1,84 @@
+//! Information Object Definitions
+
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+use crate::defn::module::ModuleRef;
+
+pub type IODRef = &'static IOD;
+
+/// Module Usage
+/// Part 3, Ch A.1.3:
+/// Whether a Module is required to be present in an instance of the IOD.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ModuleUsage {
+    /// Mandatory (M)
+    Mandatory,
+    /// Conditional (C), required only under conditions specified by the IOD
+    Conditional,
+    /// User Option (U)
+    UserOption,
+}
+
+/// A Module as it is used by an IOD.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IODModule {
+    /// The Information Entity the Module is part of: Patient, Study, Series, etc.
+    pub ie: &'static str,
+    /// The Module
+    pub module: ModuleRef,
+    /// Whether the Module is mandatory, conditional, or optional for the IOD
+    pub usage: ModuleUsage,
+}
+
+/// Information Object Definition
+/// Part 3, Ch 6:
+/// An object-oriented abstract data model used to specify information about Real-World Objects.
+/// Composite IODs are made up of Modules grouped by the Information Entity they describe.
+#[derive(Eq)]
+pub struct IOD {
+    /// Some identifier or name, useful for lookup (no spaces - matches definition/name in code)
+    pub ident: &'static str,
+    /// Descriptive name
+    pub name: &'static str,
+    /// The section of Part 3 which defines the Modules of this IOD, `A.3`
+    pub reference: &'static str,
+    /// The Modules of this IOD
+    pub modules: &'static [IODModule],
+}
+
+impl IOD {
+    pub fn get_ident(&self) -> &'static str {
+        self.ident
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_reference(&self) -> &'static str {
+        self.reference
+    }
+
+    pub fn get_modules(&self) -> &'static [IODModule] {
+        self.modules
+    }
+}
+
+impl PartialEq for IOD {
+    fn eq(&self, other: &IOD) -> bool {
+        self.ident.eq(other.ident)
+    }
+}
+
+impl Hash for IOD {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ident.hash(state);
+    }
+}
+
+impl Debug for IOD {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.ident, self.reference)
+    }
+}
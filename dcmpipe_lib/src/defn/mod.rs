@@ -1,5 +1,8 @@
 pub(crate) mod constants;
+pub mod contextgroup;
 pub mod dcmdict;
+pub mod iod;
+pub mod module;
 pub mod tag;
 pub mod ts;
 pub mod uid;
@@ -0,0 +1,112 @@
+//! Modules
+
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+pub type ModuleRef = &'static Module;
+
+/// Attribute Type
+/// Part 3, Ch 5.4 & Part 5, Ch 7.4:
+/// Whether an Attribute is required to be present in a Data Set and whether it's required to have
+/// a value. The conditional types are only required under conditions specified by the Module and
+/// otherwise behave as Type 3.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AttributeType {
+    /// Required to be present and have a value.
+    Type1,
+    /// Required to be present and have a value if the Module's condition is met.
+    Type1C,
+    /// Required to be present but may be zero-length.
+    Type2,
+    /// Required to be present, possibly zero-length, if the Module's condition is met.
+    Type2C,
+    /// Optional.
+    Type3,
+}
+
+impl AttributeType {
+    /// Whether this attribute type is required to be present irrespective of any conditions.
+    pub fn is_required(self) -> bool {
+        self == AttributeType::Type1 || self == AttributeType::Type2
+    }
+
+    /// Whether this attribute type requires a non-empty value when present.
+    pub fn requires_value(self) -> bool {
+        self == AttributeType::Type1 || self == AttributeType::Type1C
+    }
+
+    /// Whether the requirements of this attribute type depend on a condition of the Module.
+    pub fn is_conditional(self) -> bool {
+        self == AttributeType::Type1C || self == AttributeType::Type2C
+    }
+}
+
+/// An Attribute of a Module. Attributes which are sequences list the attributes of their items.
+/// Macros included by the Module in the DICOM standard are expanded in-place.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ModuleAttribute {
+    /// The tag of the attribute. Attributes defined for a repeating group, such as `(60xx,0010)`,
+    /// use the first group of the range.
+    pub tag: u32,
+    /// The type of the attribute within this Module.
+    pub attr_type: AttributeType,
+    /// If the attribute is a sequence, these are the attributes of each of its items.
+    pub items: &'static [ModuleAttribute],
+}
+
+/// Module
+/// Part 3, Ch 6:
+/// A set of Attributes within an Information Entity or normalized IOD which are logically
+/// related to each other.
+#[derive(Eq)]
+pub struct Module {
+    /// Some identifier or name, useful for lookup (no spaces - matches definition/name in code)
+    pub ident: &'static str,
+    /// Descriptive name
+    pub name: &'static str,
+    /// The section of Part 3 which defines this Module, `C.7.1.1`
+    pub reference: &'static str,
+    /// The top-level Attributes of the Module
+    pub attributes: &'static [ModuleAttribute],
+}
+
+impl Module {
+    pub fn get_ident(&self) -> &'static str {
+        self.ident
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_reference(&self) -> &'static str {
+        self.reference
+    }
+
+    pub fn get_attributes(&self) -> &'static [ModuleAttribute] {
+        self.attributes
+    }
+
+    /// Look up a top-level Attribute of this Module
+    pub fn get_attribute(&self, tag: u32) -> Option<&'static ModuleAttribute> {
+        self.attributes.iter().find(|attr| attr.tag == tag)
+    }
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Module) -> bool {
+        self.ident.eq(other.ident)
+    }
+}
+
+impl Hash for Module {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ident.hash(state);
+    }
+}
+
+impl Debug for Module {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.ident, self.reference)
+    }
+}
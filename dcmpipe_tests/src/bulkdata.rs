@@ -0,0 +1,175 @@
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::rc::Rc;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::bulkdata::BulkDataRef;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::error::ParseError;
+use dcmpipe_lib::core::parser::{Parser, ParserBuilder, Result};
+
+use crate::encode::{builder, explicit_vr_le_element, specific_character_set};
+
+const PIXEL_DATA_LENGTH: usize = 200;
+
+fn pixel_data_value() -> Vec<u8> {
+    (0..PIXEL_DATA_LENGTH).map(|i| i as u8).collect()
+}
+
+/// A dataset with elements on both sides of a large Pixel Data element.
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &pixel_data_value(),
+    ));
+    dataset.extend(explicit_vr_le_element(0x7FE1_0010, b"LO", b"TRAILER "));
+    dataset
+}
+
+fn parse_dataset(dataset: &[u8], threshold: Option<u32>) -> Result<Vec<DicomElement>> {
+    let mut builder: ParserBuilder<'_> = builder();
+    if let Some(threshold) = threshold {
+        builder = builder.bulk_data_threshold(threshold);
+    }
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder.build(Cursor::new(dataset));
+    let elements: Vec<DicomElement> = parser.by_ref().collect::<Result<Vec<DicomElement>>>()?;
+    assert_eq!(parser.get_bytes_read(), dataset.len() as u64);
+    Ok(elements)
+}
+
+#[test]
+fn test_bulk_data_is_skipped() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let elements: Vec<DicomElement> = parse_dataset(&dataset, Some(100))?;
+    assert_eq!(elements.len(), 4);
+
+    let name: &DicomElement = &elements[1];
+    assert!(name.get_bulk_data().is_none());
+    assert_eq!(String::try_from(name)?, "Doe^John");
+
+    let pixel_data: &DicomElement = &elements[2];
+    assert_eq!(pixel_data.tag, tags::PixelData.tag);
+    let bulk_data: BulkDataRef = pixel_data.get_bulk_data().expect("should be bulk data");
+    assert_eq!(bulk_data.length, PIXEL_DATA_LENGTH as u32);
+    assert_eq!(
+        bulk_data.offset as usize,
+        dataset.len() - 16 - PIXEL_DATA_LENGTH
+    );
+    assert!(pixel_data.get_data().is_empty());
+    assert!(!pixel_data.is_empty());
+    assert!(pixel_data.parse_value().is_err());
+
+    // elements after the bulk data are still parsed
+    assert_eq!(String::try_from(&elements[3])?, "TRAILER");
+    Ok(())
+}
+
+#[test]
+fn test_bulk_data_load() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let mut elements: Vec<DicomElement> = parse_dataset(&dataset, Some(100))?;
+    let mut pixel_data: DicomElement = elements.remove(2);
+
+    let mut source: Cursor<&[u8]> = Cursor::new(&dataset);
+    let bulk_data: BulkDataRef = pixel_data.get_bulk_data().expect("should be bulk data");
+    assert_eq!(bulk_data.load(&mut source)?, pixel_data_value());
+
+    pixel_data.load_bulk_data(&mut source)?;
+    assert!(pixel_data.get_bulk_data().is_none());
+    assert_eq!(pixel_data.get_data(), &pixel_data_value());
+    assert!(pixel_data.parse_value().is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_bulk_data_threshold() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+
+    // values are read when no threshold is set or when they're within the threshold
+    for threshold in [None, Some(PIXEL_DATA_LENGTH as u32)].iter() {
+        let elements: Vec<DicomElement> = parse_dataset(&dataset, *threshold)?;
+        assert!(elements.iter().all(|elem| elem.get_bulk_data().is_none()));
+        assert_eq!(elements[2].get_data(), &pixel_data_value());
+    }
+
+    // elements which affect parsing are always read
+    let elements: Vec<DicomElement> = parse_dataset(&dataset, Some(0))?;
+    assert!(elements[0].get_bulk_data().is_none());
+    assert!(elements[1].get_bulk_data().is_some());
+    assert!(elements[2].get_bulk_data().is_some());
+    Ok(())
+}
+
+/// A seekable dataset which counts the bytes read from it.
+struct CountingCursor {
+    cursor: Cursor<Vec<u8>>,
+    bytes_read: Rc<Cell<usize>>,
+}
+
+impl Read for CountingCursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read: usize = self.cursor.read(buf)?;
+        self.bytes_read.set(self.bytes_read.get() + read);
+        Ok(read)
+    }
+}
+
+impl Seek for CountingCursor {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.cursor.seek(pos)
+    }
+}
+
+fn parse_seekable(dataset: Vec<u8>, buffsize: usize) -> (Result<Vec<DicomElement>>, usize) {
+    let bytes_read: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let parser: Parser<'_, CountingCursor> = builder()
+        .buffsize(buffsize)
+        .bulk_data_threshold(100)
+        .build_seekable(CountingCursor {
+            cursor: Cursor::new(dataset),
+            bytes_read: bytes_read.clone(),
+        });
+    let elements: Result<Vec<DicomElement>> = parser.collect();
+    (elements, bytes_read.get())
+}
+
+#[test]
+fn test_bulk_data_is_seeked_past() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+
+    // without a buffer the value is never read from the dataset
+    let (elements, bytes_read) = parse_seekable(dataset.clone(), 0);
+    let elements: Vec<DicomElement> = elements?;
+    assert_eq!(bytes_read, dataset.len() - PIXEL_DATA_LENGTH);
+    assert!(elements[2].get_bulk_data().is_some());
+    assert_eq!(String::try_from(&elements[3])?, "TRAILER");
+
+    // the buffered part of the value is consumed and the rest is seeked past
+    let (elements, _) = parse_seekable(dataset, 64);
+    let elements: Vec<DicomElement> = elements?;
+    assert!(elements[2].get_bulk_data().is_some());
+    assert_eq!(String::try_from(&elements[3])?, "TRAILER");
+    Ok(())
+}
+
+#[test]
+fn test_bulk_data_seeked_past_end() {
+    let mut dataset: Vec<u8> = create_dataset();
+    dataset.truncate(dataset.len() - 32);
+
+    let (elements, _) = parse_seekable(dataset, 0);
+    match elements {
+        Err(ParseError::DetailedIOError { source, .. }) => {
+            assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof)
+        }
+        other => panic!("Expected unexpected EOF error: {:?}", other.err()),
+    }
+}
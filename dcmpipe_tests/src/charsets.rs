@@ -1,16 +1,24 @@
 use std::convert::{TryFrom, TryInto};
 use std::fs::File;
+use std::io::Cursor;
 
 use encoding::all;
+use encoding::{DecoderTrap, EncoderTrap};
 
 use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
 use dcmpipe_dict::dict::tags;
-use dcmpipe_lib::core::charset::CSRef;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::{self, CSRef, UnsupportedCharsetPolicy};
 use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
 use dcmpipe_lib::core::dcmobject::{DicomNode, DicomObject, DicomRoot};
 use dcmpipe_lib::core::parser::util::parse_into_object;
+use dcmpipe_lib::core::parser::error::ParseError;
 use dcmpipe_lib::core::parser::{Parser, ParserBuilder, Result};
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr;
 
+use crate::encode::{builder, explicit_vr_le_element};
 use crate::parse_file;
 
 /// This DICOMDIR has sequences with nested elements that change charsets
@@ -54,9 +62,9 @@ fn test_parse_nested_charset_values() -> Result<()> {
     test_nested_charset(
         &dcmroot,
         33,
-        all::WINDOWS_1252,
+        iso2022_cs(&["", "ISO 2022 IR 149"]),
         "ISO 2022 IR 149",
-        "Hong^Gildong=\u{1b}$)Cûó^\u{1b}$)CÑÎÔ×=\u{1b}$)CÈ«^\u{1b}$)C±æµ¿",
+        "Hong^Gildong=洪^吉洞=홍^길동",
     )?;
 
     Ok(())
@@ -152,7 +160,7 @@ fn test_scs_h31() -> Result<()> {
     test_scs_file(
         true,
         "./fixtures/dclunie/charsettests/SCSH31",
-        all::ISO_2022_JP,
+        iso2022_cs(&["", "ISO 2022 IR 87"]),
         "ISO 2022 IR 87",
         "Yamada^Tarou=山田^太郎=やまだ^たろう",
     )
@@ -163,9 +171,9 @@ fn test_scs_h32() -> Result<()> {
     test_scs_file(
         true,
         "./fixtures/dclunie/charsettests/SCSH32",
-        all::ISO_2022_JP,
+        iso2022_cs(&["ISO 2022 IR 13", "ISO 2022 IR 87"]),
         "ISO 2022 IR 13",
-        "",
+        "ﾔﾏﾀﾞ^ﾀﾛｳ=山田^太郎=やまだ^たろう",
     )
 }
 
@@ -185,9 +193,9 @@ fn test_scs_i12() -> Result<()> {
     test_scs_file(
         true,
         "./fixtures/dclunie/charsettests/SCSI2",
-        all::WINDOWS_1252,
+        iso2022_cs(&["", "ISO 2022 IR 149"]),
         "ISO 2022 IR 149",
-        "Hong^Gildong=\u{1b}$)Cûó^\u{1b}$)CÑÎÔ×=\u{1b}$)CÈ«^\u{1b}$)C±æµ¿",
+        "Hong^Gildong=洪^吉洞=홍^길동",
     )
 }
 
@@ -252,3 +260,181 @@ fn test_scs_file(with_std: bool, path: &str, cs: CSRef, scs: &str, pn: &str) ->
 
     Ok(())
 }
+
+/// Looks up the character set for the given values of Specific Character Set
+fn iso2022_cs(values: &[&str]) -> CSRef {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    charset::lookup_specific_character_set(&values).expect("Should resolve character set")
+}
+
+/// Decodes the given Person Name value with the character set of the given Specific Character
+/// Set values, and checks that encoding the result produces the original bytes.
+fn test_iso2022_pn(scs: &[&str], bytes: &[u8], pn: &str) -> Result<()> {
+    let cs: CSRef = iso2022_cs(scs);
+    let elem: DicomElement = DicomElement::new(
+        tags::PatientsName.tag,
+        &vr::PN,
+        ValueLength::Explicit(bytes.len() as u32),
+        &ts::ExplicitVRLittleEndian,
+        cs,
+        bytes.to_vec(),
+        SequencePath::new(),
+    );
+    assert_eq!(String::try_from(&elem)?, pn);
+    assert_eq!(
+        cs.encode(pn, EncoderTrap::Strict).expect("Should encode"),
+        bytes
+    );
+    Ok(())
+}
+
+/// PS3.5 Annex H.3.1, Japanese using JIS X 0208 with the default repertoire
+#[test]
+fn test_iso2022_japanese_h31() -> Result<()> {
+    test_iso2022_pn(
+        &["", "ISO 2022 IR 87"],
+        b"Yamada^Tarou=\x1b$B;3ED\x1b(B^\x1b$BB@O:\x1b(B=\
+            \x1b$B$d$^$@\x1b(B^\x1b$B$?$m$&\x1b(B",
+        "Yamada^Tarou=山田^太郎=やまだ^たろう",
+    )
+}
+
+/// PS3.5 Annex H.3.2, Japanese using JIS X 0208 with JIS X 0201 as the initial repertoire
+#[test]
+fn test_iso2022_japanese_h32() -> Result<()> {
+    test_iso2022_pn(
+        &["ISO 2022 IR 13", "ISO 2022 IR 87"],
+        b"\xd4\xcf\xc0\xde^\xc0\xdb\xb3=\x1b$B;3ED\x1b(J^\x1b$BB@O:\x1b(J=\
+            \x1b$B$d$^$@\x1b(J^\x1b$B$?$m$&\x1b(J",
+        "ﾔﾏﾀﾞ^ﾀﾛｳ=山田^太郎=やまだ^たろう",
+    )
+}
+
+/// PS3.5 Annex I.2, Korean using KS X 1001 in G1
+#[test]
+fn test_iso2022_korean_i2() -> Result<()> {
+    test_iso2022_pn(
+        &["", "ISO 2022 IR 149"],
+        b"Hong^Gildong=\x1b$)C\xfb\xf3^\x1b$)C\xd1\xce\xd4\xd7=\
+            \x1b$)C\xc8\xab^\x1b$)C\xb1\xe6\xb5\xbf",
+        "Hong^Gildong=洪^吉洞=홍^길동",
+    )
+}
+
+/// PS3.5 Annex J.3, Chinese using GB 2312 in G1
+#[test]
+fn test_iso2022_chinese_j3() -> Result<()> {
+    test_iso2022_pn(
+        &["", "ISO 2022 IR 58"],
+        b"Zhang^XiaoDong=\x1b$)A\xd5\xc5^\x1b$)A\xd0\xa1\xb6\xab=",
+        "Zhang^XiaoDong=张^小东=",
+    )
+}
+
+/// Single-byte character sets with code extensions switch G1 between repertoires
+#[test]
+fn test_iso2022_single_byte_extensions() -> Result<()> {
+    test_iso2022_pn(
+        &["ISO 2022 IR 100", "ISO 2022 IR 126"],
+        b"Buc^J\xe9r\xf4me=\x1b-F\xc4\xe9\xef\xed\xf5\xf3\xe9\xef\xf2\x1b-A",
+        "Buc^Jérôme=Διονυσιος",
+    )
+}
+
+/// Escape sequences of repertoires not present in Specific Character Set are still decoded
+#[test]
+fn test_iso2022_decode_invalid_sequences() {
+    let cs: CSRef = iso2022_cs(&["", "ISO 2022 IR 87"]);
+    assert_eq!(cs.name(), "ISO 2022 IR 6\\ISO 2022 IR 87");
+
+    // G1 bytes without any G1 set designated
+    assert!(cs.decode(b"Yamada\xc0", DecoderTrap::Strict).is_err());
+    // an incomplete two-byte character
+    assert!(cs.decode(b"\x1b$B;", DecoderTrap::Strict).is_err());
+    // an unknown escape sequence
+    assert!(cs.decode(b"\x1b(Z", DecoderTrap::Strict).is_err());
+    assert_eq!(
+        cs.decode(b"\x1b$B;3\xc0", DecoderTrap::Replace)
+            .expect("Should decode with replacement"),
+        "山\u{fffd}"
+    );
+}
+
+/// Values listing the same code extensions in a different order, or with variations of the Defined
+/// Terms, share a character set
+#[test]
+fn test_iso2022_charsets_are_shared() {
+    let cs: CSRef = iso2022_cs(&["", "ISO 2022 IR 100", "ISO 2022 IR 87"]);
+    assert_eq!(cs.name(), "ISO 2022 IR 6\\ISO 2022 IR 100\\ISO 2022 IR 87");
+
+    let same: CSRef = iso2022_cs(&["", "ISO 2022 IR 87", "ISO_2022 IR 100", "ISO 2022 IR 87"]);
+    assert!(std::ptr::eq(
+        cs as *const _ as *const u8,
+        same as *const _ as *const u8
+    ));
+
+    // the first value is the initial state, so it's significant
+    let other: CSRef = iso2022_cs(&["ISO 2022 IR 87", "ISO 2022 IR 100"]);
+    assert_eq!(other.name(), "ISO 2022 IR 87\\ISO 2022 IR 100");
+}
+
+/// Parses a dataset with an unsupported Specific Character Set using the given policy, returning
+/// the Patient's Name element
+fn parse_unsupported_charset(policy: UnsupportedCharsetPolicy) -> Result<DicomElement> {
+    let mut dataset: Vec<u8> =
+        explicit_vr_le_element(tags::SpecificCharacterSet.tag, b"CS", b"ISO_IR 999");
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Caf\xe9^Ren\xe9 ",
+    ));
+
+    let parser: Parser<'_, Cursor<Vec<u8>>> = builder()
+        .unsupported_charset(policy)
+        .build(Cursor::new(dataset));
+    let mut elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+    Ok(elements.pop().expect("Should have PN"))
+}
+
+#[test]
+fn test_unsupported_charset_strict() {
+    match parse_unsupported_charset(UnsupportedCharsetPolicy::Strict) {
+        Err(ParseError::UnsupportedCharacterSet { values }) => {
+            assert_eq!(values, vec!["ISO_IR 999".to_string()]);
+        }
+        other => panic!("Expected unsupported character set error: {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_unsupported_charset_substitutes() -> Result<()> {
+    let pn: DicomElement = parse_unsupported_charset(UnsupportedCharsetPolicy::Replace)?;
+    assert!(pn.is_cs_substituted());
+    assert_eq!(String::try_from(&pn)?, "Caf\u{fffd}^Ren\u{fffd}");
+
+    let pn: DicomElement = parse_unsupported_charset(UnsupportedCharsetPolicy::FallbackToDefault)?;
+    assert!(pn.is_cs_substituted());
+    assert_eq!(String::try_from(&pn)?, "Café^René");
+
+    let pn: DicomElement = parse_unsupported_charset(UnsupportedCharsetPolicy::RawBytes)?;
+    assert!(pn.is_cs_substituted());
+    assert!(String::try_from(&pn).is_err());
+    assert_eq!(pn.get_data(), b"Caf\xe9^Ren\xe9 ");
+
+    Ok(())
+}
+
+#[test]
+fn test_supported_charset_not_substituted() -> Result<()> {
+    let pn: DicomElement = DicomElement::new(
+        tags::PatientsName.tag,
+        &vr::PN,
+        ValueLength::Explicit(9),
+        &ts::ExplicitVRLittleEndian,
+        iso2022_cs(&["ISO_IR 100"]),
+        b"Caf\xe9^Ren\xe9".to_vec(),
+        SequencePath::new(),
+    );
+    assert!(!pn.is_cs_substituted());
+    Ok(())
+}
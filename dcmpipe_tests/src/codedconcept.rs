@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use dcmpipe_dict::dict::context_groups as cids;
+use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::DEFAULT_CHARACTER_SET;
+use dcmpipe_lib::core::codedconcept::{CodedConcept, CodedConceptError};
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
+use dcmpipe_lib::core::dcmobject::DicomObject;
+use dcmpipe_lib::defn::contextgroup::ContextGroupRef;
+use dcmpipe_lib::defn::dcmdict::DicomDictionary;
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr::{self, VRRef};
+
+fn element(tag: u32, vr: VRRef, data: &[u8]) -> DicomElement {
+    DicomElement::new(
+        tag,
+        vr,
+        ValueLength::Explicit(data.len() as u32),
+        &ts::ExplicitVRLittleEndian,
+        DEFAULT_CHARACTER_SET,
+        data.to_vec(),
+        SequencePath::new(),
+    )
+}
+
+/// Creates a Code Sequence item with the given code value, scheme, and meaning
+fn code_item(value: &[u8], scheme: &[u8], meaning: &[u8]) -> DicomObject {
+    let mut child_nodes: BTreeMap<u32, DicomObject> = BTreeMap::new();
+    for elem in [
+        element(tags::CodeValue.tag, &vr::SH, value),
+        element(tags::CodingSchemeDesignator.tag, &vr::SH, scheme),
+        element(tags::CodeMeaning.tag, &vr::LO, meaning),
+    ] {
+        child_nodes.insert(elem.tag, DicomObject::new(elem));
+    }
+    DicomObject::new_with_children(
+        element(tags::Item.tag, &vr::UN, &[]),
+        child_nodes,
+        Vec::new(),
+    )
+}
+
+#[test]
+fn test_coded_concept_from_item() {
+    let item: DicomObject = code_item(b"818981001 ", b"SCT ", b"Abdomen ");
+    let concept: CodedConcept = CodedConcept::try_from(&item).expect("Failed to parse code");
+    assert_eq!(concept.code_value, "818981001");
+    assert_eq!(concept.coding_scheme_designator, "SCT");
+    assert_eq!(concept.coding_scheme_version, None);
+    assert_eq!(concept.code_meaning, "Abdomen");
+    assert_eq!(concept.to_string(), "(818981001, SCT, \"Abdomen\")");
+
+    let item: DicomObject = code_item(b"", b"SCT ", b"Abdomen ");
+    match CodedConcept::try_from(&item) {
+        Err(CodedConceptError::MissingAttribute(tag)) => assert_eq!(tag, tags::CodeValue.tag),
+        other => panic!("Expected missing code value: {:?}", other),
+    }
+}
+
+#[test]
+fn test_coded_concept_in_cid() {
+    let cid: ContextGroupRef = STANDARD_DICOM_DICTIONARY
+        .get_context_group_by_cid(4)
+        .expect("Context group not found");
+    assert_eq!(cid, &cids::AnatomicRegion);
+
+    let item: DicomObject = code_item(b"818981001 ", b"SCT ", b"Abdomen ");
+    let concept: CodedConcept =
+        CodedConcept::from_item_in_cid(&item, cid).expect("Code should be in CID 4");
+    assert!(concept.find_in(cid).is_some());
+
+    let item: DicomObject = code_item(b"113076", b"DCM ", b"Segmentation");
+    match CodedConcept::from_item_in_cid(&item, cid) {
+        Err(CodedConceptError::NotInContextGroup { cid, .. }) => assert_eq!(cid, 4),
+        other => panic!("Expected code not in context group: {:?}", other),
+    }
+}
@@ -0,0 +1,211 @@
+use std::convert::TryFrom;
+
+use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::DEFAULT_CHARACTER_SET;
+use dcmpipe_lib::core::datetime::{
+    DicomDate, DicomDateTime, DicomRange, DicomTime, InvalidDateTime, UtcOffset,
+};
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
+use dcmpipe_lib::core::parser::Result;
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr::{self, VRRef};
+
+fn element(tag: u32, vr: VRRef, value: &[u8]) -> DicomElement {
+    DicomElement::new(
+        tag,
+        vr,
+        ValueLength::Explicit(value.len() as u32),
+        &ts::ExplicitVRLittleEndian,
+        DEFAULT_CHARACTER_SET,
+        value.to_vec(),
+        SequencePath::new(),
+    )
+}
+
+#[test]
+fn test_date_precision() -> std::result::Result<(), InvalidDateTime> {
+    let date: DicomDate = "20200315".parse()?;
+    assert_eq!(
+        date,
+        DicomDate {
+            year: 2020,
+            month: Some(3),
+            day: Some(15)
+        }
+    );
+    assert_eq!(date.to_string(), "20200315");
+
+    let year: DicomDate = "2020".parse()?;
+    assert_eq!(year.month, None);
+    assert_eq!(year.to_string(), "2020");
+    assert_eq!(year.latest().to_string(), "20201231");
+
+    let month: DicomDate = "202002".parse()?;
+    assert_eq!(month.latest().to_string(), "20200229");
+
+    // ACR-NEMA format
+    assert_eq!("2020.03.15".parse::<DicomDate>()?, date);
+
+    assert!("20200230".parse::<DicomDate>().is_err());
+    assert!("20201301".parse::<DicomDate>().is_err());
+    assert!("202003151".parse::<DicomDate>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_time_precision() -> std::result::Result<(), InvalidDateTime> {
+    let time: DicomTime = "070907.0705".parse()?;
+    assert_eq!(time.hour, 7);
+    assert_eq!(time.minute, Some(9));
+    assert_eq!(time.second, Some(7));
+    assert_eq!(time.microsecond, Some(70_500));
+    assert_eq!(time.to_string(), "070907.0705");
+    assert_eq!(time.latest().to_string(), "070907.070599");
+
+    let hour: DicomTime = "10".parse()?;
+    assert_eq!(hour.minute, None);
+    assert_eq!(hour.to_string(), "10");
+
+    // ACR-NEMA format
+    assert_eq!("07:09:07.0705".parse::<DicomTime>()?, time);
+
+    assert!("2400".parse::<DicomTime>().is_err());
+    assert!("1060".parse::<DicomTime>().is_err());
+    assert!("10.5".parse::<DicomTime>().is_err());
+    assert!("100000.1234567".parse::<DicomTime>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_datetime_offsets() -> std::result::Result<(), InvalidDateTime> {
+    let dt: DicomDateTime = "20200315070907.0705-0500".parse()?;
+    assert_eq!(dt.date.to_string(), "20200315");
+    assert_eq!(
+        dt.time.map(|t| t.to_string()),
+        Some("070907.0705".to_owned())
+    );
+    assert_eq!(dt.offset, Some(UtcOffset { minutes: -300 }));
+    assert_eq!(dt.to_string(), "20200315070907.0705-0500");
+
+    let dt: DicomDateTime = "2020".parse()?;
+    assert_eq!(dt.time, None);
+    assert_eq!(dt.offset, None);
+
+    // Timezone Offset From UTC applies only when the value has no offset
+    let tz: UtcOffset = "+0930".parse()?;
+    assert_eq!(dt.or_offset(Some(tz)).offset, Some(tz));
+    let dt: DicomDateTime = "202003150709-0500".parse()?;
+    assert_eq!(
+        dt.or_offset(Some(tz)).offset,
+        Some(UtcOffset { minutes: -300 })
+    );
+
+    assert!("+1500".parse::<UtcOffset>().is_err());
+    assert!("2020031507-05".parse::<DicomDateTime>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_non_ascii_is_invalid() {
+    // multi-byte characters don't panic where components are sliced by byte position
+    assert!("202€".parse::<DicomDate>().is_err());
+    assert!("0€".parse::<DicomTime>().is_err());
+    assert!("+€0".parse::<UtcOffset>().is_err());
+    assert!("202€".parse::<DicomDateTime>().is_err());
+    assert!("202012€1".parse::<DicomDateTime>().is_err());
+    assert!("20201231€".parse::<DicomDateTime>().is_err());
+    assert!("€-20201231".parse::<DicomRange<DicomDate>>().is_err());
+}
+
+#[test]
+fn test_ranges() -> std::result::Result<(), InvalidDateTime> {
+    let range: DicomRange<DicomDate> = "20200101-20201231".parse()?;
+    assert!(range.contains(&"20200615".parse()?));
+    assert!(range.contains(&"2020".parse()?));
+    assert!(!range.contains(&"20210101".parse()?));
+    assert!(!range.contains(&"2019".parse()?));
+    assert_eq!(range.to_string(), "20200101-20201231");
+
+    let open: DicomRange<DicomDate> = "-20191231".parse()?;
+    assert_eq!(open.start, None);
+    assert!(open.contains(&"19700101".parse()?));
+    assert!(!open.contains(&"20200101".parse()?));
+
+    let open: DicomRange<DicomTime> = "1200-".parse()?;
+    assert!(open.contains(&"235959".parse()?));
+    assert!(!open.contains(&"1159".parse()?));
+
+    let single: DicomRange<DicomDate> = "20200101".parse()?;
+    assert!(single.is_single_value());
+
+    // a negative offset is not a range separator
+    let single: DicomRange<DicomDateTime> = "20200101120000-0500".parse()?;
+    assert!(single.is_single_value());
+    let range: DicomRange<DicomDateTime> = "20200101120000-0500-20200101180000+0000".parse()?;
+    assert!(!range.is_single_value());
+    // 12:00 at -05:00 is 17:00 UTC
+    assert!(range.contains(&"20200101170000+0000".parse()?));
+    assert!(!range.contains(&"20200101165959+0000".parse()?));
+
+    let years: DicomRange<DicomDateTime> = "2019-2020".parse()?;
+    assert!(years.contains(&"20200615".parse()?));
+
+    assert!("20200101-20201231-2021"
+        .parse::<DicomRange<DicomDate>>()
+        .is_err());
+    assert!("-".parse::<DicomRange<DicomDate>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_elements() -> Result<()> {
+    let da: DicomElement = element(tags::StudyDate.tag, &vr::DA, b"20200315");
+    assert_eq!(DicomDate::try_from(&da)?.to_string(), "20200315");
+
+    let tm: DicomElement = element(tags::StudyTime.tag, &vr::TM, b"070907.0705 ");
+    assert_eq!(DicomTime::try_from(&tm)?.to_string(), "070907.0705");
+
+    let query: DicomElement = element(tags::StudyDate.tag, &vr::DA, b"20200101-20201231 ");
+    let range: DicomRange<DicomDate> = DicomRange::try_from(&query)?;
+    assert_eq!(
+        range.end.map(|d| d.to_string()),
+        Some("20201231".to_owned())
+    );
+
+    let tz: DicomElement = element(tags::TimezoneOffsetFromUTC.tag, &vr::SH, b"-0500 ");
+    assert_eq!(UtcOffset::try_from(&tz)?.minutes, -300);
+
+    let invalid: DicomElement = element(tags::StudyDate.tag, &vr::DA, b"2020-3-15 ");
+    assert!(DicomDate::try_from(&invalid).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_chrono_conversions() -> std::result::Result<(), InvalidDateTime> {
+    let date: DicomDate = "2020".parse()?;
+    assert_eq!(date.to_naive_date(), NaiveDate::from_ymd_opt(2020, 1, 1));
+    assert_eq!(
+        DicomDate::from(NaiveDate::from_ymd(2020, 3, 15)).to_string(),
+        "20200315"
+    );
+
+    let dt: DicomDateTime = "20200315070907.0705-0500".parse()?;
+    let chrono_dt = FixedOffset::west(5 * 3600)
+        .ymd(2020, 3, 15)
+        .and_hms_micro(7, 9, 7, 70_500);
+    assert_eq!(dt.to_datetime(), Some(chrono_dt));
+    assert_eq!(
+        DicomDateTime::from(chrono_dt).to_string(),
+        "20200315070907.070500-0500"
+    );
+
+    // without an offset the value can't be converted to a DateTime
+    let dt: DicomDateTime = "20200315070907".parse()?;
+    assert_eq!(dt.to_datetime(), None);
+    assert!(dt.to_naive_datetime().is_some());
+    Ok(())
+}
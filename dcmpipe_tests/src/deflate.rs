@@ -0,0 +1,215 @@
+use std::io::{Cursor, Write};
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+#[cfg(feature = "deflate")]
+use dcmpipe_lib::core::dcmelement::DicomElement;
+#[cfg(feature = "deflate")]
+use dcmpipe_lib::core::deflate::{deflate_file, DeflatedWriter};
+#[cfg(feature = "deflate")]
+use dcmpipe_lib::core::parser::Result;
+use dcmpipe_lib::core::parser::{ParseError, Parser};
+#[cfg(not(feature = "deflate"))]
+use dcmpipe_lib::core::parser::{PushEvent, PushParser};
+#[cfg(feature = "deflate")]
+use dcmpipe_lib::defn::ts::TSRef;
+use libflate::deflate::Encoder;
+
+#[cfg(feature = "deflate")]
+use crate::encode::EXPLICIT_VR_LITTLE_ENDIAN;
+use crate::encode::{builder, explicit_vr_le_element, specific_character_set};
+
+const DEFLATED_EXPLICIT_VR_LITTLE_ENDIAN: &[u8] = b"1.2.840.10008.1.2.1.99";
+#[cfg(feature = "deflate")]
+const IMPLICIT_VR_LITTLE_ENDIAN: &[u8] = b"1.2.840.10008.1.2\0";
+
+/// The preamble, prefix, and File Meta specifying the given transfer syntax.
+fn create_file_meta(ts_uid: &[u8]) -> Vec<u8> {
+    let mut elements: Vec<u8> = explicit_vr_le_element(
+        tags::MediaStorageSOPClassUID.tag,
+        b"UI",
+        b"1.2.840.10008.5.1.4.1.1.7\0",
+    );
+    elements.extend(explicit_vr_le_element(
+        tags::TransferSyntaxUID.tag,
+        b"UI",
+        ts_uid,
+    ));
+    elements.extend(explicit_vr_le_element(
+        tags::ImplementationVersionName.tag,
+        b"SH",
+        b"DCMPIPE ",
+    ));
+
+    let mut file: Vec<u8> = vec![0; 128];
+    file.extend_from_slice(b"DICM");
+    file.extend(explicit_vr_le_element(
+        tags::FileMetaInformationGroupLength.tag,
+        b"UL",
+        &(elements.len() as u32).to_le_bytes(),
+    ));
+    file.extend(elements);
+    file
+}
+
+/// The dataset following the File Meta, encoded as Explicit VR Little Endian.
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &[0; 1024],
+    ));
+    dataset
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder: Encoder<Vec<u8>> = Encoder::new(Vec::new());
+    encoder.write_all(data).expect("deflate");
+    encoder.finish().into_result().expect("deflate")
+}
+
+/// Parses the file, returning its elements along with the transfer syntax of the dataset.
+#[cfg(feature = "deflate")]
+fn parse(file: &[u8]) -> Result<(Vec<DicomElement>, TSRef)> {
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(file));
+    let elements: Vec<DicomElement> = parser.by_ref().collect::<Result<Vec<DicomElement>>>()?;
+    Ok((elements, parser.get_ts()))
+}
+
+#[cfg(feature = "deflate")]
+fn assert_same_dataset(elements: &[DicomElement], expected: &[DicomElement]) {
+    let dataset = |elements: &[DicomElement]| -> Vec<(u32, Vec<u8>)> {
+        elements
+            .iter()
+            .filter(|element| element.tag >> 16 != 0x0002)
+            .map(|element| (element.tag, element.get_data().clone()))
+            .collect()
+    };
+    assert_eq!(dataset(elements), dataset(expected));
+}
+
+#[test]
+#[cfg(feature = "deflate")]
+fn test_deflate_file() -> Result<()> {
+    let mut file: Vec<u8> = create_file_meta(EXPLICIT_VR_LITTLE_ENDIAN);
+    file.extend(create_dataset());
+    let deflated: Vec<u8> = deflate_file(&file)?;
+    assert!(deflated.len() < file.len());
+
+    let (expected, _) = parse(&file)?;
+    let (elements, dataset_ts) = parse(&deflated)?;
+    assert_eq!(dataset_ts, &ts::DeflatedExplicitVRLittleEndian);
+    assert_same_dataset(&elements, &expected);
+
+    // the file meta is the same other than the transfer syntax and group length
+    let file_meta: Vec<(u32, Vec<u8>)> = elements
+        .iter()
+        .take_while(|element| element.tag >> 16 == 0x0002)
+        .map(|element| (element.tag, element.get_data().clone()))
+        .collect();
+    assert_eq!(
+        file_meta,
+        vec![
+            (
+                tags::FileMetaInformationGroupLength.tag,
+                80u32.to_le_bytes().to_vec()
+            ),
+            (
+                tags::MediaStorageSOPClassUID.tag,
+                b"1.2.840.10008.5.1.4.1.1.7\0".to_vec()
+            ),
+            (
+                tags::TransferSyntaxUID.tag,
+                DEFLATED_EXPLICIT_VR_LITTLE_ENDIAN.to_vec()
+            ),
+            (tags::ImplementationVersionName.tag, b"DCMPIPE ".to_vec()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "deflate")]
+fn test_deflated_writer() -> Result<()> {
+    let mut writer: DeflatedWriter<Vec<u8>> = DeflatedWriter::new(
+        Vec::new(),
+        &create_file_meta(DEFLATED_EXPLICIT_VR_LITTLE_ENDIAN),
+    )?;
+    let dataset: Vec<u8> = create_dataset();
+    for chunk in dataset.chunks(7) {
+        writer.write_all(chunk)?;
+    }
+    let deflated: Vec<u8> = writer.finish()?;
+
+    let mut expected_file: Vec<u8> = create_file_meta(DEFLATED_EXPLICIT_VR_LITTLE_ENDIAN);
+    expected_file.extend(deflate(&dataset));
+    let (expected, _) = parse(&expected_file)?;
+    let (elements, _) = parse(&deflated)?;
+    assert_eq!(elements.len(), 7);
+    assert_same_dataset(&elements, &expected);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "deflate")]
+fn test_deflate_file_errors() {
+    let mut file: Vec<u8> = create_file_meta(IMPLICIT_VR_LITTLE_ENDIAN);
+    file.extend(create_dataset());
+    assert!(matches!(
+        deflate_file(&file),
+        Err(ParseError::UnsupportedTransferSyntax { .. })
+    ));
+
+    // the dataset alone has no preamble and prefix
+    assert!(matches!(
+        deflate_file(&create_dataset()),
+        Err(ParseError::MissingDICOMPrefix)
+    ));
+}
+
+#[test]
+#[cfg(not(feature = "deflate"))]
+fn test_deflated_dataset_is_unsupported() {
+    let mut file: Vec<u8> = create_file_meta(DEFLATED_EXPLICIT_VR_LITTLE_ENDIAN);
+    file.extend(deflate(&create_dataset()));
+
+    let assert_unsupported = |error: ParseError| match error {
+        ParseError::UnsupportedTransferSyntax { ts } => {
+            assert_eq!(ts, &ts::DeflatedExplicitVRLittleEndian);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    };
+
+    // the file meta is read before the dataset is found to be deflated
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(file.as_slice()));
+    for _ in 0..4 {
+        assert!(matches!(parser.next(), Some(Ok(_))));
+    }
+    match parser.next() {
+        Some(Err(e)) => assert_unsupported(e),
+        _ => panic!("deflated dataset should fail to parse"),
+    }
+    assert!(parser.get_error_context().is_some());
+    assert!(parser.next().is_none());
+
+    let mut parser: PushParser<'_> = builder().build_push();
+    parser.feed(&file);
+    parser.finish();
+    loop {
+        match parser.next_event() {
+            Ok(PushEvent::Element(_)) => {}
+            Ok(_) => panic!("deflated dataset should fail to parse"),
+            Err(e) => {
+                assert_unsupported(e);
+                break;
+            }
+        }
+    }
+    assert!(parser.get_parser().get_error_context().is_some());
+}
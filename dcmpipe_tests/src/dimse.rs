@@ -0,0 +1,165 @@
+use std::convert::TryFrom;
+
+use dcmpipe_lib::core::dimse::{
+    CommandField, CommandSet, DimseError, DimseMessage, Priority, StatusKind, NO_DATA_SET,
+};
+
+use crate::encode::implicit_vr_le_element;
+
+const VERIFICATION_SOP_CLASS: &str = "1.2.840.10008.1.1";
+const CT_IMAGE_STORAGE: &str = "1.2.840.10008.5.1.4.1.1.2";
+
+/// Encodes the elements with a leading command group length.
+fn command_set(elements: &[Vec<u8>]) -> Vec<u8> {
+    let elements: Vec<u8> = elements.concat();
+    let mut bytes: Vec<u8> =
+        implicit_vr_le_element(0x0000_0000, &(elements.len() as u32).to_le_bytes());
+    bytes.extend(elements);
+    bytes
+}
+
+fn c_echo_rq() -> CommandSet {
+    let mut command: CommandSet = CommandSet::new(CommandField::CEchoRq);
+    command.message_id = Some(7);
+    command.affected_sop_class_uid = Some(VERIFICATION_SOP_CLASS.to_owned());
+    command
+}
+
+#[test]
+fn test_encode_c_echo_rq() {
+    let expected: Vec<u8> = command_set(&[
+        implicit_vr_le_element(0x0000_0002, b"1.2.840.10008.1.1\0"),
+        implicit_vr_le_element(0x0000_0100, &0x0030u16.to_le_bytes()),
+        implicit_vr_le_element(0x0000_0110, &7u16.to_le_bytes()),
+        implicit_vr_le_element(0x0000_0800, &NO_DATA_SET.to_le_bytes()),
+    ]);
+    assert_eq!(c_echo_rq().encode(), expected);
+}
+
+#[test]
+fn test_command_set_round_trip() -> Result<(), DimseError> {
+    let command: CommandSet = c_echo_rq();
+    assert_eq!(CommandSet::decode(&command.encode())?, command);
+
+    let mut command: CommandSet = CommandSet::new(CommandField::CMoveRsp);
+    command.message_id_being_responded_to = Some(3);
+    command.affected_sop_class_uid = Some(CT_IMAGE_STORAGE.to_owned());
+    command.status = Some(0xFF00);
+    command.remaining_suboperations = Some(4);
+    command.completed_suboperations = Some(5);
+    command.failed_suboperations = Some(0);
+    command.warning_suboperations = Some(1);
+    command.error_comment = Some("odd".to_owned());
+    assert_eq!(CommandSet::decode(&command.encode())?, command);
+    assert_eq!(command.get_status_kind(), Some(StatusKind::Pending));
+    Ok(())
+}
+
+#[test]
+fn test_message_with_data_set() -> Result<(), DimseError> {
+    let mut command: CommandSet = CommandSet::new(CommandField::CStoreRq);
+    command.message_id = Some(1);
+    command.priority = Some(Priority::High);
+    command.affected_sop_class_uid = Some(CT_IMAGE_STORAGE.to_owned());
+    command.affected_sop_instance_uid = Some("1.2.3.4".to_owned());
+    command.move_originator_ae_title = Some("MOVESCU".to_owned());
+    command.move_originator_message_id = Some(9);
+    let data_set: Vec<u8> = implicit_vr_le_element(0x0010_0020, b"12345678");
+
+    let message: DimseMessage = DimseMessage::new(command, Some(data_set.clone()));
+    assert!(message.command.has_data_set);
+
+    let (encoded, encoded_data_set) = message.encode();
+    assert_eq!(encoded_data_set, Some(data_set.as_slice()));
+    let decoded: DimseMessage = DimseMessage::decode(&encoded, Some(data_set))?;
+    assert_eq!(decoded, message);
+    assert_eq!(
+        decoded.command.affected_sop_instance_uid.as_deref(),
+        Some("1.2.3.4")
+    );
+    assert_eq!(
+        decoded.command.move_originator_ae_title.as_deref(),
+        Some("MOVESCU")
+    );
+
+    assert_eq!(
+        DimseMessage::decode(&encoded, None),
+        Err(DimseError::MissingDataSet)
+    );
+    assert_eq!(
+        DimseMessage::decode(&c_echo_rq().encode(), Some(Vec::new())),
+        Err(DimseError::UnexpectedDataSet)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decode_response() -> Result<(), DimseError> {
+    // data set type values other than 0x0101 indicate a data set is present
+    let encoded: Vec<u8> = command_set(&[
+        implicit_vr_le_element(0x0000_0002, b"1.2.840.10008.5.1.4.1.2.2.1\0"),
+        implicit_vr_le_element(0x0000_0100, &0x8020u16.to_le_bytes()),
+        implicit_vr_le_element(0x0000_0120, &2u16.to_le_bytes()),
+        implicit_vr_le_element(0x0000_0800, &0x0102u16.to_le_bytes()),
+        implicit_vr_le_element(0x0000_0900, &0xB000u16.to_le_bytes()),
+        // retired elements are ignored
+        implicit_vr_le_element(0x0000_5010, b"RETIRED "),
+    ]);
+    let command: CommandSet = CommandSet::decode(&encoded)?;
+    assert_eq!(command.command_field, CommandField::CFindRsp);
+    assert!(command.command_field.is_response());
+    assert_eq!(
+        command.affected_sop_class_uid.as_deref(),
+        Some("1.2.840.10008.5.1.4.1.2.2.1")
+    );
+    assert_eq!(command.message_id_being_responded_to, Some(2));
+    assert!(command.has_data_set);
+    assert_eq!(command.get_status_kind(), Some(StatusKind::Warning));
+    Ok(())
+}
+
+#[test]
+fn test_decode_errors() {
+    let data_set_type: Vec<u8> = implicit_vr_le_element(0x0000_0800, &NO_DATA_SET.to_le_bytes());
+    assert_eq!(
+        CommandSet::decode(&command_set(std::slice::from_ref(&data_set_type))),
+        Err(DimseError::MissingElement(0x0000_0100))
+    );
+
+    let unknown_field: Vec<u8> = implicit_vr_le_element(0x0000_0100, &0x7777u16.to_le_bytes());
+    assert_eq!(
+        CommandSet::decode(&command_set(&[unknown_field, data_set_type.clone()])),
+        Err(DimseError::UnknownCommandField(0x7777))
+    );
+
+    // without the group length the truncated element is found
+    let mut encoded: Vec<u8> = c_echo_rq().encode()[12..].to_vec();
+    encoded.truncate(encoded.len() - 1);
+    assert_eq!(CommandSet::decode(&encoded), Err(DimseError::UnexpectedEnd));
+
+    let mut encoded: Vec<u8> = c_echo_rq().encode();
+    encoded.extend(implicit_vr_le_element(0x0008_0018, b"1.2\0"));
+    assert!(matches!(
+        CommandSet::decode(&encoded),
+        Err(DimseError::GroupLengthMismatch { .. })
+    ));
+
+    let mut encoded: Vec<u8> = c_echo_rq().encode()[12..].to_vec();
+    encoded.extend(implicit_vr_le_element(0x0008_0018, b"1.2\0"));
+    assert_eq!(
+        CommandSet::decode(&encoded),
+        Err(DimseError::NotCommandElement(0x0008_0018))
+    );
+}
+
+#[test]
+fn test_command_field_values() {
+    for value in 0..=0xFFFFu16 {
+        if let Ok(command_field) = CommandField::try_from(value) {
+            assert_eq!(command_field.get_value(), value);
+        }
+    }
+    assert_eq!(StatusKind::from(0x0000), StatusKind::Success);
+    assert_eq!(StatusKind::from(0xFE00), StatusKind::Cancel);
+    assert_eq!(StatusKind::from(0xA700), StatusKind::Failure);
+}
@@ -0,0 +1,190 @@
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmobject::{DicomNode, DicomObject, DicomRoot, DuplicateTagPolicy};
+use dcmpipe_lib::core::parser::util::parse_into_object;
+use dcmpipe_lib::core::parser::{Diagnostic, DiagnosticKind, ParseError, Parser, Result};
+use dcmpipe_lib::defn::tag::TagPath;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, item_element, specific_character_set,
+    undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A dataset where Patient's Name appears twice, the second time following Patient ID.
+fn create_duplicate_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(tags::PatientID.tag, b"LO", b"1234"));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Roe^Jane",
+    ));
+    dataset
+}
+
+/// A dataset without duplicates whose tags are out of order, both at the root and within an item.
+fn create_out_of_order_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPInstanceUID.tag,
+        b"UI",
+        b"1.3\0",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPClassUID.tag,
+        b"UI",
+        b"1.2\0",
+    ));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(explicit_vr_le_element(tags::PatientID.tag, b"LO", b"1234"));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset
+}
+
+/// Parses the dataset into an object, returning it along with the diagnostics recorded.
+fn parse(
+    dataset: &[u8],
+    policy: DuplicateTagPolicy,
+) -> Result<(DicomRoot<'static>, Vec<Diagnostic>)> {
+    let mut parser: Parser<'_, Cursor<&[u8]>> =
+        builder().duplicate_tags(policy).build(Cursor::new(dataset));
+    let dcmroot: DicomRoot<'_> = parse_into_object(&mut parser)?.expect("dataset should parse");
+    Ok((dcmroot, parser.get_diagnostics().to_vec()))
+}
+
+fn patients_name(dcmroot: &DicomRoot<'_>) -> Result<String> {
+    let name: &DicomObject = dcmroot
+        .get_child_by_tag(tags::PatientsName.tag)
+        .expect("PatientsName should be parsed");
+    String::try_from(name.get_element())
+}
+
+fn diagnostic_kinds(diagnostics: &[Diagnostic]) -> Vec<DiagnosticKind> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.kind.clone())
+        .collect()
+}
+
+#[test]
+fn test_duplicate_keep_last() -> Result<()> {
+    let (dcmroot, diagnostics) = parse(&create_duplicate_dataset(), DuplicateTagPolicy::KeepLast)?;
+    assert_eq!(patients_name(&dcmroot)?, "Roe^Jane");
+    assert_eq!(
+        dcmroot.get_child_order(),
+        &[
+            tags::SpecificCharacterSet.tag,
+            tags::PatientID.tag,
+            tags::PatientsName.tag,
+        ]
+    );
+    assert_eq!(
+        diagnostic_kinds(&diagnostics),
+        vec![
+            DiagnosticKind::TagOutOfOrder {
+                previous: tags::PatientID.tag
+            },
+            DiagnosticKind::DuplicateTag,
+        ]
+    );
+    for diagnostic in diagnostics {
+        assert_eq!(diagnostic.tagpath, TagPath::from(tags::PatientsName.tag));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_keep_first() -> Result<()> {
+    let (dcmroot, diagnostics) = parse(&create_duplicate_dataset(), DuplicateTagPolicy::KeepFirst)?;
+    assert_eq!(patients_name(&dcmroot)?, "Doe^John");
+    assert_eq!(
+        dcmroot.get_child_order(),
+        &[
+            tags::SpecificCharacterSet.tag,
+            tags::PatientsName.tag,
+            tags::PatientID.tag,
+        ]
+    );
+    assert!(diagnostic_kinds(&diagnostics).contains(&DiagnosticKind::DuplicateTag));
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_error() {
+    match parse(&create_duplicate_dataset(), DuplicateTagPolicy::Error) {
+        Err(ParseError::DuplicateTag { tagpath }) => {
+            assert_eq!(tagpath, TagPath::from(tags::PatientsName.tag));
+        }
+        Err(e) => panic!("unexpected error: {:?}", e),
+        Ok(_) => panic!("duplicate tag should fail to parse"),
+    }
+}
+
+#[test]
+fn test_out_of_order_keeps_parsed_order() -> Result<()> {
+    let (dcmroot, diagnostics) = parse(
+        &create_out_of_order_dataset(),
+        DuplicateTagPolicy::default(),
+    )?;
+
+    // the child nodes are still iterated in tag-ascending order
+    let sorted_tags: Vec<u32> = dcmroot.iter_child_nodes().map(|(tag, _)| *tag).collect();
+    assert_eq!(
+        sorted_tags,
+        vec![
+            tags::SpecificCharacterSet.tag,
+            tags::ReferencedImageSequence.tag,
+            tags::PatientsName.tag,
+            tags::PatientID.tag,
+        ]
+    );
+    assert_eq!(
+        dcmroot.get_child_order(),
+        &[
+            tags::SpecificCharacterSet.tag,
+            tags::ReferencedImageSequence.tag,
+            tags::PatientID.tag,
+            tags::PatientsName.tag,
+        ]
+    );
+
+    let item: &DicomObject = dcmroot
+        .get_child_by_tag(tags::ReferencedImageSequence.tag)
+        .and_then(|sequence| sequence.get_item_by_index(1))
+        .expect("item should be parsed");
+    assert_eq!(
+        item.get_child_order(),
+        &[
+            tags::ReferencedSOPInstanceUID.tag,
+            tags::ReferencedSOPClassUID.tag,
+            tags::ItemDelimitationItem.tag,
+        ]
+    );
+
+    assert_eq!(
+        diagnostic_kinds(&diagnostics),
+        vec![
+            DiagnosticKind::TagOutOfOrder {
+                previous: tags::ReferencedSOPInstanceUID.tag
+            },
+            DiagnosticKind::TagOutOfOrder {
+                previous: tags::PatientID.tag
+            },
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Helpers for encoding elements to build the datasets used by tests.
+
+use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::parser::ParserBuilder;
+
+pub const UNDEFINED_LENGTH: u32 = 0xFFFF_FFFF;
+pub const EXPLICIT_VR_LITTLE_ENDIAN: &[u8] = b"1.2.840.10008.1.2.1\0";
+
+/// VRs which are encoded with two reserved bytes and a 4-byte value length in explicit VR.
+const LONG_VL_VRS: [&[u8; 2]; 13] = [
+    b"OB", b"OD", b"OF", b"OL", b"OV", b"OW", b"SQ", b"SV", b"UC", b"UN", b"UR", b"UT", b"UV",
+];
+
+pub fn tag_bytes(tag: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(&((tag >> 16) as u16).to_le_bytes());
+    bytes.extend_from_slice(&((tag & 0xFFFF) as u16).to_le_bytes());
+    bytes
+}
+
+/// Encodes an element as explicit VR little endian.
+pub fn explicit_vr_le_element(tag: u32, vr: &[u8; 2], value: &[u8]) -> Vec<u8> {
+    explicit_vr_le_element_with_vl(tag, vr, value.len() as u32, value)
+}
+
+/// Encodes an element as explicit VR little endian, with a value length which may not match the
+/// length of the value. VRs with a 2-byte value length use the lower bytes of `vl`.
+pub fn explicit_vr_le_element_with_vl(tag: u32, vr: &[u8; 2], vl: u32, value: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tag_bytes(tag);
+    bytes.extend_from_slice(vr);
+    if LONG_VL_VRS.contains(&vr) {
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&vl.to_le_bytes());
+    } else {
+        bytes.extend_from_slice(&(vl as u16).to_le_bytes());
+    }
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// Encodes an element as implicit VR little endian.
+pub fn implicit_vr_le_element(tag: u32, value: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tag_bytes(tag);
+    bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// Encodes an item or delimiter, which has no VR.
+pub fn item_element(tag: u32, length: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tag_bytes(tag);
+    bytes.extend_from_slice(&length.to_le_bytes());
+    bytes
+}
+
+/// Encodes the start of a sequence of undefined length as explicit VR little endian.
+pub fn undefined_length_sequence(tag: u32) -> Vec<u8> {
+    explicit_vr_le_element_with_vl(tag, b"SQ", UNDEFINED_LENGTH, &[])
+}
+
+/// The Specific Character Set element for ISO_IR 100.
+pub fn specific_character_set() -> Vec<u8> {
+    explicit_vr_le_element(tags::SpecificCharacterSet.tag, b"CS", b"ISO_IR 100")
+}
+
+/// A parser builder using the standard dictionary.
+pub fn builder() -> ParserBuilder<'static> {
+    ParserBuilder::default().dictionary(&STANDARD_DICOM_DICTIONARY)
+}
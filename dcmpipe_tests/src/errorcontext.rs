@@ -0,0 +1,134 @@
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::UnsupportedCharsetPolicy;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{
+    ErrorContext, ParseError, Parser, ParserBuilder, PushEvent, PushParser, Result,
+};
+use dcmpipe_lib::defn::tag::{TagNode, TagPath};
+use dcmpipe_lib::defn::ts::TSRef;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, explicit_vr_le_element_with_vl, specific_character_set,
+    tag_bytes, undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A dataset whose last element is missing half of its value.
+fn create_truncated_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element_with_vl(
+        tags::PatientsName.tag,
+        b"PN",
+        16,
+        b"Doe^John",
+    ));
+    dataset
+}
+
+/// Parses the dataset, returning the error it fails with and where the error occurred.
+fn parse_error(dataset: &[u8], builder: ParserBuilder<'_>) -> (ParseError, ErrorContext) {
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder.build(Cursor::new(dataset));
+    assert!(parser.get_error_context().is_none());
+    match parser.by_ref().collect::<Result<Vec<DicomElement>>>() {
+        Ok(_) => panic!("dataset should fail to parse"),
+        Err(e) => {
+            let context: &ErrorContext = parser
+                .get_error_context()
+                .expect("error should have context");
+            (e, context.clone())
+        }
+    }
+}
+
+fn assert_context(context: &ErrorContext, offset: u64, tagpath: TagPath, ts: TSRef) {
+    assert_eq!(context.offset, offset);
+    assert_eq!(context.tagpath, Some(tagpath));
+    assert_eq!(context.ts, ts);
+}
+
+#[test]
+fn test_error_has_context() {
+    let dataset: Vec<u8> = create_truncated_dataset();
+    let (error, context): (ParseError, ErrorContext) = parse_error(&dataset, builder());
+
+    assert_context(
+        &context,
+        26,
+        tags::PatientsName.tag.into(),
+        &ts::ExplicitVRLittleEndian,
+    );
+    assert_eq!(context.bytes_before, &dataset[dataset.len() - 16..]);
+    assert!(context.bytes_after.is_empty());
+    assert_eq!(
+        context.hex_excerpt(),
+        "10 00 10 00 50 4E 10 00 44 6F 65 5E 4A 6F 68 6E |"
+    );
+    assert_eq!(
+        context.to_string(),
+        "at byte 26 in 00100010 with ExplicitVRLittleEndian"
+    );
+    assert!(matches!(error, ParseError::DetailedIOError { .. }));
+}
+
+#[test]
+fn test_error_context_has_following_bytes() {
+    let mut dataset: Vec<u8> =
+        explicit_vr_le_element(tags::SpecificCharacterSet.tag, b"CS", b"ISO_IR 999");
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    let (error, context): (ParseError, ErrorContext) = parse_error(
+        &dataset,
+        builder().unsupported_charset(UnsupportedCharsetPolicy::Strict),
+    );
+
+    assert_context(
+        &context,
+        18,
+        tags::SpecificCharacterSet.tag.into(),
+        &ts::ExplicitVRLittleEndian,
+    );
+    assert_eq!(context.bytes_before, &dataset[2..18]);
+    assert_eq!(context.bytes_after, &dataset[18..34]);
+    assert!(matches!(error, ParseError::UnsupportedCharacterSet { .. }));
+}
+
+#[test]
+fn test_error_context_within_sequence() {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    dataset.extend(tag_bytes(tags::Item.tag));
+    dataset.extend_from_slice(&UNDEFINED_LENGTH.to_le_bytes());
+    dataset.extend(explicit_vr_le_element_with_vl(
+        tags::ReferencedSOPClassUID.tag,
+        b"UI",
+        16,
+        b"1.2\0",
+    ));
+
+    let tagpath: TagPath = TagPath::from(vec![
+        TagNode::new(tags::ReferencedImageSequence.tag, Some(1)),
+        tags::ReferencedSOPClassUID.tag.into(),
+    ]);
+    let (_, context): (ParseError, ErrorContext) = parse_error(&dataset, builder());
+    assert_context(&context, 46, tagpath.clone(), &ts::ExplicitVRLittleEndian);
+
+    // the push parser reports the same position, despite rolling back partial elements
+    let mut parser: PushParser<'_> = builder().build_push();
+    for byte in dataset.chunks(1) {
+        parser.feed(byte);
+        while let PushEvent::Element(_) = parser.next_event().expect("not yet finished") {}
+    }
+    parser.finish();
+    assert!(parser.next_event().is_err());
+    let context: &ErrorContext = parser
+        .get_parser()
+        .get_error_context()
+        .expect("error should have context");
+    assert_context(context, 46, tagpath, &ts::ExplicitVRLittleEndian);
+    assert_eq!(context.bytes_before, &dataset[dataset.len() - 16..]);
+}
@@ -0,0 +1,146 @@
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::filter::ElementFilter;
+use dcmpipe_lib::core::parser::{ParseError, Parser, ParserBuilder, Result};
+
+use crate::encode::{
+    builder, explicit_vr_le_element, explicit_vr_le_element_with_vl, item_element,
+    undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+const PRIVATE_CREATOR: u32 = 0x0009_0010;
+const PRIVATE_DATA: u32 = 0x0009_1001;
+const OVERLAY_DATA: u32 = 0x6002_3000;
+
+/// A dataset with a group length, private elements, a sequence and overlay data. The private data
+/// and overlay data values are the given length.
+fn create_dataset(value_length: usize) -> Vec<u8> {
+    let mut dataset: Vec<u8> = explicit_vr_le_element(0x0008_0000, b"UL", &[0, 0, 0, 0]);
+    dataset.extend(explicit_vr_le_element(
+        tags::SpecificCharacterSet.tag,
+        b"CS",
+        b"ISO_IR 100",
+    ));
+    dataset.extend(explicit_vr_le_element(PRIVATE_CREATOR, b"LO", b"ACME"));
+    dataset.extend(explicit_vr_le_element_with_vl(
+        PRIVATE_DATA,
+        b"OB",
+        value_length as u32,
+        &vec![1; value_length],
+    ));
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPClassUID.tag,
+        b"UI",
+        b"1.2\0",
+    ));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    // "Müller" encoded as ISO_IR 100
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        &[b'M', 0xFC, b'l', b'l', b'e', b'r'],
+    ));
+    dataset.extend(explicit_vr_le_element_with_vl(
+        OVERLAY_DATA,
+        b"OB",
+        value_length as u32,
+        &vec![2; value_length],
+    ));
+    dataset
+}
+
+fn parse(dataset: &[u8], builder: ParserBuilder<'_>) -> Result<Vec<DicomElement>> {
+    let parser: Parser<'_, Cursor<&[u8]>> = builder.build(Cursor::new(dataset));
+    parser.collect::<Result<Vec<DicomElement>>>()
+}
+
+fn filter_builder(filter: ElementFilter) -> ParserBuilder<'static> {
+    builder().filter(filter)
+}
+
+fn element_tags(elements: &[DicomElement]) -> Vec<u32> {
+    elements.iter().map(|element| element.tag).collect()
+}
+
+#[test]
+fn test_filter_rules() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset(16);
+    let filter: ElementFilter = ElementFilter::new()
+        .private_groups(true)
+        .group_lengths(true)
+        .overlay_data(true)
+        .delimiters(true);
+    let elements: Vec<DicomElement> = parse(&dataset, filter_builder(filter))?;
+    assert_eq!(
+        element_tags(&elements),
+        vec![
+            tags::SpecificCharacterSet.tag,
+            tags::ReferencedImageSequence.tag,
+            tags::Item.tag,
+            tags::ReferencedSOPClassUID.tag,
+            tags::PatientsName.tag,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_tags_and_ranges() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset(16);
+    let filter: ElementFilter = ElementFilter::new()
+        .tags(vec![
+            tags::PatientsName.tag,
+            tags::ReferencedImageSequence.tag,
+        ])
+        .tag_range(0x0009_0000..=0x0009_FFFF);
+    let elements: Vec<DicomElement> = parse(&dataset, filter_builder(filter))?;
+
+    // the contents of the filtered sequence are also filtered
+    assert_eq!(
+        element_tags(&elements),
+        vec![0x0008_0000, tags::SpecificCharacterSet.tag, OVERLAY_DATA]
+    );
+    assert_eq!(elements[2].get_data(), &vec![2; 16]);
+    Ok(())
+}
+
+#[test]
+fn test_filtered_values_are_not_read() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset(4096);
+    let filter: ElementFilter = ElementFilter::new().private_groups(true).overlay_data(true);
+
+    // only filtered values are skipped over, otherwise they exceed the limit on values read
+    // into memory
+    let elements: Vec<DicomElement> =
+        parse(&dataset, filter_builder(filter).max_value_length(1024))?;
+    assert_eq!(elements.len(), 8);
+    assert!(matches!(
+        parse(
+            &dataset,
+            filter_builder(ElementFilter::new()).max_value_length(1024)
+        ),
+        Err(ParseError::ValueLengthExceedsLimit { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_filtered_character_set_applies() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset(16);
+    let filter: ElementFilter = ElementFilter::new().tags(vec![tags::SpecificCharacterSet.tag]);
+    let elements: Vec<DicomElement> = parse(&dataset, filter_builder(filter))?;
+
+    assert!(!element_tags(&elements).contains(&tags::SpecificCharacterSet.tag));
+    let name: &DicomElement = elements
+        .iter()
+        .find(|element| element.tag == tags::PatientsName.tag)
+        .expect("PatientsName should be parsed");
+    assert_eq!(String::try_from(name)?, "Müller");
+    Ok(())
+}
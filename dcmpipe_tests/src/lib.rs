@@ -14,12 +14,56 @@ use dcmpipe_lib::core::parser::util::parse_into_object;
 use dcmpipe_lib::core::tagstop::TagStop;
 use dcmpipe_lib::defn::tag::Tag;
 
+#[cfg(test)]
+mod bulkdata;
 #[cfg(test)]
 mod charsets;
 #[cfg(test)]
+mod codedconcept;
+#[cfg(test)]
+mod datetime;
+#[cfg(test)]
+mod deflate;
+#[cfg(test)]
+mod dimse;
+#[cfg(test)]
+mod duplicates;
+#[cfg(test)]
+mod encode;
+#[cfg(test)]
+mod errorcontext;
+#[cfg(test)]
+mod filter;
+#[cfg(test)]
+mod limits;
+#[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod parsing;
+#[cfg(test)]
+mod personname;
+#[cfg(test)]
+mod push;
+#[cfg(test)]
+mod recover;
+#[cfg(test)]
+mod seekable;
+#[cfg(test)]
+mod sequencepath;
+#[cfg(test)]
+mod slice;
+#[cfg(test)]
+mod source;
+#[cfg(test)]
+mod strict;
+#[cfg(test)]
+mod stream;
+#[cfg(test)]
+mod tagstop;
+#[cfg(test)]
+mod validate;
+#[cfg(test)]
+mod values;
 
 /// Parses the given file into a `DicomObject`
 pub fn parse_file(path: &str, with_std: bool) -> Result<DicomRoot<'_>> {
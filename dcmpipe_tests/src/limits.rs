@@ -0,0 +1,156 @@
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::fuzz::fuzz_parse;
+use dcmpipe_lib::core::parser::util::parse_into_object;
+use dcmpipe_lib::core::parser::{ParseError, Parser, ParserBuilder, Result};
+
+use crate::encode::{
+    builder, explicit_vr_le_element, explicit_vr_le_element_with_vl, item_element,
+    specific_character_set, undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A dataset of sequences nested within each other to the given depth, each with undefined length
+/// and never delimited.
+fn create_nested_dataset(depth: usize) -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    for _ in 0..depth {
+        dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+        dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    }
+    dataset
+}
+
+fn parse_error(dataset: &[u8], builder: ParserBuilder<'_>) -> ParseError {
+    let parser: Parser<'_, Cursor<&[u8]>> = builder.build(Cursor::new(dataset));
+    match parser.collect::<Result<Vec<DicomElement>>>() {
+        Ok(_) => panic!("dataset should fail to parse"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn test_value_length_limit() {
+    // claims a value of nearly 4GB which isn't in the dataset
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element_with_vl(
+        tags::PatientComments.tag,
+        b"UT",
+        0xFFFF_FFF0,
+        b"Comment",
+    ));
+
+    match parse_error(&dataset, builder().max_value_length(1024)) {
+        ParseError::ValueLengthExceedsLimit { tag, vl, max } => {
+            assert_eq!(tag, tags::PatientComments.tag);
+            assert_eq!(vl, 0xFFFF_FFF0);
+            assert_eq!(max, 1024);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_value_length_limit_allows_bulk_data() -> Result<()> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientComments.tag,
+        b"UT",
+        b"A long comment..",
+    ));
+
+    let parser: Parser<'_, Cursor<&[u8]>> = builder()
+        .max_value_length(12)
+        .bulk_data_threshold(12)
+        .build(Cursor::new(&dataset));
+    let elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+    assert_eq!(elements.len(), 2);
+    assert!(elements[1].get_bulk_data().is_some());
+    Ok(())
+}
+
+#[test]
+fn test_dataset_size_limit() {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+
+    match parse_error(&dataset, builder().max_bytes(dataset.len() as u64 - 1)) {
+        ParseError::DatasetSizeExceedsLimit { max } => {
+            assert_eq!(max, dataset.len() as u64 - 1)
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_nesting_depth_limit() {
+    let dataset: Vec<u8> = create_nested_dataset(10_000);
+
+    match parse_error(&dataset, builder().max_depth(64)) {
+        ParseError::NestingDepthExceedsLimit { max } => assert_eq!(max, 64),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    let mut parser: Parser<'_, Cursor<&[u8]>> =
+        builder().max_depth(64).build(Cursor::new(&dataset));
+    match parse_into_object(&mut parser) {
+        Err(e) => assert!(matches!(e, ParseError::NestingDepthExceedsLimit { .. })),
+        Ok(_) => panic!("dataset should fail to parse"),
+    }
+}
+
+#[test]
+fn test_element_count_limit() {
+    let mut dataset: Vec<u8> = specific_character_set();
+    for _ in 0..10 {
+        dataset.extend(explicit_vr_le_element(
+            tags::PatientsName.tag,
+            b"PN",
+            b"Doe^John",
+        ));
+    }
+
+    match parse_error(&dataset, builder().max_elements(5)) {
+        ParseError::ElementCountExceedsLimit { max } => assert_eq!(max, 5),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_fuzz_parse_arbitrary_input() {
+    let mut valid: Vec<u8> = create_nested_dataset(8);
+    valid.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+
+    // a simple xorshift so the inputs are arbitrary but the same on every run
+    let mut state: u32 = 0x2545_F491;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    fuzz_parse(&[]);
+    fuzz_parse(&valid);
+    for _ in 0..256 {
+        let mut mutated: Vec<u8> = valid.clone();
+        for _ in 0..(next() % 8) + 1 {
+            let index: usize = next() as usize % mutated.len();
+            mutated[index] = next() as u8;
+        }
+        let len: usize = next() as usize % (mutated.len() + 1);
+        fuzz_parse(&mutated[..len]);
+
+        let random: Vec<u8> = (0..next() % 512).map(|_| next() as u8).collect();
+        fuzz_parse(&random);
+    }
+}
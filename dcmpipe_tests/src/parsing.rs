@@ -698,15 +698,18 @@ fn test_rle(with_std: bool) -> Result<()> {
 }
 
 #[test]
+#[cfg(feature = "deflate")]
 fn test_deflated_evrle_with_std() -> Result<()> {
     test_deflated_evrle(true)
 }
 
 #[test]
+#[cfg(feature = "deflate")]
 fn test_deflated_evrle_without_std() -> Result<()> {
     test_deflated_evrle(false)
 }
 
+#[cfg(feature = "deflate")]
 fn test_deflated_evrle(with_std: bool) -> Result<()> {
     let _dcmroot: DicomRoot<'_> =
         parse_file("./fixtures/gdcm/gdcmConformanceTests/SequenceWithUndefinedLengthNotConvertibleToDefinedLength.dcm", with_std)?;
@@ -1180,16 +1183,19 @@ fn test_sq_with_undefined_length_converted_to_defined_length(with_std: bool) ->
 }
 
 #[test]
+#[cfg(feature = "deflate")]
 fn test_sq_with_undefined_length_unconvertable_to_defined_length_with_std() -> Result<()> {
     test_sq_with_undefined_length_unconvertable_to_defined_length(true)
 }
 
 #[test]
+#[cfg(feature = "deflate")]
 fn test_sq_with_undefined_length_unconvertable_to_defined_length_without_std() -> Result<()> {
     test_sq_with_undefined_length_unconvertable_to_defined_length(false)
 }
 
 /// This is a deflated dataset
+#[cfg(feature = "deflate")]
 fn test_sq_with_undefined_length_unconvertable_to_defined_length(with_std: bool) -> Result<()> {
     let _dcmroot: DicomRoot<'_> = parse_file("./fixtures/gdcm/gdcmConformanceTests/SequenceWithUndefinedLengthNotConvertibleToDefinedLength.dcm", with_std)?;
 
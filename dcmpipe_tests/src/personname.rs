@@ -0,0 +1,101 @@
+use std::convert::TryFrom;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::{self, CSRef, DEFAULT_CHARACTER_SET};
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
+use dcmpipe_lib::core::parser::Result;
+use dcmpipe_lib::core::personname::{PersonName, PersonNameGroup};
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr;
+
+fn pn_element(cs: CSRef, bytes: &[u8]) -> DicomElement {
+    DicomElement::new(
+        tags::PatientsName.tag,
+        &vr::PN,
+        ValueLength::Explicit(bytes.len() as u32),
+        &ts::ExplicitVRLittleEndian,
+        cs,
+        bytes.to_vec(),
+        SequencePath::new(),
+    )
+}
+
+#[test]
+fn test_person_name_components() -> Result<()> {
+    let elem: DicomElement = pn_element(
+        DEFAULT_CHARACTER_SET,
+        b"Adams^John Robert Quincy^^Rev.^B.A. M.Div. ",
+    );
+    let pn: PersonName = PersonName::try_from(&elem)?;
+
+    assert_eq!(
+        pn.alphabetic,
+        PersonNameGroup {
+            family: "Adams".to_owned(),
+            given: "John Robert Quincy".to_owned(),
+            middle: "".to_owned(),
+            prefix: "Rev.".to_owned(),
+            suffix: "B.A. M.Div.".to_owned(),
+        }
+    );
+    assert!(pn.ideographic.is_empty());
+    assert!(pn.phonetic.is_empty());
+    assert_eq!(pn.to_string(), "Adams^John Robert Quincy^^Rev.^B.A. M.Div.");
+
+    let pn: PersonName = PersonName::from("Morrison-Jones^Susan^^^Ph.D., Chief Executive Officer");
+    assert_eq!(pn.alphabetic.family, "Morrison-Jones");
+    assert_eq!(pn.alphabetic.suffix, "Ph.D., Chief Executive Officer");
+
+    // trailing empty components and groups are not formatted
+    let pn: PersonName = PersonName::from("Wang^XiaoDong^^^==");
+    assert_eq!(pn.to_string(), "Wang^XiaoDong");
+
+    Ok(())
+}
+
+#[test]
+fn test_person_name_multiple_values() -> Result<()> {
+    let elem: DicomElement = pn_element(DEFAULT_CHARACTER_SET, b"Smith^Jane\\Doe^John");
+    let pns: Vec<PersonName> = Vec::<PersonName>::try_from(&elem)?;
+    assert_eq!(pns.len(), 2);
+    assert_eq!(pns[0].alphabetic.family, "Smith");
+    assert_eq!(pns[1].alphabetic.given, "John");
+    Ok(())
+}
+
+/// PS3.5 Annex H.3.1, each component group is decoded and encoded in its own repertoire
+#[test]
+fn test_person_name_component_groups() -> Result<()> {
+    let values: Vec<String> = vec!["".to_owned(), "ISO 2022 IR 87".to_owned()];
+    let cs: CSRef = charset::lookup_specific_character_set(&values).expect("Should resolve");
+    let bytes: &[u8] = b"Yamada^Tarou=\x1b$B;3ED\x1b(B^\x1b$BB@O:\x1b(B=\
+        \x1b$B$d$^$@\x1b(B^\x1b$B$?$m$&\x1b(B";
+
+    let pn: PersonName = PersonName::try_from(&pn_element(cs, bytes))?;
+    assert_eq!(pn.alphabetic.family, "Yamada");
+    assert_eq!(pn.alphabetic.given, "Tarou");
+    assert_eq!(pn.ideographic.family, "山田");
+    assert_eq!(pn.ideographic.given, "太郎");
+    assert_eq!(pn.phonetic.family, "やまだ");
+    assert_eq!(pn.phonetic.given, "たろう");
+
+    assert_eq!(pn.encode(cs).expect("Should encode"), bytes);
+    Ok(())
+}
+
+#[test]
+fn test_person_name_encode_rejects_delimiters() {
+    for component in &["Doe^Jane", "Doe=Jane", "Doe\\Jane"] {
+        let mut pn: PersonName = PersonName::from("Smith^John");
+        pn.phonetic.given = (*component).to_owned();
+        assert!(pn.encode(DEFAULT_CHARACTER_SET).is_err(), "{}", component);
+    }
+    assert_eq!(
+        PersonName::from("Smith^John")
+            .encode(DEFAULT_CHARACTER_SET)
+            .expect("Should encode"),
+        b"Smith^John"
+    );
+}
@@ -0,0 +1,154 @@
+use std::io::{Cursor, Write};
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{Parser, PushEvent, PushParser, Result};
+use libflate::deflate::Encoder;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, item_element, specific_character_set,
+    undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A dataset with a preamble, file meta, and a sequence of undefined length. When `deflated` is
+/// set the dataset following the file meta is deflated.
+fn create_dataset(deflated: bool) -> Vec<u8> {
+    let ts_uid: &[u8] = if deflated {
+        b"1.2.840.10008.1.2.1.99"
+    } else {
+        b"1.2.840.10008.1.2.1\0"
+    };
+    let ts_element: Vec<u8> = explicit_vr_le_element(tags::TransferSyntaxUID.tag, b"UI", ts_uid);
+
+    let mut dataset: Vec<u8> = vec![0; 128];
+    dataset.extend_from_slice(b"DICM");
+    dataset.extend(explicit_vr_le_element(
+        tags::FileMetaInformationGroupLength.tag,
+        b"UL",
+        &(ts_element.len() as u32).to_le_bytes(),
+    ));
+    dataset.extend(ts_element);
+
+    let mut contents: Vec<u8> = specific_character_set();
+    contents.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+
+    contents.extend(undefined_length_sequence(0x0008_1140));
+    contents.extend(item_element(0xFFFE_E000, UNDEFINED_LENGTH));
+    contents.extend(explicit_vr_le_element(0x0008_1155, b"UI", b"1.2.3\0"));
+    contents.extend(item_element(0xFFFE_E00D, 0));
+    contents.extend(item_element(0xFFFE_E0DD, 0));
+
+    contents.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &(0..64).collect::<Vec<u8>>(),
+    ));
+
+    if deflated {
+        let mut encoder: Encoder<Vec<u8>> = Encoder::new(Vec::new());
+        encoder.write_all(&contents).expect("deflate");
+        contents = encoder.finish().into_result().expect("deflate");
+    }
+    dataset.extend(contents);
+    dataset
+}
+
+/// Feeds the dataset to a push parser in chunks of the given size, collecting all elements along
+/// with the number of bytes parsed.
+fn parse_in_chunks(dataset: &[u8], chunk_size: usize) -> Result<(Vec<DicomElement>, u64)> {
+    let mut parser: PushParser<'_> = builder().build_push();
+    let mut elements: Vec<DicomElement> = Vec::new();
+    for chunk in dataset.chunks(chunk_size) {
+        parser.feed(chunk);
+        while let PushEvent::Element(element) = parser.next_event()? {
+            elements.push(element);
+        }
+    }
+    parser.finish();
+    loop {
+        match parser.next_event()? {
+            PushEvent::Element(element) => elements.push(element),
+            PushEvent::NeedMoreData => panic!("all data was fed"),
+            PushEvent::EndOfDataset => break,
+        }
+    }
+    Ok((elements, parser.get_parser().get_bytes_read()))
+}
+
+fn parse_from_reader(dataset: &[u8]) -> Result<(Vec<DicomElement>, u64)> {
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(dataset));
+    let elements: Vec<DicomElement> = parser.by_ref().collect::<Result<Vec<DicomElement>>>()?;
+    Ok((elements, parser.get_bytes_read()))
+}
+
+fn assert_chunked_input_matches_parser(deflated: bool) -> Result<()> {
+    let dataset: Vec<u8> = create_dataset(deflated);
+    let (expected, expected_bytes_read): (Vec<DicomElement>, u64) = parse_from_reader(&dataset)?;
+    assert_eq!(expected.len(), 10);
+    if !deflated {
+        assert_eq!(expected_bytes_read, dataset.len() as u64);
+    }
+
+    for chunk_size in &[1, 3, 7, 64, dataset.len()] {
+        let (elements, bytes_read): (Vec<DicomElement>, u64) =
+            parse_in_chunks(&dataset, *chunk_size)?;
+        assert_eq!(elements.len(), expected.len(), "chunk size {}", chunk_size);
+        assert_eq!(bytes_read, expected_bytes_read);
+        for (element, expected) in elements.iter().zip(expected.iter()) {
+            assert_eq!(element.tag, expected.tag);
+            assert_eq!(element.vl, expected.vl);
+            assert_eq!(element.get_data(), expected.get_data());
+            assert_eq!(element.get_tagpath(), expected.get_tagpath());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_chunked_input_matches_parser() -> Result<()> {
+    assert_chunked_input_matches_parser(false)
+}
+
+#[test]
+#[cfg(feature = "deflate")]
+fn test_chunked_deflated_input_matches_parser() -> Result<()> {
+    assert_chunked_input_matches_parser(true)
+}
+
+#[test]
+fn test_partial_element_needs_more_data() -> Result<()> {
+    let dataset: Vec<u8> = specific_character_set();
+    let mut parser: PushParser<'_> = builder().build_push();
+
+    parser.feed(&dataset[..10]);
+    assert!(matches!(parser.next_event()?, PushEvent::NeedMoreData));
+    assert_eq!(parser.get_parser().get_bytes_read(), 0);
+
+    parser.feed(&dataset[10..]);
+    match parser.next_event()? {
+        PushEvent::Element(element) => assert_eq!(element.get_data(), b"ISO_IR 100"),
+        _ => panic!("expected an element"),
+    }
+    assert!(matches!(parser.next_event()?, PushEvent::NeedMoreData));
+
+    parser.finish();
+    assert!(matches!(parser.next_event()?, PushEvent::EndOfDataset));
+    assert!(matches!(parser.next_event()?, PushEvent::EndOfDataset));
+    Ok(())
+}
+
+#[test]
+fn test_truncated_input_is_error_once_finished() {
+    let dataset: Vec<u8> = create_dataset(false);
+    let mut parser: PushParser<'_> = builder().build_push();
+    parser.feed(&dataset[..dataset.len() - 10]);
+    while let Ok(PushEvent::Element(_)) = parser.next_event() {}
+
+    parser.finish();
+    assert!(parser.next_event().is_err());
+    assert!(matches!(parser.next_event(), Ok(PushEvent::EndOfDataset)));
+}
@@ -0,0 +1,187 @@
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{
+    Diagnostic, DiagnosticKind, Parser, ParserBuilder, PushEvent, PushParser, Result,
+};
+use dcmpipe_lib::defn::tag::{TagNode, TagPath};
+use dcmpipe_lib::defn::vr;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, explicit_vr_le_element_with_vl, implicit_vr_le_element,
+    item_element, specific_character_set, undefined_length_sequence,
+};
+
+/// An explicit VR dataset with an element mistakenly encoded as implicit VR.
+fn create_implicit_element_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(implicit_vr_le_element(tags::PatientsName.tag, b"Doe^John"));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientID.tag,
+        b"LO",
+        b"12345678",
+    ));
+    dataset
+}
+
+/// A sequence whose item has an explicit length, containing an element whose value length runs
+/// past the end of the item.
+fn create_overrun_dataset() -> Vec<u8> {
+    let mut item_contents: Vec<u8> =
+        explicit_vr_le_element_with_vl(tags::ReferencedSOPClassUID.tag, b"UI", 0x40, b"1.2\0");
+    item_contents.extend(explicit_vr_le_element(
+        tags::ReferencedSOPInstanceUID.tag,
+        b"UI",
+        b"1.3\0",
+    ));
+
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    dataset.extend(item_element(tags::Item.tag, item_contents.len() as u32));
+    dataset.extend(item_contents);
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset
+}
+
+fn recover_builder() -> ParserBuilder<'static> {
+    builder().recover(true)
+}
+
+fn parse(dataset: &[u8]) -> Result<(Vec<DicomElement>, Vec<Diagnostic>)> {
+    let mut parser: Parser<'_, Cursor<&[u8]>> = recover_builder().build(Cursor::new(dataset));
+    let elements: Vec<DicomElement> = parser.by_ref().collect::<Result<Vec<DicomElement>>>()?;
+    Ok((elements, parser.take_diagnostics()))
+}
+
+#[test]
+fn test_unknown_vr_read_as_implicit() -> Result<()> {
+    let dataset: Vec<u8> = create_implicit_element_dataset();
+    let (elements, diagnostics): (Vec<DicomElement>, Vec<Diagnostic>) = parse(&dataset)?;
+
+    assert_eq!(elements.len(), 3);
+    assert_eq!(elements[1].tag, tags::PatientsName.tag);
+    assert_eq!(elements[1].vr, &vr::PN);
+    assert_eq!(elements[1].get_data(), b"Doe^John");
+    assert_eq!(elements[2].get_data(), b"12345678");
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic {
+            offset: 18,
+            tagpath: tags::PatientsName.tag.into(),
+            kind: DiagnosticKind::UnknownExplicitVR(0x0800),
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_value_length_overrun_resyncs() -> Result<()> {
+    let dataset: Vec<u8> = create_overrun_dataset();
+    let (elements, diagnostics): (Vec<DicomElement>, Vec<Diagnostic>) = parse(&dataset)?;
+
+    let parsed_tags: Vec<u32> = elements.iter().map(|element| element.tag).collect();
+    assert_eq!(
+        parsed_tags,
+        vec![
+            tags::SpecificCharacterSet.tag,
+            tags::ReferencedImageSequence.tag,
+            tags::Item.tag,
+            tags::ReferencedSOPInstanceUID.tag,
+            tags::SequenceDelimitationItem.tag,
+            tags::PatientsName.tag,
+        ]
+    );
+    assert_eq!(
+        elements[3].get_tagpath(),
+        TagPath::from(vec![
+            TagNode::new(tags::ReferencedImageSequence.tag, Some(1)),
+            tags::ReferencedSOPInstanceUID.tag.into(),
+        ])
+    );
+
+    let tagpath: TagPath = TagPath::from(vec![
+        TagNode::new(tags::ReferencedImageSequence.tag, Some(1)),
+        tags::ReferencedSOPClassUID.tag.into(),
+    ]);
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic {
+                offset: 38,
+                tagpath: tagpath.clone(),
+                kind: DiagnosticKind::ValueLengthOverrun {
+                    vl: 0x40,
+                    remaining: 16
+                },
+            },
+            Diagnostic {
+                offset: 46,
+                tagpath,
+                kind: DiagnosticKind::SkippedBytes(4),
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_truncated_value_is_returned() -> Result<()> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element_with_vl(
+        tags::PatientsName.tag,
+        b"PN",
+        16,
+        b"Doe^John",
+    ));
+
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    assert!(parser.collect::<Result<Vec<DicomElement>>>().is_err());
+
+    let (elements, diagnostics): (Vec<DicomElement>, Vec<Diagnostic>) = parse(&dataset)?;
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[1].get_data(), b"Doe^John");
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic {
+            offset: 18,
+            tagpath: tags::PatientsName.tag.into(),
+            kind: DiagnosticKind::TruncatedValue { vl: 16, read: 8 },
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_push_parser_recovers() -> Result<()> {
+    for dataset in &[create_implicit_element_dataset(), create_overrun_dataset()] {
+        let (expected, expected_diagnostics): (Vec<DicomElement>, Vec<Diagnostic>) =
+            parse(dataset)?;
+
+        let mut parser: PushParser<'_> = recover_builder().build_push();
+        let mut elements: Vec<DicomElement> = Vec::new();
+        for byte in dataset.chunks(1) {
+            parser.feed(byte);
+            while let PushEvent::Element(element) = parser.next_event()? {
+                elements.push(element);
+            }
+        }
+        parser.finish();
+        while let PushEvent::Element(element) = parser.next_event()? {
+            elements.push(element);
+        }
+
+        assert_eq!(elements.len(), expected.len());
+        assert_eq!(
+            parser.get_parser().get_diagnostics(),
+            &expected_diagnostics[..]
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{ParserBuilder, Result};
+use dcmpipe_lib::core::seekable::{ElementOffsets, SeekableDataset};
+use dcmpipe_lib::defn::tag::TagPath;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, item_element, specific_character_set, UNDEFINED_LENGTH,
+};
+
+/// Encodes an item or delimiter followed by the given value.
+fn item(tag: u32, length: u32, value: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = item_element(tag, length);
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// A dataset of 2x2 8-bit images, with the given number of frames.
+fn image_header(frames: &[u8]) -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::SamplesperPixel.tag,
+        b"US",
+        &1u16.to_le_bytes(),
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::NumberofFrames.tag,
+        b"IS",
+        frames,
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::Rows.tag,
+        b"US",
+        &2u16.to_le_bytes(),
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::Columns.tag,
+        b"US",
+        &2u16.to_le_bytes(),
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::BitsAllocated.tag,
+        b"US",
+        &8u16.to_le_bytes(),
+    ));
+    dataset
+}
+
+fn open(dataset: Vec<u8>) -> Result<SeekableDataset<Cursor<Vec<u8>>>> {
+    let builder: ParserBuilder<'_> = builder().bulk_data_threshold(8);
+    SeekableDataset::open(builder, Cursor::new(dataset))
+}
+
+#[test]
+fn test_element_offsets() -> Result<()> {
+    let mut dataset: Vec<u8> = image_header(b"3 ");
+    let pixel_data: Vec<u8> = (0..12).collect::<Vec<u8>>();
+    dataset.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &pixel_data,
+    ));
+    let mut seekable: SeekableDataset<Cursor<Vec<u8>>> = open(dataset)?;
+
+    let name_path: TagPath = TagPath::from(tags::PatientsName.tag);
+    let name: &DicomElement = seekable.get_element(&name_path).expect("should have name");
+    assert_eq!(
+        name.get_offsets(),
+        Some(ElementOffsets {
+            element_offset: 18,
+            header_length: 8,
+            value_offset: 26,
+        })
+    );
+
+    let pixel_data_path: TagPath = TagPath::from(tags::PixelData.tag);
+    let offsets: ElementOffsets = seekable
+        .get_element(&pixel_data_path)
+        .and_then(DicomElement::get_offsets)
+        .expect("should have pixel data");
+    assert_eq!(offsets.header_length, 12);
+    assert_eq!(offsets.value_offset, offsets.element_offset + 12);
+
+    let name: DicomElement = seekable
+        .read_element(&name_path)?
+        .expect("should have name");
+    assert_eq!(String::try_from(&name)?, "Doe^John");
+    assert_eq!(seekable.read_value(&pixel_data_path)?, Some(pixel_data));
+    assert_eq!(
+        seekable.read_value(&TagPath::from(tags::Modality.tag))?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_native_frames() -> Result<()> {
+    let mut dataset: Vec<u8> = image_header(b"3 ");
+    dataset.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &(0..12).collect::<Vec<u8>>(),
+    ));
+    let mut seekable: SeekableDataset<Cursor<Vec<u8>>> = open(dataset)?;
+
+    assert_eq!(seekable.get_frame_count(), 3);
+    assert_eq!(seekable.read_frame(2)?, vec![8, 9, 10, 11]);
+    assert_eq!(seekable.read_frame(0)?, vec![0, 1, 2, 3]);
+    assert!(seekable.read_frame(3).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_encapsulated_frames() -> Result<()> {
+    // the first frame is split across two fragments, located by the basic offset table
+    let mut offset_table: Vec<u8> = 0u32.to_le_bytes().to_vec();
+    offset_table.extend_from_slice(&24u32.to_le_bytes());
+
+    let mut dataset: Vec<u8> = image_header(b"2 ");
+    dataset.extend(explicit_vr_le_element(tags::PixelData.tag, b"OB", &[]));
+    let vl_pos: usize = dataset.len() - 4;
+    dataset[vl_pos..].copy_from_slice(&UNDEFINED_LENGTH.to_le_bytes());
+    dataset.extend(item(0xFFFE_E000, 8, &offset_table));
+    dataset.extend(item(0xFFFE_E000, 4, &[1, 2, 3, 4]));
+    dataset.extend(item(0xFFFE_E000, 4, &[5, 6, 7, 8]));
+    dataset.extend(item(0xFFFE_E000, 4, &[9, 10, 11, 12]));
+    dataset.extend(item(0xFFFE_E0DD, 0, &[]));
+    let mut seekable: SeekableDataset<Cursor<Vec<u8>>> = open(dataset)?;
+
+    assert_eq!(seekable.get_frame_count(), 2);
+    assert_eq!(seekable.read_frame(0)?, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(seekable.read_frame(1)?, vec![9, 10, 11, 12]);
+
+    // without an offset table each fragment is a frame
+    let mut dataset: Vec<u8> = image_header(b"2 ");
+    dataset.extend(explicit_vr_le_element(tags::PixelData.tag, b"OB", &[]));
+    let vl_pos: usize = dataset.len() - 4;
+    dataset[vl_pos..].copy_from_slice(&UNDEFINED_LENGTH.to_le_bytes());
+    dataset.extend(item(0xFFFE_E000, 0, &[]));
+    dataset.extend(item(0xFFFE_E000, 4, &[1, 2, 3, 4]));
+    dataset.extend(item(0xFFFE_E000, 4, &[5, 6, 7, 8]));
+    dataset.extend(item(0xFFFE_E0DD, 0, &[]));
+    let mut seekable: SeekableDataset<Cursor<Vec<u8>>> = open(dataset)?;
+
+    assert_eq!(seekable.read_frame(1)?, vec![5, 6, 7, 8]);
+    Ok(())
+}
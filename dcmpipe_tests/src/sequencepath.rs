@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequenceElement;
+use dcmpipe_lib::core::parser::{Parser, ParserBuilder, Result};
+use dcmpipe_lib::core::tagstop::TagStop;
+use dcmpipe_lib::defn::tag::{TagNode, TagPath};
+
+use crate::encode::{
+    builder, explicit_vr_le_element, item_element, specific_character_set,
+    undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A sequence with two items where the first item has a nested sequence.
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPClassUID.tag,
+        b"UI",
+        b"1.2\0",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPInstanceUID.tag,
+        b"UI",
+        b"1.3\0",
+    ));
+    dataset.extend(undefined_length_sequence(
+        tags::ReferencedSeriesSequence.tag,
+    ));
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(explicit_vr_le_element(
+        tags::SeriesInstanceUID.tag,
+        b"UI",
+        b"1.4\0",
+    ));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(explicit_vr_le_element(
+        tags::ReferencedSOPInstanceUID.tag,
+        b"UI",
+        b"1.5\0",
+    ));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset
+}
+
+fn find<'e>(elements: &'e [DicomElement], data: &[u8]) -> &'e DicomElement {
+    elements
+        .iter()
+        .find(|element| element.get_data().as_slice() == data)
+        .expect("element should be parsed")
+}
+
+#[test]
+fn test_sequence_paths_share_ancestors() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    let elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+
+    let class_uid: &DicomElement = find(&elements, b"1.2\0");
+    let instance_uid: &DicomElement = find(&elements, b"1.3\0");
+    let series_uid: &DicomElement = find(&elements, b"1.4\0");
+    let second_instance_uid: &DicomElement = find(&elements, b"1.5\0");
+    let name: &DicomElement = find(&elements, b"Doe^John");
+
+    // paths are unaffected by later items incrementing the item number of the sequence
+    assert_eq!(
+        class_uid.get_tagpath(),
+        TagPath::from(vec![
+            TagNode::new(tags::ReferencedImageSequence.tag, Some(1)),
+            tags::ReferencedSOPClassUID.tag.into(),
+        ])
+    );
+    assert_eq!(
+        series_uid.get_tagpath(),
+        TagPath::from(vec![
+            TagNode::new(tags::ReferencedImageSequence.tag, Some(1)),
+            TagNode::new(tags::ReferencedSeriesSequence.tag, Some(1)),
+            tags::SeriesInstanceUID.tag.into(),
+        ])
+    );
+    assert_eq!(
+        second_instance_uid.get_tagpath(),
+        TagPath::from(vec![
+            TagNode::new(tags::ReferencedImageSequence.tag, Some(2)),
+            tags::ReferencedSOPInstanceUID.tag.into(),
+        ])
+    );
+    assert!(name.get_sequence_path().is_empty());
+    assert_eq!(series_uid.get_sequence_path().len(), 4);
+
+    // elements within the same item share the sequence elements of their path
+    assert!(std::ptr::eq(
+        class_uid.get_sequence_path().last().expect("in item"),
+        instance_uid.get_sequence_path().last().expect("in item"),
+    ));
+    assert!(std::ptr::eq(
+        class_uid.get_sequence_path().first().expect("in sequence"),
+        series_uid.get_sequence_path().first().expect("in sequence"),
+    ));
+    // the next item copies only the sequence element whose item number changed
+    assert!(!std::ptr::eq(
+        class_uid.get_sequence_path().first().expect("in sequence"),
+        second_instance_uid
+            .get_sequence_path()
+            .first()
+            .expect("in sequence"),
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_sequence_paths_shared_in_rtstruct() -> Result<()> {
+    let file: File = File::open("./fixtures/gdcm/gdcmConformanceTests/RTStruct_VRDSAsVRUN.dcm")?;
+    let parser: Parser<'_, File> = ParserBuilder::default()
+        .tagstop(TagStop::BeforeTag(tags::PixelData.tag.into()))
+        .dictionary(&STANDARD_DICOM_DICTIONARY)
+        .build(file);
+    let elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+
+    // each element previously held its own copy of every sequence element in its path
+    let path_entries: usize = elements
+        .iter()
+        .map(|element| element.get_sequence_path().len())
+        .sum();
+    let distinct_entries: usize = elements
+        .iter()
+        .flat_map(|element| element.get_sequence_path().iter())
+        .map(|sq: &SequenceElement| sq as *const SequenceElement)
+        .collect::<HashSet<*const SequenceElement>>()
+        .len();
+
+    assert!(path_entries > 0);
+    assert!(
+        distinct_entries * 4 < path_entries,
+        "{} distinct sequence elements across {} path entries",
+        distinct_entries,
+        path_entries
+    );
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::slice::SliceElement;
+use dcmpipe_lib::core::parser::{ErrorContext, Parser, Result, SliceParser};
+
+use crate::encode::{builder, explicit_vr_le_element, specific_character_set};
+
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(tags::Rows.tag, b"US", &[0, 2]));
+    dataset.extend(explicit_vr_le_element(
+        tags::PixelData.tag,
+        b"OB",
+        &(0..64).collect::<Vec<u8>>(),
+    ));
+    dataset
+}
+
+#[test]
+fn test_values_borrow_from_dataset() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let elements: Vec<SliceElement<'_>> = builder()
+        .build_slice(&dataset)
+        .collect::<Result<Vec<SliceElement<'_>>>>()?;
+    assert_eq!(elements.len(), 4);
+
+    // the specific character set is read by the parser so its value is owned
+    assert!(matches!(elements[0].value, Cow::Owned(_)));
+    assert_eq!(&*elements[0].value, b"ISO_IR 100");
+
+    for element in elements.iter().skip(1) {
+        let value: &[u8] = match &element.value {
+            Cow::Borrowed(value) => value,
+            Cow::Owned(_) => panic!("value should be borrowed"),
+        };
+        let offset: usize = element.get_offsets().expect("offsets").value_offset as usize;
+        assert_eq!(value.as_ptr(), dataset[offset..].as_ptr());
+        assert!(element.element.get_data().is_empty());
+    }
+    assert_eq!(&*elements[1].value, b"Doe^John");
+    assert_eq!(elements[3].value.len(), 64);
+    assert_eq!(elements[3].value[63], 63);
+    Ok(())
+}
+
+/// Values are skipped by advancing through the slice rather than being read and discarded, which
+/// shows in the bytes an error reports as having been read before it.
+#[test]
+fn test_values_are_skipped_without_reading() {
+    let mut dataset: Vec<u8> = create_dataset();
+    // a truncated tag following the pixel data
+    dataset.extend_from_slice(&[0x10, 0x00]);
+
+    let mut parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    assert!(parser.by_ref().any(|element| element.is_err()));
+    let context: &ErrorContext = parser.get_error_context().expect("context");
+    let offset: u64 = context.offset;
+    assert!(context.bytes_before.ends_with(&[62, 63, 0x10, 0x00]));
+
+    let mut slice_parser: SliceParser<'_, '_> = builder().build_slice(&dataset);
+    assert!(slice_parser.by_ref().any(|element| element.is_err()));
+    let context: &ErrorContext = slice_parser
+        .get_parser()
+        .get_error_context()
+        .expect("context");
+    assert_eq!(context.offset, offset);
+    assert_eq!(context.bytes_before, [0x10, 0x00]);
+}
+
+#[test]
+fn test_into_element_matches_parser() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let mut slice_parser: SliceParser<'_, '_> = builder().build_slice(&dataset);
+    let from_slice: Vec<DicomElement> = slice_parser
+        .by_ref()
+        .map(|element| element.map(SliceElement::into_element))
+        .collect::<Result<Vec<DicomElement>>>()?;
+    assert_eq!(
+        slice_parser.get_parser().get_bytes_read(),
+        dataset.len() as u64
+    );
+
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    let from_reader: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+
+    assert_eq!(from_slice.len(), from_reader.len());
+    for (slice_elem, reader_elem) in from_slice.iter().zip(from_reader.iter()) {
+        assert_eq!(slice_elem.tag, reader_elem.tag);
+        assert_eq!(slice_elem.get_data(), reader_elem.get_data());
+        assert!(slice_elem.get_bulk_data().is_none());
+    }
+    assert_eq!(String::try_from(&from_slice[1])?, "Doe^John");
+    assert_eq!(u16::try_from(&from_slice[2])?, 512);
+    Ok(())
+}
+
+/// Odd length values are padded to be even length the same as values read by `Parser`, which
+/// requires copying them.
+#[test]
+fn test_odd_length_values_are_padded() -> Result<()> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^J",
+    ));
+
+    let elements: Vec<SliceElement<'_>> = builder()
+        .build_slice(&dataset)
+        .collect::<Result<Vec<SliceElement<'_>>>>()?;
+    assert!(matches!(elements[1].value, Cow::Owned(_)));
+    assert_eq!(&*elements[1].value, b"Doe^J\0");
+
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    let from_reader: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+    assert_eq!(&*elements[1].value, from_reader[1].get_data().as_slice());
+
+    let element: DicomElement = elements.into_iter().nth(1).expect("PN").into_element();
+    assert_eq!(element.get_data(), from_reader[1].get_data());
+    assert_eq!(String::try_from(&element)?, "Doe^J");
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::convert::TryFrom;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{Parser, ParserBuilder, Result};
+use dcmpipe_lib::core::source::{entry_name, for_each_entry_in, split_entry_name, SourceKind};
+use libflate::gzip;
+
+use crate::encode::{builder, explicit_vr_le_element, EXPLICIT_VR_LITTLE_ENDIAN};
+
+/// Creates a file with the preamble, prefix and File Meta, followed by the Patient ID.
+fn create_file(patient_id: &[u8]) -> Vec<u8> {
+    let ts: Vec<u8> = explicit_vr_le_element(
+        tags::TransferSyntaxUID.tag,
+        b"UI",
+        EXPLICIT_VR_LITTLE_ENDIAN,
+    );
+
+    let mut file: Vec<u8> = vec![0; 128];
+    file.extend_from_slice(b"DICM");
+    file.extend(explicit_vr_le_element(
+        tags::FileMetaInformationGroupLength.tag,
+        b"UL",
+        &(ts.len() as u32).to_le_bytes(),
+    ));
+    file.extend(ts);
+    file.extend(explicit_vr_le_element(
+        tags::PatientID.tag,
+        b"LO",
+        patient_id,
+    ));
+    file
+}
+
+fn create_zip(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut writer: zip::ZipWriter<Cursor<Vec<u8>>> = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .add_directory("series", zip::write::FileOptions::default())
+        .expect("zip directory");
+    for (name, data) in entries {
+        writer
+            .start_file(*name, zip::write::FileOptions::default())
+            .expect("zip entry");
+        writer.write_all(data).expect("zip entry data");
+    }
+    writer.finish().expect("zip").into_inner()
+}
+
+fn create_tar(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut builder: tar::Builder<Vec<u8>> = tar::Builder::new(Vec::new());
+    for (name, data) in entries {
+        let mut header: tar::Header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, data.as_slice())
+            .expect("tar entry");
+    }
+    builder.into_inner().expect("tar")
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder: gzip::Encoder<Vec<u8>> = gzip::Encoder::new(Vec::new()).expect("gzip");
+    encoder.write_all(data).expect("gzip data");
+    encoder.finish().into_result().expect("gzip")
+}
+
+/// Parses each entry of the source, returning the entry names along with their Patient ID.
+fn parse_entries(kind: SourceKind, name: &str, source: Vec<u8>) -> Result<Vec<(String, String)>> {
+    let builder: ParserBuilder<'_> = builder();
+    let mut parsed: Vec<(String, String)> = Vec::new();
+    for_each_entry_in(
+        kind,
+        name,
+        Cursor::new(source),
+        |entry_name: &str, entry: &mut dyn Read| -> Result<()> {
+            let parser: Parser<'_, &mut dyn Read> = builder.build(entry);
+            let elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+            let patient_id: String =
+                String::try_from(elements.last().expect("entry should have elements"))?;
+            parsed.push((entry_name.to_owned(), patient_id));
+            Ok(())
+        },
+    )?;
+    Ok(parsed)
+}
+
+fn expected_entries(container: &str) -> Vec<(String, String)> {
+    vec![
+        (
+            entry_name(container, "series/IMG0001"),
+            "ID000001".to_owned(),
+        ),
+        (
+            entry_name(container, "series/IMG0002"),
+            "ID000002".to_owned(),
+        ),
+    ]
+}
+
+fn create_entries() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("series/IMG0001", create_file(b"ID000001")),
+        ("series/IMG0002", create_file(b"ID000002")),
+    ]
+}
+
+#[test]
+fn test_source_kind_from_path() {
+    let kind = |path: &str| SourceKind::from_path(Path::new(path));
+    assert_eq!(kind("a/study.zip"), SourceKind::Zip);
+    assert_eq!(kind("a/study.TAR"), SourceKind::Tar);
+    assert_eq!(kind("a/study.tar.gz"), SourceKind::TarGzip);
+    assert_eq!(kind("a/study.tgz"), SourceKind::TarGzip);
+    assert_eq!(kind("a/IMG0001.dcm.gz"), SourceKind::Gzip);
+    assert_eq!(kind("a/IMG0001"), SourceKind::File);
+    assert!(kind("a/study.zip").is_container());
+    assert!(!kind("a/IMG0001.dcm.gz").is_container());
+}
+
+#[test]
+fn test_entry_names() {
+    let name: String = entry_name("in/study.zip", "series/IMG0001");
+    assert_eq!(name, "in/study.zip!/series/IMG0001");
+    assert_eq!(
+        split_entry_name(&name),
+        ("in/study.zip", Some("series/IMG0001"))
+    );
+    assert_eq!(split_entry_name("in/IMG0001"), ("in/IMG0001", None));
+}
+
+#[test]
+fn test_zip_entries() -> Result<()> {
+    let zip: Vec<u8> = create_zip(&create_entries());
+    assert_eq!(
+        parse_entries(SourceKind::Zip, "study.zip", zip)?,
+        expected_entries("study.zip")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tar_entries() -> Result<()> {
+    let tar: Vec<u8> = create_tar(&create_entries());
+    assert_eq!(
+        parse_entries(SourceKind::Tar, "study.tar", tar.clone())?,
+        expected_entries("study.tar")
+    );
+    assert_eq!(
+        parse_entries(SourceKind::TarGzip, "study.tar.gz", gzip(&tar))?,
+        expected_entries("study.tar.gz")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_gzip_file() -> Result<()> {
+    let file: Vec<u8> = gzip(&create_file(b"ID000001"));
+    assert_eq!(
+        parse_entries(SourceKind::Gzip, "IMG0001.dcm.gz", file)?,
+        vec![("IMG0001.dcm.gz".to_owned(), "ID000001".to_owned())]
+    );
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::io::{self, Cursor};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{AsyncParser, Parser, Result};
+use futures::executor::block_on;
+use futures::StreamExt;
+use tokio::io::AsyncRead;
+
+use crate::encode::{builder, explicit_vr_le_element, specific_character_set};
+
+/// Reads a few bytes at a time, and is only ready to be read from every other time it's polled.
+struct TrickleReader<'a> {
+    data: &'a [u8],
+    ready: bool,
+}
+
+impl<'a> AsyncRead for TrickleReader<'a> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.ready = !self.ready;
+        if !self.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let len: usize = self.data.len().min(buf.len()).min(5);
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Poll::Ready(Ok(len))
+    }
+}
+
+/// Fails once all data has been read.
+struct FailingReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> AsyncRead for FailingReader<'a> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.data.is_empty() {
+            return Poll::Ready(Err(io::Error::from(io::ErrorKind::ConnectionReset)));
+        }
+        let len: usize = self.data.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Poll::Ready(Ok(len))
+    }
+}
+
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientID.tag,
+        b"LO",
+        b"12345678",
+    ));
+    dataset
+}
+
+#[test]
+fn test_async_parser_matches_parser() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().build(Cursor::new(&dataset));
+    let expected: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+
+    let reader: TrickleReader<'_> = TrickleReader {
+        data: &dataset,
+        ready: false,
+    };
+    let mut parser: AsyncParser<'_, TrickleReader<'_>> = builder().build_async(reader);
+    let elements: Vec<DicomElement> =
+        block_on(parser.by_ref().collect::<Vec<Result<DicomElement>>>())
+            .into_iter()
+            .collect::<Result<Vec<DicomElement>>>()?;
+
+    assert_eq!(elements.len(), 3);
+    assert_eq!(elements.len(), expected.len());
+    for (element, expected) in elements.iter().zip(expected.iter()) {
+        assert_eq!(element.tag, expected.tag);
+        assert_eq!(element.get_data(), expected.get_data());
+    }
+    assert_eq!(parser.get_parser().get_bytes_read(), dataset.len() as u64);
+    Ok(())
+}
+
+#[test]
+fn test_async_parser_read_error_ends_stream() {
+    let dataset: Vec<u8> = create_dataset();
+    let reader: FailingReader<'_> = FailingReader { data: &dataset };
+    let results: Vec<Result<DicomElement>> = block_on(
+        builder()
+            .build_async(reader)
+            .collect::<Vec<Result<DicomElement>>>(),
+    );
+
+    assert_eq!(results.len(), 4);
+    assert!(results[..3].iter().all(|result| result.is_ok()));
+    assert!(results[3].is_err());
+}
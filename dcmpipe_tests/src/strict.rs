@@ -0,0 +1,162 @@
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{ParseError, Parser, Result};
+use dcmpipe_lib::defn::vr;
+
+use crate::encode::{
+    builder, explicit_vr_le_element, implicit_vr_le_element, item_element, specific_character_set,
+    tag_bytes, undefined_length_sequence, EXPLICIT_VR_LITTLE_ENDIAN, UNDEFINED_LENGTH,
+};
+
+/// Creates a file with the preamble, prefix and File Meta, followed by the given dataset elements.
+/// The File Meta is encoded as explicit or implicit VR little endian.
+fn create_file(fmi_explicit_vr: bool, dataset: &[u8]) -> Vec<u8> {
+    let element = |tag: u32, vr: &[u8; 2], value: &[u8]| {
+        if fmi_explicit_vr {
+            explicit_vr_le_element(tag, vr, value)
+        } else {
+            implicit_vr_le_element(tag, value)
+        }
+    };
+    let ts: Vec<u8> = element(
+        tags::TransferSyntaxUID.tag,
+        b"UI",
+        EXPLICIT_VR_LITTLE_ENDIAN,
+    );
+
+    let mut file: Vec<u8> = vec![0; 128];
+    file.extend_from_slice(b"DICM");
+    file.extend(element(
+        tags::FileMetaInformationGroupLength.tag,
+        b"UL",
+        &(ts.len() as u32).to_le_bytes(),
+    ));
+    file.extend(ts);
+    file.extend_from_slice(dataset);
+    file
+}
+
+/// A conformant dataset, with a sequence whose items repeat the same tags.
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    for _ in 0..2 {
+        dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+        dataset.extend(explicit_vr_le_element(
+            tags::ReferencedSOPClassUID.tag,
+            b"UI",
+            b"1.2\0",
+        ));
+        dataset.extend(explicit_vr_le_element(
+            tags::ReferencedSOPInstanceUID.tag,
+            b"UI",
+            b"1.3\0",
+        ));
+        dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    }
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset
+}
+
+fn parse(file: &[u8], strict: bool) -> Result<Vec<DicomElement>> {
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().strict(strict).build(Cursor::new(file));
+    parser.collect::<Result<Vec<DicomElement>>>()
+}
+
+/// Parses the file leniently, which should succeed, and in strict mode returning its error.
+fn parse_strict_error(file: &[u8]) -> ParseError {
+    assert!(parse(file, false).is_ok());
+    match parse(file, true) {
+        Ok(_) => panic!("file should fail to parse in strict mode"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn test_conformant_file() -> Result<()> {
+    let file: Vec<u8> = create_file(true, &create_dataset());
+    assert_eq!(parse(&file, true)?.len(), 14);
+    Ok(())
+}
+
+#[test]
+fn test_missing_dicom_prefix() {
+    let dataset: Vec<u8> = create_dataset();
+    assert!(matches!(
+        parse_strict_error(&dataset),
+        ParseError::MissingDICOMPrefix
+    ));
+}
+
+#[test]
+fn test_file_meta_implicit_vr() {
+    let file: Vec<u8> = create_file(false, &create_dataset());
+    match parse_strict_error(&file) {
+        ParseError::FileMetaNotExplicitVRLittleEndian { ts } => {
+            assert!(!ts.explicit_vr);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_odd_value_length() {
+    let mut dataset: Vec<u8> = create_dataset();
+    dataset.extend(explicit_vr_le_element(tags::PatientID.tag, b"LO", b"12345"));
+    let file: Vec<u8> = create_file(true, &dataset);
+    match parse_strict_error(&file) {
+        ParseError::OddValueLength { tag, vl } => {
+            assert_eq!(tag, tags::PatientID.tag);
+            assert_eq!(vl, 5);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_undefined_length_private_element() {
+    // a private sequence encoded as UN with undefined length, which the parser otherwise reads as
+    // a sequence of implicit VR elements
+    let mut dataset: Vec<u8> = create_dataset();
+    dataset.extend(tag_bytes(0x0011_1001));
+    dataset.extend_from_slice(b"UN");
+    dataset.extend_from_slice(&[0, 0]);
+    dataset.extend_from_slice(&UNDEFINED_LENGTH.to_le_bytes());
+    dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+    dataset.extend(implicit_vr_le_element(tags::PatientID.tag, b"12345678"));
+    dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    let file: Vec<u8> = create_file(true, &dataset);
+    match parse_strict_error(&file) {
+        ParseError::UnexpectedUndefinedLength { tag, vr } => {
+            assert_eq!(tag, 0x0011_1001);
+            assert_eq!(vr, &vr::UN);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_tag_out_of_order() {
+    let mut dataset: Vec<u8> = create_dataset();
+    dataset.extend(explicit_vr_le_element(
+        tags::SpecificCharacterSet.tag,
+        b"CS",
+        b"ISO_IR 100",
+    ));
+    let file: Vec<u8> = create_file(true, &dataset);
+    match parse_strict_error(&file) {
+        ParseError::TagOutOfOrder { tag, previous } => {
+            assert_eq!(tag, tags::SpecificCharacterSet.tag);
+            assert_eq!(previous, tags::PatientsName.tag);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
@@ -0,0 +1,176 @@
+use std::io::Cursor;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::parser::{Parser, PushEvent, PushParser, Result};
+use dcmpipe_lib::core::tagstop::TagStop;
+use dcmpipe_lib::defn::tag::{TagNode, TagPath};
+
+use crate::encode::{
+    builder, explicit_vr_le_element, item_element, specific_character_set,
+    undefined_length_sequence, UNDEFINED_LENGTH,
+};
+
+/// A dataset with a sequence of two items followed by elements of groups 0010, 0020 and 0028.
+fn create_dataset() -> Vec<u8> {
+    let mut dataset: Vec<u8> = specific_character_set();
+    dataset.extend(undefined_length_sequence(tags::ReferencedImageSequence.tag));
+    for _ in 0..2 {
+        dataset.extend(item_element(tags::Item.tag, UNDEFINED_LENGTH));
+        dataset.extend(explicit_vr_le_element(
+            tags::ReferencedSOPClassUID.tag,
+            b"UI",
+            b"1.2\0",
+        ));
+        dataset.extend(explicit_vr_le_element(
+            tags::ReferencedSOPInstanceUID.tag,
+            b"UI",
+            b"1.3\0",
+        ));
+        dataset.extend(item_element(tags::ItemDelimitationItem.tag, 0));
+    }
+    dataset.extend(item_element(tags::SequenceDelimitationItem.tag, 0));
+    dataset.extend(explicit_vr_le_element(
+        tags::PatientsName.tag,
+        b"PN",
+        b"Doe^John",
+    ));
+    dataset.extend(explicit_vr_le_element(
+        tags::StudyInstanceUID.tag,
+        b"UI",
+        b"1.4\0",
+    ));
+    dataset.extend(explicit_vr_le_element(tags::SeriesNumber.tag, b"IS", b"1 "));
+    dataset.extend(explicit_vr_le_element(tags::Rows.tag, b"US", &[0, 2]));
+    dataset
+}
+
+fn parse_tags(tagstop: TagStop) -> Result<Vec<u32>> {
+    let dataset: Vec<u8> = create_dataset();
+    let parser: Parser<'_, Cursor<&[u8]>> = builder().tagstop(tagstop).build(Cursor::new(&dataset));
+    let elements: Vec<DicomElement> = parser.collect::<Result<Vec<DicomElement>>>()?;
+    Ok(elements.iter().map(|element| element.tag).collect())
+}
+
+/// The tags of the elements of `create_dataset` up to and including the first item.
+fn tags_through_first_item() -> Vec<u32> {
+    vec![
+        tags::SpecificCharacterSet.tag,
+        tags::ReferencedImageSequence.tag,
+        tags::Item.tag,
+        tags::ReferencedSOPClassUID.tag,
+        tags::ReferencedSOPInstanceUID.tag,
+        tags::ItemDelimitationItem.tag,
+    ]
+}
+
+#[test]
+fn test_after_first_item_of_sequence() -> Result<()> {
+    let tagpath: TagPath = TagPath::from(vec![TagNode::new(
+        tags::ReferencedImageSequence.tag,
+        Some(1),
+    )]);
+    assert_eq!(
+        parse_tags(TagStop::AfterTagPath(tagpath))?,
+        tags_through_first_item()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_before_tag_within_sequence() -> Result<()> {
+    let tagpath: TagPath = TagPath::from(vec![
+        TagNode::new(tags::ReferencedImageSequence.tag, Some(2)),
+        tags::ReferencedSOPInstanceUID.tag.into(),
+    ]);
+    let mut expected: Vec<u32> = tags_through_first_item();
+    expected.extend(&[tags::Item.tag, tags::ReferencedSOPClassUID.tag]);
+    assert_eq!(parse_tags(TagStop::BeforeTagPath(tagpath))?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_after_tag_within_any_item() -> Result<()> {
+    // without an item number the path refers to the element within any item, so parsing stops
+    // after the first one
+    let tagpath: TagPath = TagPath::from(vec![
+        tags::ReferencedImageSequence.tag,
+        tags::ReferencedSOPClassUID.tag,
+    ]);
+    assert_eq!(
+        parse_tags(TagStop::AfterTagPath(tagpath))?,
+        vec![
+            tags::SpecificCharacterSet.tag,
+            tags::ReferencedImageSequence.tag,
+            tags::Item.tag,
+            tags::ReferencedSOPClassUID.tag,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_after_root_elements() -> Result<()> {
+    let mut expected: Vec<u32> = tags_through_first_item();
+    expected.extend(&tags_through_first_item()[2..]);
+    expected.extend(&[tags::SequenceDelimitationItem.tag, tags::PatientsName.tag]);
+    assert_eq!(parse_tags(TagStop::AfterRootElements(3))?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_after_group() -> Result<()> {
+    let parsed_tags: Vec<u32> = parse_tags(TagStop::AfterGroup(0x0020))?;
+    assert_eq!(parsed_tags.last(), Some(&tags::SeriesNumber.tag));
+    assert_eq!(parsed_tags.len(), 14);
+
+    let parsed_tags: Vec<u32> = parse_tags(TagStop::AfterGroup(0x0008))?;
+    assert_eq!(
+        parsed_tags.last(),
+        Some(&tags::SequenceDelimitationItem.tag)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_after_predicate() -> Result<()> {
+    let tagstop: TagStop =
+        TagStop::after_predicate(|element| element.tag == tags::ReferencedSOPInstanceUID.tag);
+    assert_eq!(parse_tags(tagstop)?, &tags_through_first_item()[..5]);
+    Ok(())
+}
+
+#[test]
+fn test_push_parser_stops() -> Result<()> {
+    let dataset: Vec<u8> = create_dataset();
+    let tagpath: TagPath = TagPath::from(vec![TagNode::new(
+        tags::ReferencedImageSequence.tag,
+        Some(1),
+    )]);
+    let mut parser: PushParser<'_> = builder()
+        .tagstop(TagStop::AfterTagPath(tagpath))
+        .build_push();
+
+    let mut parsed_tags: Vec<u32> = Vec::new();
+    let mut ended: bool = false;
+    for byte in dataset.chunks(1) {
+        parser.feed(byte);
+        loop {
+            match parser.next_event()? {
+                PushEvent::Element(element) => parsed_tags.push(element.tag),
+                PushEvent::NeedMoreData => break,
+                PushEvent::EndOfDataset => {
+                    ended = true;
+                    break;
+                }
+            }
+        }
+        if ended {
+            break;
+        }
+    }
+
+    assert!(ended);
+    assert_eq!(parsed_tags, tags_through_first_item());
+    Ok(())
+}
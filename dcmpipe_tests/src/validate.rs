@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+
+use dcmpipe_dict::dict::stdlookup::STANDARD_DICOM_DICTIONARY;
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_dict::dict::uids;
+use dcmpipe_lib::core::charset::DEFAULT_CHARACTER_SET;
+use dcmpipe_lib::core::dcmelement::DicomElement;
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
+use dcmpipe_lib::core::dcmobject::{DicomObject, DicomRoot};
+use dcmpipe_lib::core::validate::{validate, ValidationIssueKind, ValidationResult};
+use dcmpipe_lib::defn::dcmdict::DicomDictionary;
+use dcmpipe_lib::defn::iod::{IODModule, IODRef, ModuleUsage, IOD};
+use dcmpipe_lib::defn::module::{AttributeType, Module, ModuleAttribute};
+use dcmpipe_lib::defn::tag::{TagPath, TagRef};
+use dcmpipe_lib::defn::ts::TSRef;
+use dcmpipe_lib::defn::uid::UIDRef;
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr::{self, VRRef};
+
+static TEST_PATIENT_MODULE: Module = Module {
+    ident: "TestPatientModule",
+    name: "Test Patient Module",
+    reference: "C.7.1.1",
+    attributes: &[
+        ModuleAttribute {
+            tag: 0x0010_0010,
+            attr_type: AttributeType::Type2,
+            items: &[],
+        },
+        ModuleAttribute {
+            tag: 0x0010_0020,
+            attr_type: AttributeType::Type2,
+            items: &[],
+        },
+        ModuleAttribute {
+            tag: 0x0010_0030,
+            attr_type: AttributeType::Type1,
+            items: &[],
+        },
+    ],
+};
+
+static TEST_STUDY_MODULE: Module = Module {
+    ident: "TestStudyModule",
+    name: "Test Study Module",
+    reference: "C.7.2.1",
+    attributes: &[ModuleAttribute {
+        tag: 0x0020_000D,
+        attr_type: AttributeType::Type1,
+        items: &[],
+    }],
+};
+
+static TEST_IOD: IOD = IOD {
+    ident: "TestIOD",
+    name: "Test IOD",
+    reference: "A.3",
+    modules: &[
+        IODModule {
+            ie: "Patient",
+            module: &TEST_PATIENT_MODULE,
+            usage: ModuleUsage::Mandatory,
+        },
+        IODModule {
+            ie: "Study",
+            module: &TEST_STUDY_MODULE,
+            usage: ModuleUsage::UserOption,
+        },
+    ],
+};
+
+static TEST_IOD_DICTIONARY: TestIODDictionary = TestIODDictionary {};
+
+/// Looks up everything in the standard dictionary, except for the IOD of the CT Image Storage
+/// SOP Class, which is `TEST_IOD`.
+struct TestIODDictionary {}
+impl DicomDictionary for TestIODDictionary {
+    fn get_ts_by_uid(&self, uid: &str) -> Option<TSRef> {
+        STANDARD_DICOM_DICTIONARY.get_ts_by_uid(uid)
+    }
+
+    fn get_ts_by_name(&self, name: &str) -> Option<TSRef> {
+        STANDARD_DICOM_DICTIONARY.get_ts_by_name(name)
+    }
+
+    fn get_tag_by_number(&self, number: u32) -> Option<TagRef> {
+        STANDARD_DICOM_DICTIONARY.get_tag_by_number(number)
+    }
+
+    fn get_tag_by_name(&self, name: &str) -> Option<TagRef> {
+        STANDARD_DICOM_DICTIONARY.get_tag_by_name(name)
+    }
+
+    fn get_uid_by_uid(&self, uid: &str) -> Option<UIDRef> {
+        STANDARD_DICOM_DICTIONARY.get_uid_by_uid(uid)
+    }
+
+    fn get_uid_by_name(&self, name: &str) -> Option<UIDRef> {
+        STANDARD_DICOM_DICTIONARY.get_uid_by_name(name)
+    }
+
+    fn get_iod_by_sop_class_uid(&self, uid: &str) -> Option<IODRef> {
+        if uid == uids::CTImageStorage.uid {
+            Some(&TEST_IOD)
+        } else {
+            None
+        }
+    }
+}
+
+fn element(tag: u32, vr: VRRef, data: &[u8]) -> (u32, DicomObject) {
+    let elem: DicomElement = DicomElement::new(
+        tag,
+        vr,
+        ValueLength::Explicit(data.len() as u32),
+        &ts::ExplicitVRLittleEndian,
+        DEFAULT_CHARACTER_SET,
+        data.to_vec(),
+        SequencePath::new(),
+    );
+    (tag, DicomObject::new(elem))
+}
+
+fn create_root(elements: Vec<(u32, DicomObject)>) -> DicomRoot<'static> {
+    create_root_with_dictionary(&STANDARD_DICOM_DICTIONARY, elements)
+}
+
+fn create_root_with_dictionary(
+    dictionary: &'static dyn DicomDictionary,
+    elements: Vec<(u32, DicomObject)>,
+) -> DicomRoot<'static> {
+    let child_nodes: BTreeMap<u32, DicomObject> = elements.into_iter().collect();
+    DicomRoot::new(
+        &ts::ExplicitVRLittleEndian,
+        DEFAULT_CHARACTER_SET,
+        dictionary,
+        child_nodes,
+    )
+}
+
+fn has_issue(result: &ValidationResult, kind: ValidationIssueKind, tag: u32) -> bool {
+    let tagpath: TagPath = tag.into();
+    result
+        .issues
+        .iter()
+        .any(|issue| issue.kind == kind && issue.tagpath == tagpath)
+}
+
+#[test]
+fn test_validate_element_values() {
+    let dcmroot: DicomRoot<'_> = create_root(vec![
+        element(tags::MediaStorageSOPInstanceUID.tag, &vr::UI, b"1.2.3.4\0"),
+        element(tags::SOPInstanceUID.tag, &vr::UI, b"1.2.3.5\0"),
+        element(tags::Modality.tag, &vr::CS, b"ct"),
+        element(tags::StudyDate.tag, &vr::DA, b"20201301"),
+        element(tags::StudyTime.tag, &vr::TM, b"12300"),
+        element(tags::SeriesDate.tag, &vr::DA, b"20200230"),
+        element(tags::AcquisitionDateTime.tag, &vr::DT, b"2020123124"),
+        element(
+            tags::InstanceCoercionDateTime.tag,
+            &vr::DT,
+            b"20201231235959.50-0500",
+        ),
+        element(tags::PatientsName.tag, &vr::LO, b"Doe^John"),
+        element(tags::ImageOrientationPatient.tag, &vr::DS, b"1\\0\\0 "),
+        element(tags::Rows.tag, &vr::US, &[0x00, 0x02, 0x00, 0x02]),
+        element(tags::InstanceNumber.tag, &vr::IS, b"12.0"),
+        element(tags::PatientsAge.tag, &vr::AS, b"045Y"),
+    ]);
+
+    let result: ValidationResult = validate(&dcmroot);
+    assert!(!result.is_valid());
+    assert!(result.iod.is_none());
+
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::FileMetaMismatch,
+        tags::MediaStorageSOPInstanceUID.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::Modality.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::StudyDate.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::OddLength,
+        tags::StudyTime.tag
+    ));
+    // days past the end of the month and hours past 23 are invalid
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::SeriesDate.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::AcquisitionDateTime.tag
+    ));
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::InstanceCoercionDateTime.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::VRMismatch,
+        tags::PatientsName.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::VMViolation,
+        tags::ImageOrientationPatient.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::VMViolation,
+        tags::Rows.tag
+    ));
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::InstanceNumber.tag
+    ));
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::InvalidValue,
+        tags::PatientsAge.tag
+    ));
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::FileMetaMismatch,
+        tags::SOPInstanceUID.tag
+    ));
+}
+
+#[test]
+fn test_validate_iod_attributes() {
+    let sop_class_uid: String = format!("{}\0", uids::CTImageStorage.uid);
+    let dcmroot: DicomRoot<'_> = create_root_with_dictionary(
+        &TEST_IOD_DICTIONARY,
+        vec![
+            element(tags::SOPClassUID.tag, &vr::UI, sop_class_uid.as_bytes()),
+            element(tags::PatientID.tag, &vr::LO, b""),
+            element(tags::PatientsBirthDate.tag, &vr::DA, b""),
+        ],
+    );
+
+    let result: ValidationResult = validate(&dcmroot);
+    assert_eq!(result.iod, Some("TestIOD"));
+
+    // Patient's Name is Type 2 in the mandatory module and must be present
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::MissingAttribute,
+        tags::PatientsName.tag
+    ));
+    // Patient ID is Type 2 and may be empty
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::MissingAttribute,
+        tags::PatientID.tag
+    ));
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::EmptyValue,
+        tags::PatientID.tag
+    ));
+    // Patient's Birth Date is Type 1 and must have a value
+    assert!(has_issue(
+        &result,
+        ValidationIssueKind::EmptyValue,
+        tags::PatientsBirthDate.tag
+    ));
+    // attributes of modules which aren't mandatory for the IOD aren't required
+    assert!(!has_issue(
+        &result,
+        ValidationIssueKind::MissingAttribute,
+        tags::StudyInstanceUID.tag
+    ));
+}
@@ -0,0 +1,218 @@
+use std::convert::TryFrom;
+
+use dcmpipe_dict::dict::tags;
+use dcmpipe_dict::dict::transfer_syntaxes as ts;
+use dcmpipe_lib::core::charset::DEFAULT_CHARACTER_SET;
+use dcmpipe_lib::core::dcmelement::{DicomElement, RawValue};
+use dcmpipe_lib::core::dcmsqelem::SequencePath;
+use dcmpipe_lib::core::numeric::{self, Age, AgeUnit, InvalidNumericString, NumericStringMode};
+use dcmpipe_lib::core::parser::Result;
+use dcmpipe_lib::defn::ts::TSRef;
+use dcmpipe_lib::defn::vl::ValueLength;
+use dcmpipe_lib::defn::vr::{self, VRRef};
+
+fn element(vr: VRRef, ts: TSRef, bytes: Vec<u8>) -> DicomElement {
+    element_with_tag(tags::ExtendedOffsetTable.tag, vr, ts, bytes)
+}
+
+fn element_with_tag(tag: u32, vr: VRRef, ts: TSRef, bytes: Vec<u8>) -> DicomElement {
+    DicomElement::new(
+        tag,
+        vr,
+        ValueLength::Explicit(bytes.len() as u32),
+        ts,
+        DEFAULT_CHARACTER_SET,
+        bytes,
+        SequencePath::new(),
+    )
+}
+
+#[test]
+fn test_signed_very_long_values() -> Result<()> {
+    let values: [i64; 3] = [-2, i64::MAX, i64::MIN];
+    let le_bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    let be_bytes: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+
+    let le_elem: DicomElement = element(&vr::SV, &ts::ExplicitVRLittleEndian, le_bytes);
+    assert_eq!(Vec::<i64>::try_from(&le_elem)?, values.to_vec());
+    assert_eq!(i64::try_from(&le_elem)?, -2);
+
+    let be_elem: DicomElement = element(&vr::SV, &ts::ExplicitVRBigEndian, be_bytes);
+    assert_eq!(Vec::<i64>::try_from(&be_elem)?, values.to_vec());
+
+    match le_elem.parse_value()? {
+        RawValue::Longs(longs) => assert_eq!(longs, values.to_vec()),
+        _ => panic!("SV should parse as longs"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_unsigned_very_long_values() -> Result<()> {
+    let values: [u64; 3] = [1, 0x0102_0304_0506_0708, u64::MAX];
+    let le_bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    let be_bytes: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+
+    let uv_elem: DicomElement = element(&vr::UV, &ts::ExplicitVRLittleEndian, le_bytes.clone());
+    assert_eq!(Vec::<u64>::try_from(&uv_elem)?, values.to_vec());
+    assert_eq!(u64::try_from(&uv_elem)?, 1);
+
+    let be_elem: DicomElement = element(&vr::UV, &ts::ExplicitVRBigEndian, be_bytes);
+    assert_eq!(Vec::<u64>::try_from(&be_elem)?, values.to_vec());
+
+    let ov_elem: DicomElement = element(&vr::OV, &ts::ExplicitVRLittleEndian, le_bytes);
+    match ov_elem.parse_value()? {
+        RawValue::UnsignedLongs(ulongs) => assert_eq!(ulongs, values.to_vec()),
+        _ => panic!("OV should parse as unsigned longs"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_very_long_values_invalid_length() {
+    let elem: DicomElement = element(&vr::UV, &ts::ExplicitVRLittleEndian, vec![0u8; 12]);
+    assert!(Vec::<u64>::try_from(&elem).is_err());
+}
+
+#[test]
+fn test_age_values() -> Result<()> {
+    let elem: DicomElement = element_with_tag(
+        tags::PatientsAge.tag,
+        &vr::AS,
+        &ts::ExplicitVRLittleEndian,
+        b"045Y".to_vec(),
+    );
+    let age: Age = Age::try_from(&elem)?;
+    assert_eq!(
+        age,
+        Age {
+            count: 45,
+            unit: AgeUnit::Years
+        }
+    );
+    assert_eq!(age.to_string(), "045Y");
+
+    assert_eq!(
+        "003M".parse::<Age>(),
+        Ok(Age {
+            count: 3,
+            unit: AgeUnit::Months
+        })
+    );
+    assert!("45Y".parse::<Age>().is_err());
+    assert!("045y".parse::<Age>().is_err());
+    assert!("0451".parse::<Age>().is_err());
+    assert!("+45Y".parse::<Age>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decimal_string_modes() {
+    let strict = |value: &str| numeric::parse_decimal_string(value, NumericStringMode::Strict);
+    let lenient = |value: &str| numeric::parse_decimal_string(value, NumericStringMode::Lenient);
+
+    assert_eq!(strict(" -1.5E+3 "), Ok(-1500.0));
+    assert_eq!(strict(".5"), Ok(0.5));
+    assert_eq!(strict("2."), Ok(2.0));
+
+    let long: &str = "0.12345678901234567";
+    assert_eq!(
+        strict(long).map_err(|e| e.reason),
+        Err("exceeds 16 characters")
+    );
+    assert!(lenient(long).is_ok());
+
+    assert!(strict("\t1.5").is_err());
+    assert_eq!(lenient("\t1.5\0"), Ok(1.5));
+
+    let invalid: InvalidNumericString = lenient("1.2.3").unwrap_err();
+    assert_eq!(invalid.vr, "DS");
+    assert_eq!(invalid.reason, "expected a fixed or floating point number");
+    assert!(lenient("inf").is_err());
+    assert!(lenient("NaN").is_err());
+    assert!(lenient("1e999").is_err());
+    assert!(lenient("E5").is_err());
+}
+
+#[test]
+fn test_integer_string_modes() {
+    let strict = |value: &str| numeric::parse_integer_string(value, NumericStringMode::Strict);
+    let lenient = |value: &str| numeric::parse_integer_string(value, NumericStringMode::Lenient);
+
+    assert_eq!(strict(" +42"), Ok(42));
+    assert_eq!(strict("-2147483648"), Ok(i32::MIN));
+    assert_eq!(
+        strict("2147483648").map_err(|e| e.reason),
+        Err("value is out of range")
+    );
+    assert_eq!(
+        strict("0000000000001").map_err(|e| e.reason),
+        Err("exceeds 12 characters")
+    );
+
+    assert!(strict("12.0").is_err());
+    assert_eq!(lenient("12.0"), Ok(12));
+    assert_eq!(lenient("1E2"), Ok(100));
+    assert_eq!(
+        lenient("12.5").map_err(|e| e.reason),
+        Err("value has a fractional part")
+    );
+    assert_eq!(
+        lenient("3E9").map_err(|e| e.reason),
+        Err("value is out of range")
+    );
+    assert!(lenient("twelve").is_err());
+}
+
+#[test]
+fn test_parse_value_numeric_strings() -> Result<()> {
+    let ds: DicomElement = element_with_tag(
+        tags::ImageOrientationPatient.tag,
+        &vr::DS,
+        &ts::ExplicitVRLittleEndian,
+        b"0.5\\12.0 ".to_vec(),
+    );
+    match ds.parse_value_with_mode(NumericStringMode::Strict)? {
+        RawValue::Doubles(doubles) => assert_eq!(doubles, vec![0.5, 12.0]),
+        _ => panic!("DS should parse as doubles"),
+    }
+
+    // previously parsed as a float and truncated, now kept as encoded
+    let is: DicomElement = element_with_tag(
+        tags::InstanceNumber.tag,
+        &vr::IS,
+        &ts::ExplicitVRLittleEndian,
+        b"12.5".to_vec(),
+    );
+    assert!(is.parse_value_with_mode(NumericStringMode::Strict).is_err());
+    match is.parse_value()? {
+        RawValue::Strings(strings) => assert_eq!(strings, vec!["12.5".to_owned()]),
+        _ => panic!("malformed IS should parse as strings"),
+    }
+
+    let is: DicomElement = element_with_tag(
+        tags::InstanceNumber.tag,
+        &vr::IS,
+        &ts::ExplicitVRLittleEndian,
+        b"12.0".to_vec(),
+    );
+    assert!(is.parse_value_with_mode(NumericStringMode::Strict).is_err());
+    match is.parse_value()? {
+        RawValue::Integers(ints) => assert_eq!(ints, vec![12]),
+        _ => panic!("IS should parse as integers"),
+    }
+
+    // malformed values are only an error in strict mode
+    let ds: DicomElement = element_with_tag(
+        tags::SliceThickness.tag,
+        &vr::DS,
+        &ts::ExplicitVRLittleEndian,
+        b"1,25".to_vec(),
+    );
+    assert!(ds.parse_value_with_mode(NumericStringMode::Strict).is_err());
+    match ds.parse_value()? {
+        RawValue::Strings(strings) => assert_eq!(strings, vec!["1,25".to_owned()]),
+        _ => panic!("malformed DS should parse as strings"),
+    }
+    Ok(())
+}